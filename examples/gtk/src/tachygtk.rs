@@ -171,6 +171,12 @@ impl Renderer for TachyGtk {
         todo!()
     }
 
+    fn tag_name(el: &Self::Element) -> String {
+        // widgets don't have tag names, but their CSS names (like `label`
+        // or `button`) serve the same purpose
+        el.0.css_name().to_string()
+    }
+
     fn clear_children(parent: &Self::Element) {
         todo!()
     }
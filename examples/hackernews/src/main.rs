@@ -26,7 +26,10 @@ mod ssr {
         use futures::StreamExt;
         use tachy_reaccy::{context::provide_context, Owner, Root};
         use tachy_route::{location::RequestUrl, PathSegment, RouteList};
-        use tachys::tachydom::{renderer::dom::Dom, view::RenderHtml};
+        use tachys::{
+            nonce::{inline_script, Nonce},
+            tachydom::{renderer::dom::Dom, view::RenderHtml},
+        };
 
         trait ActixPath {
             fn to_actix_path(&self) -> String;
@@ -114,37 +117,45 @@ mod ssr {
                             let additional_context = additional_context.clone();
 
                             async move {
-                                let Root(owner, stream) =
+                                let Root(owner, (nonce, stream)) =
                                     Root::global_ssr_islands(move || {
                                         // provide contexts
                                         let path = req.path();
                                         println!("inside handler for {path}");
+                                        let nonce = Nonce::new();
+                                        provide_context(nonce.clone());
                                         additional_context();
-                                        provide_context(RequestUrl::from_path(
-                                            path,
-                                        ));
+                                        provide_context(
+                                            RequestUrl::from_path(path),
+                                        );
                                         // TODO provide HttpRequest
 
                                         // run app
                                         let app = app_fn();
 
                                         // convert app to appropriate response type
-                                        let app_stream =
-                                            app.to_html_stream_out_of_order();
+                                        let app_stream = app
+                                            .to_html_stream_out_of_order_with_nonce(
+                                                Some(nonce.to_arc()),
+                                            );
                                         let shared_context =
                                             Owner::shared_context().unwrap();
-                                        // TODO nonce
+                                        let script_nonce = nonce.clone();
                                         let shared_context = shared_context
                                             .pending_data()
                                             .unwrap()
-                                            .map(|chunk| {
-                                                format!(
-                                                    "<script>{chunk}</script>"
+                                            .map(move |chunk| {
+                                                inline_script(
+                                                    Some(&script_nonce),
+                                                    &chunk,
                                                 )
                                             });
-                                        futures::stream::select(
-                                            app_stream,
-                                            shared_context,
+                                        (
+                                            nonce,
+                                            futures::stream::select(
+                                                app_stream,
+                                                shared_context,
+                                            ),
                                         )
                                     });
                                 std::mem::forget(owner); // TOOD close leak
@@ -153,6 +164,14 @@ mod ssr {
                                     .content_type(
                                         http::header::ContentType::html(),
                                     )
+                                    .insert_header((
+                                        http::header::CONTENT_SECURITY_POLICY,
+                                        format!(
+                                            "script-src 'self' \
+                                             'nonce-{nonce}' \
+                                             'wasm-unsafe-eval'"
+                                        ),
+                                    ))
                                     .streaming({
                                         stream.map(|html| {
                                             Ok(web::Bytes::from(html))
@@ -105,9 +105,9 @@ mod ssr {
                                             .as_str();
                                         println!("path = {path:?}");
                                         additional_context();
-                                        provide_context(RequestUrl::from_path(
-                                            path,
-                                        ));
+                                        provide_context(
+                                            RequestUrl::from_path(path),
+                                        );
                                         // TODO provide Request
 
                                         // run app
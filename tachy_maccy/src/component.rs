@@ -9,14 +9,16 @@ use proc_macro2::{Ident, Span, TokenStream};
 use proc_macro_error::abort;
 use quote::{format_ident, quote, quote_spanned, ToTokens, TokenStreamExt};
 use syn::{
-    parse::Parse, parse_quote, spanned::Spanned,
-    AngleBracketedGenericArguments, Attribute, FnArg, GenericArgument, Item,
-    ItemFn, LitStr, Meta, Pat, PatIdent, Path, PathArguments, ReturnType,
-    Signature, Stmt, Type, TypePath, Visibility,
+    ext::IdentExt, parse::Parse, parse_quote, spanned::Spanned,
+    AngleBracketedGenericArguments, Attribute, Field, FnArg, GenericArgument,
+    Item, ItemFn, LitStr, Meta, Pat, PatIdent, Path, PathArguments, ReturnType,
+    Signature, Stmt, Token, Type, TypePath, Visibility,
 };
 
 pub struct Model {
     is_island: bool,
+    serializer: Option<Path>,
+    hydrate: Option<TokenStream>,
     docs: Docs,
     vis: Visibility,
     name: Ident,
@@ -58,6 +60,8 @@ impl Parse for Model {
 
         Ok(Self {
             is_island: false,
+            serializer: None,
+            hydrate: None,
             docs,
             vis: item.vis.clone(),
             name: convert_from_snake_case(&item.sig.ident),
@@ -97,6 +101,8 @@ impl ToTokens for Model {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Self {
             is_island,
+            serializer,
+            hydrate,
             docs,
             vis,
             name,
@@ -135,7 +141,6 @@ impl ToTokens for Model {
 
         let props_name = format_ident!("{name}Props");
         let props_builder_name = format_ident!("{name}PropsBuilder");
-        let props_serialized_name = format_ident!("{name}PropsSerialized");
         let trace_name = format!("<{name} />");
 
         let is_island_with_children = *is_island
@@ -144,19 +149,18 @@ impl ToTokens for Model {
             && ((is_island_with_children && props.len() > 1)
                 || (!is_island_with_children && !props.is_empty()));
 
-        let prop_builder_fields =
-            prop_builder_fields(vis, props, is_island_with_other_props);
-        let props_serializer = if is_island_with_other_props {
-            let fields = prop_serializer_fields(vis, props);
-            quote! {
-                #[derive(::leptos::serde::Deserialize)]
-                #vis struct #props_serialized_name {
-                    #fields
-                }
-            }
-        } else {
-            quote! {}
-        };
+        let prop_builder_fields = prop_builder_fields(vis, props);
+        // every prop except `children` is serialized into the island's tag
+        let serialized_props = props
+            .iter()
+            .filter(|prop| prop.name.ident != "children")
+            .collect::<Vec<_>>();
+        let serializer = serializer
+            .as_ref()
+            .map(ToTokens::to_token_stream)
+            .unwrap_or_else(|| {
+                quote! { ::tachys::tachy_reaccy::serialization::SerdeJson }
+            });
 
         let prop_names = prop_names(props);
 
@@ -205,20 +209,37 @@ impl ToTokens for Model {
             Ident::new(&format!("_island_{}", component_id), name.span());
 
         let island_serialize_props = if is_island_with_other_props {
+            let serialized = serialized_props.iter().map(|prop| {
+                let ident = &prop.name.ident;
+                let prop_name = ident.unraw().to_string();
+                let ty = &prop.ty;
+                quote_spanned! {ty.span()=>
+                    ::tachys::islands::serialize_prop::<#serializer, #ty>(
+                        #prop_name,
+                        &#ident
+                    )
+                }
+            });
             quote! {
-                let _leptos_ser_props = ::leptos::serde_json::to_string(&props).expect("couldn't serialize island props");
+                let _island_props = ::std::vec![#(#serialized),*];
             }
         } else {
             quote! {}
         };
         let island_serialized_props = if is_island_with_other_props {
             quote! {
-                .attr("data-props", _leptos_ser_props)
+                .with_props(_island_props)
             }
         } else {
             quote! {}
         };
 
+        let island_trigger = hydrate.as_ref().map(|trigger| {
+            quote! {
+                .with_trigger(#trigger)
+            }
+        });
+
         let body_name = unmodified_fn_name_from_fn_name(&body_name);
         let body_expr = if *is_island {
             quote! {
@@ -250,7 +271,8 @@ impl ToTokens for Model {
                         #component_id,
                         #component
                     )
-                        // #island_serialized_props
+                    #island_serialized_props
+                    #island_trigger
                 }
             }
         } else {
@@ -277,47 +299,14 @@ impl ToTokens for Model {
                 quote! {}
             };
             quote! {
-                #island_serialize_props
                 let #props_name {
                     #prop_names
                 } = props;
+                #island_serialize_props
                 #wrapped_children
             }
         };
 
-        let count = props
-            .iter()
-            .filter(
-                |Prop {
-                     prop_opts: PropOpt { attrs, .. },
-                     ..
-                 }| *attrs,
-            )
-            .count();
-
-        let dyn_attrs_props = props
-            .iter()
-            .filter(
-                |Prop {
-                     prop_opts: PropOpt { attrs, .. },
-                     ..
-                 }| *attrs,
-            )
-            .enumerate()
-            .map(|(idx, Prop { name, .. })| {
-                let ident = &name.ident;
-                if idx < count - 1 {
-                    quote! {
-                        self.#ident = v.clone().into();
-                    }
-                } else {
-                    quote! {
-                        self.#ident = v.into();
-                    }
-                }
-            })
-            .collect::<TokenStream>();
-
         let body = quote! {
             #destructure_props
             #tracing_span_expr
@@ -328,88 +317,60 @@ impl ToTokens for Model {
             let island_props = if is_island_with_children
                 || is_island_with_other_props
             {
-                let (destructure, prop_builders) = if is_island_with_other_props
-                {
-                    let prop_names = props
-                        .iter()
-                        .filter_map(|prop| {
-                            if prop.name.ident == "children" {
-                                None
-                            } else {
-                                let name = &prop.name.ident;
-                                Some(quote! { #name, })
-                            }
-                        })
-                        .collect::<TokenStream>();
-                    let destructure = quote! {
-                        let #props_serialized_name {
-                            #prop_names
-                        } = props;
-                    };
-                    let prop_builders = props
-                        .iter()
-                        .filter_map(|prop| {
-                            if prop.name.ident == "children" {
-                                None
-                            } else {
-                                let name = &prop.name.ident;
-                                Some(quote! {
-                                    .#name(#name)
-                                })
-                            }
-                        })
-                        .collect::<TokenStream>();
-                    (destructure, prop_builders)
-                } else {
-                    (quote! {}, quote! {})
-                };
+                // the props are deserialized from the attributes of the
+                // island's tag, and the struct is built directly, so that
+                // optional props don't need to go through their setters
+                let deserialize = serialized_props.iter().map(|prop| {
+                    let ident = &prop.name.ident;
+                    let prop_name = ident.unraw().to_string();
+                    let ty = &prop.ty;
+                    quote_spanned! {ty.span()=>
+                        let #ident = ::tachys::islands::deserialize_prop::<
+                            #serializer,
+                            #ty,
+                        >(
+                            #prop_name,
+                            el.get_attribute(
+                                &::tachys::tachydom::html::islands::prop_attribute(
+                                    #prop_name
+                                )
+                            ),
+                        );
+                    }
+                });
+                let fields = serialized_props.iter().map(|prop| {
+                    let ident = &prop.name.ident;
+                    quote! { #ident, }
+                });
                 let children = if is_island_with_children {
                     quote! {
-                        .children({Box::new(|| {
-                            use tachys::tachydom::view::any_view::IntoAny;
-                            ::tachys::tachydom::html::islands::IslandChildren::new(
-                                // TODO owner restoration for context
-                                ()
-                            ).into_any()})})
-                        //.children(children)
-                        /*.children(Box::new(|| {
+                        children: Box::new(|| {
                             use tachys::tachydom::view::any_view::IntoAny;
                             ::tachys::tachydom::html::islands::IslandChildren::new(
                                 // TODO owner restoration for context
                                 ()
                             ).into_any()
-                        }))*/
+                        }),
                     }
                 } else {
                     quote! {}
                 };
 
                 quote! {{
-                    #destructure
-                    #props_name::builder()
-                        #prop_builders
+                    #(#deserialize)*
+                    #props_name {
+                        #(#fields)*
                         #children
-                        .build()
+                    }
                 }}
             } else {
                 quote! {}
             };
-            let deserialize_island_props = quote! {}; /*if is_island_with_other_props {
-                                                          quote! {
-                                                              let props = el.dataset().get("props") // TODO ::leptos::wasm_bindgen::intern("props"))
-                                                                  .and_then(|data| ::leptos::serde_json::from_str::<#props_serialized_name>(&data).ok())
-                                                                  .expect("could not deserialize props");
-                                                          }
-                                                      } else {
-                                                          quote! {}
-                                                      };*/
-            // TODO
 
             quote! {
                 #[::tachys::tachydom::wasm_bindgen::prelude::wasm_bindgen]
                 #[allow(non_snake_case)]
                 pub fn #hydrate_fn_name(el: ::tachys::tachydom::web_sys::HtmlElement) {
-                    #deserialize_island_props
                     let island = #name(#island_props);
                     let state = island.hydrate_from_position::<true>(&el, ::tachys::tachydom::view::Position::Current);
                     // TODO better cleanup
@@ -420,19 +381,13 @@ impl ToTokens for Model {
             quote! {}
         };
 
-        let props_derive_serialize = if is_island_with_other_props {
-            quote! { , ::leptos::serde::Serialize }
-        } else {
-            quote! {}
-        };
-
         let output = quote! {
             #[doc = #builder_name_doc]
             #[doc = ""]
             #docs
             #[doc = ""]
             #component_fn_prop_docs
-            #[derive(::tachys::typed_builder_macro::TypedBuilder #props_derive_serialize)]
+            #[derive(::tachys::typed_builder_macro::TypedBuilder)]
             //#[builder(doc)]
             #[builder(crate_module_path=::tachys::typed_builder)]
             #[allow(non_snake_case)]
@@ -440,8 +395,6 @@ impl ToTokens for Model {
                 #prop_builder_fields
             }
 
-            #props_serializer
-
             #[allow(missing_docs)]
             #binding
 
@@ -453,14 +406,6 @@ impl ToTokens for Model {
                 }
             }
 
-            // TODO restore dyn attrs
-            /*impl #impl_generics ::leptos::DynAttrs for #props_name #generics #where_clause {
-                fn dyn_attrs(mut self, v: Vec<(&'static str, ::leptos::Attribute)>) -> Self {
-                    #dyn_attrs_props
-                    self
-                }
-            } */
-
             #docs
             #[doc = ""]
             #component_fn_prop_docs
@@ -487,6 +432,79 @@ impl Model {
 
         self
     }
+
+    /// Sets the serializer for the props and the hydration trigger of an
+    /// island.
+    pub fn with_island_args(mut self, args: IslandArgs) -> Self {
+        self.serializer = args.serializer;
+        self.hydrate = args.hydrate;
+
+        self
+    }
+}
+
+/// The arguments to `#[island]`, such as
+/// `#[island(serializer = Miniserde, hydrate = "visible")]`.
+#[derive(Default)]
+pub struct IslandArgs {
+    pub serializer: Option<Path>,
+    pub hydrate: Option<TokenStream>,
+}
+
+impl Parse for IslandArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut args = Self::default();
+        while !input.is_empty() {
+            let key = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            if key == "serializer" {
+                args.serializer = Some(input.parse::<Path>()?);
+            } else if key == "hydrate" {
+                args.hydrate = Some(hydration_trigger(&input.parse()?)?);
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "unknown island argument; expected `serializer` or \
+                     `hydrate`",
+                ));
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// Parses a hydration trigger, such as `"visible"` or
+/// `"media((min-width: 800px))"`, into a `HydrationTrigger`.
+fn hydration_trigger(trigger: &LitStr) -> syn::Result<TokenStream> {
+    let value = trigger.value();
+    let variant = match value.as_str() {
+        "load" => quote! { Load },
+        "idle" => quote! { Idle },
+        "visible" => quote! { Visible },
+        "interaction" => quote! { Interaction },
+        _ => match value
+            .strip_prefix("media(")
+            .and_then(|query| query.strip_suffix(')'))
+        {
+            Some(query) if !query.trim().is_empty() => {
+                let query = query.trim();
+                quote! { Media(#query) }
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    trigger.span(),
+                    "unknown hydration trigger; expected `load`, `idle`, \
+                     `visible`, `interaction` or `media(<query>)`",
+                ))
+            }
+        },
+    };
+    Ok(quote! {
+        ::tachys::tachydom::html::islands::HydrationTrigger::#variant
+    })
 }
 
 /// A model that is more lenient in case of a syntax error in the function body,
@@ -564,7 +582,7 @@ impl ToTokens for DummyModel {
     }
 }
 
-struct Prop {
+pub(crate) struct Prop {
     docs: Docs,
     prop_opts: PropOpt,
     name: PatIdent,
@@ -602,6 +620,31 @@ impl Prop {
             ty: *typed.ty,
         }
     }
+
+    /// Creates a prop from a named field of a `#[slot]` struct.
+    pub(crate) fn from_field(field: &Field) -> Self {
+        let prop_opts =
+            PropOpt::from_attributes(&field.attrs).unwrap_or_else(|e| {
+                abort!(e.span(), e.to_string());
+            });
+
+        let Some(ident) = field.ident.clone() else {
+            abort!(field, "only named fields are allowed within a `#[slot]`");
+        };
+
+        Self {
+            docs: Docs::new(&field.attrs),
+            prop_opts,
+            name: PatIdent {
+                attrs: vec![],
+                by_ref: None,
+                mutability: None,
+                ident,
+                subpat: None,
+            },
+            ty: field.ty.clone(),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -789,25 +832,6 @@ impl TypedBuilderOpts {
     }
 }
 
-impl TypedBuilderOpts {
-    fn to_serde_tokens(&self) -> TokenStream {
-        let default = if let Some(v) = &self.default_with_value {
-            let v = v.to_token_stream().to_string();
-            quote! { default=#v, }
-        } else if self.default {
-            quote! { default, }
-        } else {
-            quote! {}
-        };
-
-        if !default.is_empty() {
-            quote! { #[serde(#default)] }
-        } else {
-            quote! {}
-        }
-    }
-}
-
 impl ToTokens for TypedBuilderOpts {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let default = if let Some(v) = &self.default_with_value {
@@ -847,10 +871,9 @@ impl ToTokens for TypedBuilderOpts {
     }
 }
 
-fn prop_builder_fields(
+pub(crate) fn prop_builder_fields(
     vis: &Visibility,
     props: &[Prop],
-    is_island_with_other_props: bool,
 ) -> TokenStream {
     props
         .iter()
@@ -873,13 +896,6 @@ fn prop_builder_fields(
             } else {
                 quote!()
             };
-            let skip_children_serde =
-                if is_island_with_other_props && name.ident == "children" {
-                    quote!(#[serde(skip)])
-                } else {
-                    quote!()
-                };
-
             let PatIdent { ident, by_ref, .. } = &name;
 
             quote! {
@@ -887,43 +903,12 @@ fn prop_builder_fields(
                 #builder_docs
                 #builder_attrs
                 #allow_missing_docs
-                #skip_children_serde
                 #vis #by_ref #ident: #ty,
             }
         })
         .collect()
 }
 
-fn prop_serializer_fields(vis: &Visibility, props: &[Prop]) -> TokenStream {
-    props
-        .iter()
-        .filter_map(|prop| {
-            if prop.name.ident == "children" {
-                None
-            } else {
-                let Prop {
-                    docs,
-                    name,
-                    prop_opts,
-                    ty,
-                } = prop;
-
-                let builder_attrs =
-                    TypedBuilderOpts::from_opts(prop_opts, is_option(ty));
-                let serde_attrs = builder_attrs.to_serde_tokens();
-
-                let PatIdent { ident, by_ref, .. } = &name;
-
-                Some(quote! {
-                    #docs
-                    #serde_attrs
-                    #vis #by_ref #ident: #ty,
-                })
-            }
-        })
-        .collect()
-}
-
 fn prop_names(props: &[Prop]) -> TokenStream {
     props
         .iter()
@@ -936,7 +921,7 @@ fn prop_names(props: &[Prop]) -> TokenStream {
         .collect()
 }
 
-fn generate_component_fn_prop_docs(props: &[Prop]) -> TokenStream {
+pub(crate) fn generate_component_fn_prop_docs(props: &[Prop]) -> TokenStream {
     let required_prop_docs = props
         .iter()
         .filter(|Prop { prop_opts, .. }| {
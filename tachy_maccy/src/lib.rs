@@ -1,7 +1,8 @@
 mod component;
+mod slot;
 mod view;
 use crate::component::unmodified_fn_name_from_fn_name;
-use component::DummyModel;
+use component::{DummyModel, IslandArgs};
 use proc_macro::TokenStream;
 use proc_macro2::TokenTree;
 use proc_macro_error::abort;
@@ -59,27 +60,95 @@ pub fn view(tokens: TokenStream) -> TokenStream {
     .into()
 }
 
+/// Defines a component.
+///
+/// Where the component is used, `attr:`, `class:`, `style:` and `on:`
+/// attributes are added to the element at the root of the component, as in
+/// `<MyButton attr:aria-label="Close" on:click=close/>`. This requires the
+/// component to return `impl RenderHtml<Dom> + AddAnyAttr<Dom>`.
 #[proc_macro_error::proc_macro_error]
 #[proc_macro_attribute]
 pub fn component(
     _args: proc_macro::TokenStream,
     s: TokenStream,
 ) -> TokenStream {
-    component_macro(s, false)
+    component_macro(s, false, IslandArgs::default())
 }
 
+/// Defines an island: a component that is rendered on the server and then
+/// hydrated on its own in the browser.
+///
+/// The island's props (other than `children`) are serialized into its tag,
+/// using [`SerdeJson`] by default. Another serializer can be chosen with
+/// `#[island(serializer = Miniserde)]`; every prop must be serializable with
+/// it.
+///
+/// By default, an island is hydrated as soon as the WASM binary has loaded.
+/// `#[island(hydrate = "...")]` delays hydration until the browser is
+/// `"idle"`, until the island is `"visible"`, until the first
+/// `"interaction"` with it, or until a media query matches, as in
+/// `"media((min-width: 800px))"`.
+///
+/// [`SerdeJson`]: https://docs.rs/tachy_reaccy/latest/tachy_reaccy/serialization/struct.SerdeJson.html
 #[proc_macro_error::proc_macro_error]
 #[proc_macro_attribute]
-pub fn island(_args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
-    component_macro(s, true)
+pub fn island(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
+    let args = match syn::parse::<IslandArgs>(args) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    component_macro(s, true, args)
+}
+
+/// Defines a slot: a struct that is passed to a component as one of its
+/// props, and which is created in the `view` macro as a child of the
+/// component, as in `<Then slot>"yes"</Then>`.
+///
+/// The fields of the struct are its props, with the same `#[prop(...)]`
+/// options as a component. A component takes a slot as a prop with its name
+/// in snake case, or with the name given in `slot:name`. It can take an
+/// `Option` of it, with `#[prop(optional)]`, or a `Vec` of it, with
+/// `#[prop(default = vec![], into)]` so that a single slot can be passed too.
+///
+/// ```rust,ignore
+/// #[slot]
+/// struct Then {
+///     children: ChildrenFn,
+/// }
+///
+/// #[component]
+/// fn If(condition: Signal<bool>, then: Then) -> impl RenderHtml<Dom> {
+///     move || condition.get().then(|| (then.children)())
+/// }
+///
+/// view! {
+///     <If condition=is_on>
+///         <Then slot>"on"</Then>
+///     </If>
+/// }
+/// ```
+#[proc_macro_error::proc_macro_error]
+#[proc_macro_attribute]
+pub fn slot(_args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
+    match syn::parse::<slot::Model>(s) {
+        Ok(model) => model.into_token_stream().into(),
+        Err(e) => e.to_compile_error().into(),
+    }
 }
 
-fn component_macro(s: TokenStream, island: bool) -> TokenStream {
+fn component_macro(
+    s: TokenStream,
+    island: bool,
+    island_args: IslandArgs,
+) -> TokenStream {
     let mut dummy = syn::parse::<DummyModel>(s.clone());
     let parse_result = syn::parse::<component::Model>(s);
 
     if let (Ok(ref mut unexpanded), Ok(model)) = (&mut dummy, parse_result) {
-        let expanded = model.is_island(island).into_token_stream();
+        let expanded = model
+            .is_island(island)
+            .with_island_args(island_args)
+            .into_token_stream();
         if !matches!(unexpanded.vis, Visibility::Public(_)) {
             unexpanded.vis = Visibility::Public(Pub {
                 span: unexpanded.vis.span(),
@@ -0,0 +1,106 @@
+use crate::component::{
+    generate_component_fn_prop_docs, prop_builder_fields, Docs, Prop,
+};
+use proc_macro2::{Ident, TokenStream};
+use proc_macro_error::abort;
+use quote::{quote, ToTokens, TokenStreamExt};
+use syn::{
+    parse::Parse, Attribute, Fields, Generics, ItemStruct, LitStr, Meta,
+    Visibility,
+};
+
+pub struct Model {
+    docs: Docs,
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    generics: Generics,
+    props: Vec<Prop>,
+}
+
+impl Parse for Model {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let item = ItemStruct::parse(input)?;
+
+        let docs = Docs::new(&item.attrs);
+
+        let props = match &item.fields {
+            Fields::Named(fields) => {
+                fields.named.iter().map(Prop::from_field).collect()
+            }
+            Fields::Unit => Vec::new(),
+            Fields::Unnamed(_) => abort!(
+                item.fields,
+                "only named fields are allowed within a `#[slot]`"
+            ),
+        };
+
+        // the docs are added again, along with those for the props
+        let attrs = item
+            .attrs
+            .into_iter()
+            .filter(|attr| match &attr.meta {
+                Meta::NameValue(attr) => !attr.path.is_ident("doc"),
+                _ => true,
+            })
+            .collect();
+
+        Ok(Self {
+            docs,
+            attrs,
+            vis: item.vis,
+            name: item.ident,
+            generics: item.generics,
+            props,
+        })
+    }
+}
+
+impl ToTokens for Model {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            docs,
+            attrs,
+            vis,
+            name,
+            generics,
+            props,
+        } = self;
+
+        let (impl_generics, ty_generics, where_clause) =
+            generics.split_for_impl();
+
+        let builder_name_doc = LitStr::new(
+            &format!(" Props for the [`{name}`] slot."),
+            name.span(),
+        );
+
+        let prop_builder_fields = prop_builder_fields(vis, props);
+        let slot_prop_docs = generate_component_fn_prop_docs(props);
+
+        let output = quote! {
+            #[doc = #builder_name_doc]
+            #[doc = ""]
+            #docs
+            #[doc = ""]
+            #slot_prop_docs
+            #(#attrs)*
+            #[derive(::tachys::typed_builder_macro::TypedBuilder)]
+            #[builder(crate_module_path=::tachys::typed_builder)]
+            #vis struct #name #impl_generics #where_clause {
+                #prop_builder_fields
+            }
+
+            // allows a single slot to be passed to a prop that takes several
+            impl #impl_generics ::core::convert::From<#name #ty_generics>
+                for ::std::vec::Vec<#name #ty_generics> #where_clause
+            {
+                fn from(value: #name #ty_generics) -> Self {
+                    ::std::vec![value]
+                }
+            }
+        };
+
+        tokens.append_all(output)
+    }
+}
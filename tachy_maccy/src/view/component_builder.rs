@@ -1,6 +1,9 @@
-use super::{fragment_to_tokens, TagType};
+use super::{
+    attribute_value, class_to_tokens, event_to_tokens, fragment_to_tokens,
+    style_to_tokens, TagType,
+};
 use proc_macro2::{Ident, TokenStream, TokenTree};
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, ToTokens};
 use rstml::node::{NodeAttribute, NodeElement, NodeName};
 use std::collections::HashMap;
 use syn::spanned::Spanned;
@@ -12,6 +15,104 @@ pub(crate) fn component_to_tokens(
     let name = node.name();
     #[cfg(debug_assertions)]
     let component_name = ident_from_tag_name(node.name());
+
+    let attrs = node.attributes().iter().filter_map(|node| {
+        if let NodeAttribute::Attribute(node) = node {
+            Some(node)
+        } else {
+            None
+        }
+    });
+
+    // TODO directives
+
+    // attributes that are added to the component's root element
+    let root_attrs = attrs
+        .clone()
+        .filter_map(|attr| {
+            let key = attr.key.to_string();
+            if let Some(name) = key.strip_prefix("attr:") {
+                let value = attribute_value(attr);
+                Some(quote! { .attr(#name, #value) })
+            } else if let Some(name) = key.strip_prefix("on:") {
+                Some(event_to_tokens(name, attr))
+            } else if let Some(name) = key.strip_prefix("class:") {
+                let class = match &attr.key {
+                    NodeName::Punctuated(parts) => &parts[0],
+                    _ => unreachable!(),
+                };
+                Some(class_to_tokens(
+                    attr,
+                    class.into_token_stream(),
+                    Some(name),
+                ))
+            } else if let Some(name) = key.strip_prefix("style:") {
+                let style = match &attr.key {
+                    NodeName::Punctuated(parts) => &parts[0],
+                    _ => unreachable!(),
+                };
+                Some(style_to_tokens(
+                    attr,
+                    style.into_token_stream(),
+                    Some(name),
+                ))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let builder = builder_to_tokens(node, global_class);
+
+    let generics = &node.open_tag.generics;
+    let generics = if generics.lt_token.is_some() {
+        quote! { ::#generics }
+    } else {
+        quote! {}
+    };
+
+    let mut component = quote! {
+        ::tachys::component::component_view(
+            &#name,
+            ::tachys::component::component_props_builder(&#name #generics)
+                #builder
+                .build()
+        )
+    };
+
+    if !root_attrs.is_empty() {
+        component = quote! {
+            ::tachys::tachydom::html::attribute::any_attr::AddAnyAttr::add_any_attr(
+                #component,
+                ::tachys::tachydom::html::attribute::any_attr::attributes()
+                    #(#root_attrs)*
+            )
+        };
+    }
+
+    // names the component in the path of any hydration mismatch inside it
+    let name = name.to_string();
+    let component = quote! {
+        ::tachys::tachydom::view::component::ComponentView::new(
+            #name,
+            #component
+        )
+    };
+
+    // (Temporarily?) removed
+    // See note on the function itself below.
+    /* #[cfg(debug_assertions)]
+    IdeTagHelper::add_component_completion(&mut component, node); */
+
+    component
+}
+
+/// Generates the calls to the props builder of a component or a slot, for
+/// its props, its slots, and its children.
+pub(crate) fn builder_to_tokens(
+    node: &NodeElement,
+    global_class: Option<&TokenTree>,
+) -> TokenStream {
     let span = node.name().span();
 
     let attrs = node.attributes().iter().filter_map(|node| {
@@ -25,10 +126,14 @@ pub(crate) fn component_to_tokens(
     let props = attrs
         .clone()
         .filter(|attr| {
-            !attr.key.to_string().starts_with("let:")
+            attr.key.to_string() != "slot"
+                && !attr.key.to_string().starts_with("slot:")
+                && !attr.key.to_string().starts_with("let:")
                 && !attr.key.to_string().starts_with("clone:")
                 && !attr.key.to_string().starts_with("on:")
                 && !attr.key.to_string().starts_with("attr:")
+                && !attr.key.to_string().starts_with("class:")
+                && !attr.key.to_string().starts_with("style:")
                 && !attr.key.to_string().starts_with("use:")
         })
         .map(|attr| {
@@ -66,50 +171,6 @@ pub(crate) fn component_to_tokens(
         })
         .collect::<Vec<_>>();
 
-    // TODO events and directives
-    /*     let events = attrs
-        .clone()
-        .filter(|attr| attr.key.to_string().starts_with("on:"))
-        .map(|attr| {
-            let (event_type, handler) = event_from_attribute_node(attr, true);
-
-            quote! {
-                .on(#event_type, #handler)
-            }
-        })
-        .collect::<Vec<_>>();
-
-    let directives = attrs
-        .clone()
-        .filter_map(|attr| {
-            attr.key
-                .to_string()
-                .strip_prefix("use:")
-                .map(|ident| directive_call_from_attribute_node(attr, ident))
-        })
-        .collect::<Vec<_>>();
-
-    let events_and_directives =
-        events.into_iter().chain(directives).collect::<Vec<_>>(); */
-
-    let dyn_attrs = attrs
-        .filter(|attr| attr.key.to_string().starts_with("attr:"))
-        .filter_map(|attr| {
-            let name = &attr.key.to_string();
-            let name = name.strip_prefix("attr:");
-            let value = attr.value().map(|v| {
-                quote! { #v }
-            })?;
-            Some(quote! { (#name, #value.into_attribute()) })
-        })
-        .collect::<Vec<_>>();
-
-    let dyn_attrs = if dyn_attrs.is_empty() {
-        quote! {}
-    } else {
-        quote! { .dyn_attrs(vec![#(#dyn_attrs),*]) }
-    };
-
     let mut slots = HashMap::new();
     let children = if node.children.is_empty() {
         quote! {}
@@ -156,8 +217,8 @@ pub(crate) fn component_to_tokens(
         let slot = Ident::new(&slot, span);
         if values.len() > 1 {
             quote! {
-                .#slot(vec![
-                    #(#values)*
+                .#slot(::std::vec![
+                    #(#values),*
                 ])
             }
         } else {
@@ -166,41 +227,11 @@ pub(crate) fn component_to_tokens(
         }
     });
 
-    let generics = &node.open_tag.generics;
-    let generics = if generics.lt_token.is_some() {
-        quote! { ::#generics }
-    } else {
-        quote! {}
-    };
-
-    #[allow(unused_mut)] // used in debug
-    let mut component = quote! {
-        ::tachys::component::component_view(
-            &#name,
-            ::tachys::component::component_props_builder(&#name #generics)
-                #(#props)*
-                #(#slots)*
-                #children
-                .build()
-                #dyn_attrs
-        )
-    };
-
-    // (Temporarily?) removed
-    // See note on the function itself below.
-    /* #[cfg(debug_assertions)]
-    IdeTagHelper::add_component_completion(&mut component, node); */
-
-    // TODO events and directives
-    /* if events_and_directives.is_empty() {
-        component
-    } else {
-        quote! {
-            #component.into_view()
-            #(#events_and_directives)*
-        }
-    } */
-    component
+    quote! {
+        #(#props)*
+        #(#slots)*
+        #children
+    }
 }
 
 #[cfg(debug_assertions)]
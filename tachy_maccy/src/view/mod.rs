@@ -1,14 +1,22 @@
 mod component_builder;
+mod slot_helper;
 
-use self::component_builder::component_to_tokens;
+use self::{
+    component_builder::component_to_tokens,
+    slot_helper::{get_slot, slot_to_tokens},
+};
 use convert_case::{Case::Snake, Casing};
 use leptos_hot_reload::parsing::is_component_node;
 use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use proc_macro_error::abort;
 use quote::{quote, quote_spanned, ToTokens};
-use rstml::node::{KeyedAttribute, Node, NodeAttribute, NodeElement, NodeName};
+use rstml::node::{
+    KeyedAttribute, Node, NodeAttribute, NodeBlock, NodeElement, NodeName,
+};
 use std::collections::HashMap;
-use syn::{spanned::Spanned, Expr, ExprPath, Lit, LitStr};
+use syn::{
+    spanned::Spanned, Expr, ExprPath, ExprRange, Lit, LitStr, RangeLimits, Stmt,
+};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub(crate) enum TagType {
@@ -89,7 +97,8 @@ fn fragment_to_tokens(
         global_class,
         view_marker,
     );
-    if children.len() == 1 {
+    // slots are not rendered in place, so there may be no children left
+    if children.len() <= 1 {
         children.into_iter().next()
     } else {
         Some(quote! {
@@ -117,13 +126,14 @@ fn children_to_tokens(
             None => vec![],
         }
     } else {
+        let mut parent_slots = parent_slots;
         nodes
             .iter()
             .filter_map(|node| {
                 node_to_tokens(
                     node,
                     TagType::Unknown,
-                    None,
+                    parent_slots.as_deref_mut(),
                     global_class,
                     view_marker,
                 )
@@ -191,13 +201,12 @@ pub(crate) fn element_to_tokens(
 ) -> Option<TokenStream> {
     let name = node.name();
     if is_component_node(node) {
-        // TODO slots
-        /* if let Some(slot) = get_slot(node) {
+        if let Some(slot) = get_slot(node) {
             slot_to_tokens(node, slot, parent_slots, global_class);
             None
-        } else { */
-        Some(component_to_tokens(node, global_class))
-        //}
+        } else {
+            Some(component_to_tokens(node, global_class))
+        }
     } else {
         let tag = name.to_string();
         // collect close_tag name to emit semantic information for IDE.
@@ -263,10 +272,11 @@ pub(crate) fn element_to_tokens(
 
         let self_closing = is_self_closing(node);
         let children = if !self_closing {
+            // slots must be direct children of a component
             element_children_to_tokens(
                 &node.children,
                 parent_type,
-                parent_slots,
+                None,
                 global_class,
                 view_marker,
             )
@@ -300,7 +310,12 @@ fn attribute_to_tokens(
     global_class: Option<&TokenTree>,
 ) -> TokenStream {
     match node {
-        NodeAttribute::Block(_) => todo!(),
+        NodeAttribute::Block(block) => match spread_attributes(block) {
+            Some(attrs) => quote! {
+                .spread(#attrs)
+            },
+            None => block_attribute_to_tokens(block),
+        },
         NodeAttribute::Attribute(node) => {
             let name = node.key.to_string();
             if name == "node_ref" {
@@ -363,10 +378,77 @@ fn attribute_to_tokens(
     }
 }
 
+/// Returns the attributes in a `{..attrs}` block, if it is one.
+fn spread_attributes(block: &NodeBlock) -> Option<&Expr> {
+    let NodeBlock::ValidBlock(block) = block else {
+        return None;
+    };
+    match block.stmts.as_slice() {
+        [Stmt::Expr(
+            Expr::Range(ExprRange {
+                start: None,
+                end: Some(attrs),
+                limits: RangeLimits::HalfOpen(_),
+                ..
+            }),
+            None,
+        )] => Some(attrs),
+        _ => None,
+    }
+}
+
+/// Adds the attribute, or set of attributes, in a `{attrs}` block.
+fn block_attribute_to_tokens(block: &NodeBlock) -> TokenStream {
+    // the parser has already reported an error for an invalid block
+    let NodeBlock::ValidBlock(block) = block else {
+        return quote! {};
+    };
+    match block.stmts.last() {
+        None => proc_macro_error::emit_error!(
+            block.span(),
+            "expected an attribute in the block"
+        ),
+        Some(Stmt::Expr(Expr::Lit(lit), None)) => {
+            proc_macro_error::emit_error!(
+                lit.span(),
+                "a literal is not an attribute";
+                help = "use `name=value` to add an attribute with a name"
+            )
+        }
+        Some(Stmt::Expr(Expr::Range(range), None)) => {
+            proc_macro_error::emit_error!(
+                range.span(),
+                "only `{..attrs}` can be used to spread attributes"
+            )
+        }
+        Some(Stmt::Expr(_, None)) => {}
+        Some(Stmt::Macro(mac)) if mac.semi_token.is_none() => {}
+        Some(stmt) => proc_macro_error::emit_error!(
+            stmt.span(),
+            "expected the block to end with an attribute"
+        ),
+    }
+    // a block with a single expression is passed on without its braces, so
+    // that it doesn't trigger `unused_braces`
+    match block.stmts.as_slice() {
+        [Stmt::Expr(attrs, None)] => quote! {
+            .attrs(#attrs)
+        },
+        _ => quote! {
+            .attrs(#block)
+        },
+    }
+}
+
 fn event_to_tokens(name: &str, node: &KeyedAttribute) -> TokenStream {
     let handler = attribute_value(node);
 
-    let (event_type, is_custom, is_force_undelegated) = parse_event_name(name);
+    let (event_type, is_custom, is_force_undelegated, modifiers) =
+        parse_event_name(name);
+    let (flags, key) = match event_modifiers(&modifiers) {
+        Ok(parsed) => parsed,
+        Err(e) => abort!(node.key, "{}", e),
+    };
 
     let event_name_ident = match &node.key {
         NodeName::Punctuated(parts) => {
@@ -379,13 +461,9 @@ fn event_to_tokens(name: &str, node: &KeyedAttribute) -> TokenStream {
         _ => unreachable!(),
     };
     let undelegated_ident = match &node.key {
-        NodeName::Punctuated(parts) => parts.last().and_then(|last| {
-            if last.to_string() == "undelegated" {
-                Some(last)
-            } else {
-                None
-            }
-        }),
+        NodeName::Punctuated(parts) => {
+            parts.iter().find(|part| part.to_string() == "undelegated")
+        }
         _ => unreachable!(),
     };
     let on = match &node.key {
@@ -418,6 +496,24 @@ fn event_to_tokens(name: &str, node: &KeyedAttribute) -> TokenStream {
         quote! { ::tachys::tachydom::html::event::#event_type }
     };
 
+    let event_type = if modifiers.is_empty() {
+        event_type
+    } else {
+        let fields =
+            flags.iter().map(|field| Ident::new(field, node.key.span()));
+        let key = key.map(|key| quote! { key: Some(#key), });
+        quote! {
+            ::tachys::tachydom::html::event::Modified::new(
+                #event_type,
+                ::tachys::tachydom::html::event::Modifiers {
+                    #(#fields: true,)*
+                    #key
+                    ..::core::default::Default::default()
+                }
+            )
+        }
+    };
+
     quote! {
         .#on(#event_type, #handler)
     }
@@ -597,12 +693,77 @@ fn is_ambiguous_element(tag: &str) -> bool {
     tag == "a" || tag == "script" || tag == "title"
 }
 
-fn parse_event(event_name: &str) -> (&str, bool) {
-    if let Some(event_name) = event_name.strip_suffix(":undelegated") {
-        (event_name, true)
-    } else {
-        (event_name, false)
+/// Modifiers that can follow the event name, as in `on:click:prevent:once`.
+const EVENT_MODIFIERS: &[&str] =
+    &["capture", "once", "passive", "prevent", "self", "stop"];
+
+/// Key modifiers, as in `on:keydown:enter`, and the `KeyboardEvent.key` each
+/// one filters for.
+const KEY_MODIFIERS: &[(&str, &str)] = &[
+    ("down", "ArrowDown"),
+    ("enter", "Enter"),
+    ("esc", "Escape"),
+    ("left", "ArrowLeft"),
+    ("right", "ArrowRight"),
+    ("space", " "),
+    ("tab", "Tab"),
+    ("up", "ArrowUp"),
+];
+
+/// Sorts an event's modifiers into the names of the `Modifiers` fields to
+/// set, and the key to filter for, if any.
+fn event_modifiers<'a>(
+    modifiers: &[&'a str],
+) -> Result<(Vec<&'a str>, Option<&'static str>), String> {
+    let mut flags = Vec::new();
+    let mut key = None;
+    for modifier in modifiers {
+        if let Some((name, value)) =
+            KEY_MODIFIERS.iter().find(|(name, _)| name == modifier)
+        {
+            if let Some((other, _)) = key {
+                return Err(format!(
+                    "only one key modifier can be used, but found `{other}` \
+                     and `{name}`"
+                ));
+            }
+            key = Some((*name, *value));
+        } else if EVENT_MODIFIERS.contains(modifier) {
+            flags.push(if *modifier == "self" {
+                "self_only"
+            } else {
+                modifier
+            });
+        } else {
+            return Err(format!(
+                "unknown event modifier `{modifier}`; expected one of {}",
+                EVENT_MODIFIERS
+                    .iter()
+                    .chain(KEY_MODIFIERS.iter().map(|(name, _)| name))
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    }
+    Ok((flags, key.map(|(_, value)| value)))
+}
+
+/// Splits an event into its name, whether it's undelegated, and the rest of
+/// its modifiers.
+fn parse_event(event_name: &str) -> (&str, bool, Vec<&str>) {
+    let mut parts = event_name.split(':');
+    let event_name = parts.next().unwrap_or_default();
+    let mut is_force_undelegated = false;
+    let mut modifiers = Vec::new();
+    for part in parts {
+        if part == "undelegated" {
+            is_force_undelegated = true;
+        } else if !modifiers.contains(&part) {
+            modifiers.push(part);
+        }
     }
+    (event_name, is_force_undelegated, modifiers)
 }
 
 /// Escapes Rust keywords that are also HTML attribute names
@@ -768,8 +929,10 @@ const TYPED_EVENTS: [&str; 126] = [
 
 const CUSTOM_EVENT: &str = "Custom";
 
-pub(crate) fn parse_event_name(name: &str) -> (TokenStream, bool, bool) {
-    let (name, is_force_undelegated) = parse_event(name);
+pub(crate) fn parse_event_name(
+    name: &str,
+) -> (TokenStream, bool, bool, Vec<&str>) {
+    let (name, is_force_undelegated, modifiers) = parse_event(name);
 
     let (event_type, is_custom) = TYPED_EVENTS
         .binary_search(&name)
@@ -785,7 +948,7 @@ pub(crate) fn parse_event_name(name: &str) -> (TokenStream, bool, bool) {
     } else {
         event_type
     };
-    (event_type, is_custom, is_force_undelegated)
+    (event_type, is_custom, is_force_undelegated, modifiers)
 }
 
 fn expr_to_ident(expr: &syn::Expr) -> Option<&ExprPath> {
@@ -809,3 +972,77 @@ fn convert_to_snake_case(name: String) -> String {
         name
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{event_modifiers, parse_event};
+
+    #[test]
+    fn parse_event_splits_modifiers() {
+        assert_eq!(parse_event("click"), ("click", false, vec![]));
+        assert_eq!(
+            parse_event("click:prevent:stop"),
+            ("click", false, vec!["prevent", "stop"])
+        );
+    }
+
+    #[test]
+    fn parse_event_finds_undelegated_anywhere() {
+        assert_eq!(
+            parse_event("click:undelegated:once"),
+            ("click", true, vec!["once"])
+        );
+        assert_eq!(
+            parse_event("click:once:undelegated"),
+            ("click", true, vec!["once"])
+        );
+    }
+
+    #[test]
+    fn parse_event_drops_repeated_modifiers() {
+        assert_eq!(
+            parse_event("click:once:prevent:once"),
+            ("click", false, vec!["once", "prevent"])
+        );
+    }
+
+    #[test]
+    fn event_modifiers_map_to_fields() {
+        assert_eq!(
+            event_modifiers(&["capture", "once", "passive"]),
+            Ok((vec!["capture", "once", "passive"], None))
+        );
+        assert_eq!(
+            event_modifiers(&["prevent", "self", "stop"]),
+            Ok((vec!["prevent", "self_only", "stop"], None))
+        );
+    }
+
+    #[test]
+    fn key_modifiers_map_to_keys() {
+        assert_eq!(event_modifiers(&["enter"]), Ok((vec![], Some("Enter"))));
+        assert_eq!(event_modifiers(&["esc"]), Ok((vec![], Some("Escape"))));
+        assert_eq!(event_modifiers(&["space"]), Ok((vec![], Some(" "))));
+        assert_eq!(
+            event_modifiers(&["prevent", "up"]),
+            Ok((vec!["prevent"], Some("ArrowUp")))
+        );
+    }
+
+    #[test]
+    fn unknown_modifiers_are_rejected() {
+        let err = event_modifiers(&["prevent", "preventt"]).unwrap_err();
+        assert!(err.starts_with("unknown event modifier `preventt`"));
+        assert!(err.contains("stop, down, enter"));
+    }
+
+    #[test]
+    fn only_one_key_modifier_is_allowed() {
+        assert_eq!(
+            event_modifiers(&["enter", "esc"]),
+            Err("only one key modifier can be used, but found `enter` and \
+                 `esc`"
+                .to_string())
+        );
+    }
+}
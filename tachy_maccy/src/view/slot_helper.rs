@@ -0,0 +1,86 @@
+use super::{component_builder::builder_to_tokens, convert_to_snake_case};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use rstml::node::{KeyedAttribute, NodeAttribute, NodeElement};
+use std::collections::HashMap;
+use syn::spanned::Spanned;
+
+/// Finds the `slot` or `slot:name` attribute that marks a component-like node
+/// as a slot.
+pub(crate) fn get_slot(node: &NodeElement) -> Option<&KeyedAttribute> {
+    node.attributes().iter().find_map(|node| match node {
+        NodeAttribute::Attribute(node)
+            if node.key.to_string() == "slot"
+                || node.key.to_string().starts_with("slot:") =>
+        {
+            Some(node)
+        }
+        _ => None,
+    })
+}
+
+/// Builds the slot, and adds it to the slots of its parent component, under
+/// the name of the prop that it is passed to.
+pub(crate) fn slot_to_tokens(
+    node: &NodeElement,
+    slot: &KeyedAttribute,
+    parent_slots: Option<&mut HashMap<String, Vec<TokenStream>>>,
+    global_class: Option<&TokenTree>,
+) {
+    let name = node.name();
+
+    let Some(parent_slots) = parent_slots else {
+        proc_macro_error::emit_error!(
+            name.span(),
+            "slots must be direct children of a component"
+        );
+        return;
+    };
+
+    let prop_name = match slot.key.to_string().strip_prefix("slot:") {
+        Some(prop_name) => prop_name.to_string(),
+        None => convert_to_snake_case(
+            name.to_string()
+                .rsplit("::")
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+        ),
+    };
+
+    if let Some(value) = slot.value() {
+        proc_macro_error::emit_error!(
+            value.span(),
+            "`slot` does not take a value; use `slot:name` to pass the slot \
+             to a prop with another name"
+        );
+    }
+
+    // these only make sense for an element
+    for attr in node.attributes() {
+        let NodeAttribute::Attribute(attr) = attr else {
+            continue;
+        };
+        let key = attr.key.to_string();
+        if ["attr:", "on:", "class:", "style:", "use:"]
+            .iter()
+            .any(|prefix| key.starts_with(prefix))
+        {
+            proc_macro_error::emit_error!(
+                attr.key.span(),
+                format!("`{key}` can't be used on a slot")
+            );
+        }
+    }
+
+    let builder = builder_to_tokens(node, global_class);
+
+    let slot = quote! {
+        #name::builder()
+            #builder
+            .build()
+    };
+
+    parent_slots.entry(prop_name).or_default().push(slot);
+}
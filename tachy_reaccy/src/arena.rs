@@ -39,12 +39,14 @@ thread_local! {
 pub struct Root<T>(pub Owner, pub T);
 
 impl<T> Root<T> {
+    #[track_caller]
     pub fn global(fun: impl FnOnce() -> T) -> T {
         let Root(owner, value) = Root::new(fun);
         mem::forget(owner);
         value
     }
 
+    #[track_caller]
     pub fn global_hydrate(fun: impl FnOnce() -> T) -> T {
         let Root(owner, value) = Root::new_with_shared_context(
             fun,
@@ -54,6 +56,7 @@ impl<T> Root<T> {
         value
     }
 
+    #[track_caller]
     pub fn global_islands(fun: impl FnOnce() -> T) -> T {
         let Root(owner, value) = Root::new_with_shared_context(
             fun,
@@ -63,6 +66,7 @@ impl<T> Root<T> {
         value
     }
 
+    #[track_caller]
     pub fn global_ssr(fun: impl FnOnce() -> T) -> Root<T> {
         Root::new_with_shared_context(
             fun,
@@ -70,6 +74,7 @@ impl<T> Root<T> {
         )
     }
 
+    #[track_caller]
     pub fn global_ssr_islands(fun: impl FnOnce() -> T) -> Root<T> {
         Root::new_with_shared_context(
             fun,
@@ -77,10 +82,12 @@ impl<T> Root<T> {
         )
     }
 
+    #[track_caller]
     pub fn new(fun: impl FnOnce() -> T) -> Self {
         Self::new_with_shared_context(fun, None)
     }
 
+    #[track_caller]
     pub fn new_with_shared_context(
         fun: impl FnOnce() -> T,
         shared_context: Option<Arc<dyn SharedContext + Send + Sync>>,
@@ -89,6 +96,10 @@ impl<T> Root<T> {
             shared_context,
             ..Default::default()
         };
+        #[cfg(debug_assertions)]
+        {
+            owner.inner.write().defined_at = Some(Location::caller());
+        }
         let prev = OWNER.with(|o| {
             std::mem::replace(&mut *o.borrow_mut(), Some(owner.clone()))
         });
@@ -112,6 +123,7 @@ pub struct Owner {
 }
 
 impl Owner {
+    #[track_caller]
     pub fn new() -> Self {
         let (parent, shared_context) = {
             OWNER
@@ -122,17 +134,42 @@ impl Owner {
                 })
                 .unzip()
         };
+        let inner = Arc::new(RwLock::new(OwnerInner {
+            parent,
+            nodes: Default::default(),
+            contexts: Default::default(),
+            cleanups: Default::default(),
+            #[cfg(debug_assertions)]
+            children: Default::default(),
+            #[cfg(debug_assertions)]
+            defined_at: Some(Location::caller()),
+        }));
+        #[cfg(debug_assertions)]
+        if let Some(parent) =
+            inner.read().parent.as_ref().and_then(|p| p.upgrade())
+        {
+            let mut parent = parent.write();
+            parent.children.retain(|child| child.strong_count() > 0);
+            parent.children.push(Arc::downgrade(&inner));
+        }
         Self {
-            inner: Arc::new(RwLock::new(OwnerInner {
-                parent,
-                nodes: Default::default(),
-                contexts: Default::default(),
-                cleanups: Default::default(),
-            })),
+            inner,
             shared_context: shared_context.flatten(),
         }
     }
 
+    /// Where the owner was created, if known.
+    pub fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(debug_assertions)]
+        {
+            self.inner.read().defined_at
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            None
+        }
+    }
+
     pub fn with<T>(&self, fun: impl FnOnce() -> T) -> T {
         let prev = {
             OWNER.with(|o| {
@@ -202,6 +239,60 @@ impl Owner {
     pub fn current() -> Option<Owner> {
         OWNER.with(|o| o.borrow().clone())
     }
+
+    /// Takes a snapshot of this owner, its descendant owners, and all the
+    /// reactive values they currently hold.
+    ///
+    /// Drop every handle to the owner, then call
+    /// [`OwnerLeakCheck::assert_released`] to check that nothing outlived it.
+    #[cfg(debug_assertions)]
+    pub fn leak_check(&self) -> OwnerLeakCheck {
+        fn collect(
+            inner: &Arc<RwLock<OwnerInner>>,
+            owners: &mut Vec<Weak<RwLock<OwnerInner>>>,
+            nodes: &mut Vec<NodeId>,
+        ) {
+            owners.push(Arc::downgrade(inner));
+            let lock = inner.read();
+            nodes.extend(lock.nodes.iter().copied());
+            for child in lock.children.iter().filter_map(Weak::upgrade) {
+                collect(&child, owners, nodes);
+            }
+        }
+
+        let mut owners = Vec::new();
+        let mut nodes = Vec::new();
+        collect(&self.inner, &mut owners, &mut nodes);
+        OwnerLeakCheck { owners, nodes }
+    }
+}
+
+/// A snapshot of an [`Owner`] tree, created by [`Owner::leak_check`].
+#[cfg(debug_assertions)]
+#[derive(Debug)]
+pub struct OwnerLeakCheck {
+    owners: Vec<Weak<RwLock<OwnerInner>>>,
+    nodes: Vec<NodeId>,
+}
+
+#[cfg(debug_assertions)]
+impl OwnerLeakCheck {
+    /// Panics if the owner, any of its descendant owners, or any reactive
+    /// value they held is still alive.
+    #[track_caller]
+    pub fn assert_released(&self) {
+        let live_owners =
+            self.owners.iter().filter(|o| o.strong_count() > 0).count();
+        let live_nodes = {
+            let map = MAP.read();
+            self.nodes.iter().filter(|n| map.contains_key(**n)).count()
+        };
+        assert!(
+            live_owners == 0 && live_nodes == 0,
+            "expected Owner to release all its children, but {live_owners} \
+             owner(s) and {live_nodes} reactive value(s) are still alive"
+        );
+    }
 }
 
 #[derive(Default)]
@@ -210,6 +301,10 @@ pub(crate) struct OwnerInner {
     nodes: Vec<NodeId>,
     pub contexts: FxHashMap<TypeId, Box<dyn Any + Send + Sync>>,
     pub cleanups: Vec<Box<dyn FnOnce() + Send + Sync>>,
+    #[cfg(debug_assertions)]
+    children: Vec<Weak<RwLock<OwnerInner>>>,
+    #[cfg(debug_assertions)]
+    defined_at: Option<&'static Location<'static>>,
 }
 
 impl Debug for OwnerInner {
@@ -237,6 +332,8 @@ impl Drop for OwnerInner {
 #[derive(Debug)]
 pub struct Stored<T> {
     node: NodeId,
+    #[cfg(debug_assertions)]
+    defined_at: &'static Location<'static>,
     ty: PhantomData<T>,
 }
 
@@ -266,10 +363,25 @@ where
 
         Self {
             node,
+            #[cfg(debug_assertions)]
+            defined_at: Location::caller(),
             ty: PhantomData,
         }
     }
 
+    /// Where the value was created, which is still known once it has been
+    /// disposed.
+    pub fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(debug_assertions)]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            None
+        }
+    }
+
     pub fn with_value<U>(&self, fun: impl FnOnce(&T) -> U) -> Option<U> {
         let m = MAP.read();
         let m = m.get(self.node);
@@ -302,6 +414,8 @@ pub trait StoredData {
 
     fn get_value(&self) -> Option<Self::Data>;
 
+    fn stored_defined_at(&self) -> Option<&'static Location<'static>>;
+
     fn dispose(&self);
 }
 
@@ -385,7 +499,9 @@ where
     T::Data: DefinedAt,
 {
     fn defined_at(&self) -> Option<&'static Location<'static>> {
-        self.get_value().and_then(|n| n.defined_at())
+        // read from the arena entry, rather than the value, so that it can
+        // still be reported once the value has been disposed
+        self.stored_defined_at()
     }
 }
 
@@ -427,7 +543,12 @@ where
         &self,
         fun: impl FnOnce(&Self::Value) -> U,
     ) -> Option<U> {
-        self.get_value().and_then(|n| n.try_with_untracked(fun))
+        let Some(inner) = self.get_value() else {
+            #[cfg(debug_assertions)]
+            warn_disposed("access", self.defined_at(), Location::caller());
+            return None;
+        };
+        inner.try_with_untracked(fun)
     }
 }
 
@@ -445,16 +566,43 @@ where
 
 impl<T> SignalUpdateUntracked for T
 where
-    T: StoredData,
+    T: StoredData + DefinedAt,
     T::Data: SignalUpdateUntracked,
 {
     type Value = <<T as StoredData>::Data as SignalUpdateUntracked>::Value;
 
+    #[track_caller]
     fn try_update_untracked<U>(
         &self,
         fun: impl FnOnce(&mut Self::Value) -> U,
     ) -> Option<U> {
-        self.get_value()
-            .and_then(|inner| inner.try_update_untracked(fun))
+        let Some(inner) = self.get_value() else {
+            #[cfg(debug_assertions)]
+            warn_disposed("update", self.defined_at(), Location::caller());
+            return None;
+        };
+        inner.try_update_untracked(fun)
     }
 }
+
+#[cfg(debug_assertions)]
+fn warn_disposed(
+    action: &str,
+    defined_at: Option<&'static Location<'static>>,
+    location: &'static Location<'static>,
+) {
+    let msg = match defined_at {
+        Some(defined_at) => format!(
+            "At {location}, you tried to {action} a reactive value which was \
+             defined at {defined_at}, but it has already been disposed."
+        ),
+        None => format!(
+            "At {location}, you tried to {action} a reactive value, but it \
+             has already been disposed."
+        ),
+    };
+    #[cfg(feature = "tracing")]
+    tracing::warn!("{msg}");
+    #[cfg(not(feature = "tracing"))]
+    crate::log(&msg);
+}
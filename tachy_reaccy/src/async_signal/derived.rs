@@ -419,6 +419,10 @@ impl<T: Send + Sync + 'static> StoredData for AsyncDerived<T> {
         self.inner.get()
     }
 
+    fn stored_defined_at(&self) -> Option<&'static Location<'static>> {
+        self.inner.defined_at()
+    }
+
     fn dispose(&self) {
         self.inner.dispose();
     }
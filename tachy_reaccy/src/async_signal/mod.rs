@@ -1,6 +1,6 @@
 mod derived;
 mod resource;
-use crate::{arena::Owner, source::AnySubscriber, Observer};
+use crate::{arena::Owner, source::AnySubscriber, untrack, Observer};
 pub use derived::*;
 use futures::Future;
 use pin_project_lite::pin_project;
@@ -50,6 +50,18 @@ impl<Fut> ScopedFuture<Fut> {
             fut,
         }
     }
+
+    /// Captures the current [`Owner`], but not the current observer, so that
+    /// the future can be spawned as an independent task without tracking
+    /// reads into whatever was running when it was created.
+    pub fn new_untracked(fut: Fut) -> Self {
+        let owner = Owner::current();
+        Self {
+            owner,
+            observer: None,
+            fut,
+        }
+    }
 }
 
 impl<Fut: Future> Future for ScopedFuture<Fut> {
@@ -57,10 +69,12 @@ impl<Fut: Future> Future for ScopedFuture<Fut> {
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.project();
+        // without a captured observer, reads are not tracked by whatever
+        // happens to be running when the future is polled
         match (this.owner, this.observer) {
-            (None, None) => this.fut.poll(cx),
+            (None, None) => untrack(|| this.fut.poll(cx)),
             (None, Some(obs)) => obs.with_observer(|| this.fut.poll(cx)),
-            (Some(owner), None) => owner.with(|| this.fut.poll(cx)),
+            (Some(owner), None) => owner.with(|| untrack(|| this.fut.poll(cx))),
             (Some(owner), Some(observer)) => {
                 owner.with(|| observer.with_observer(|| this.fut.poll(cx)))
             }
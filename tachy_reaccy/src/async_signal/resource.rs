@@ -10,10 +10,12 @@ use crate::{
     prelude::SignalWithUntracked,
     serialization::{SerdeJson, SerializableData, Serializer, Str},
     shared_context::SerializedDataId,
+    PinnedFuture,
 };
 use core::{fmt::Debug, marker::PhantomData};
 use futures::Future;
-use std::{future::IntoFuture, ops::Deref};
+use parking_lot::Mutex;
+use std::{future::IntoFuture, ops::Deref, sync::Arc};
 
 pub struct ArcResource<T, Ser> {
     ser: PhantomData<Ser>,
@@ -112,7 +114,7 @@ where
 
 impl<T, Ser> ArcResource<T, Ser>
 where
-    Ser: Serializer,
+    Ser: Serializer + 'static,
     T: SerializableData<Ser>,
     T::SerErr: Debug,
     T::DeErr: Debug,
@@ -129,8 +131,12 @@ where
             .unwrap_or_default();
 
         let initial = Self::initial_value(&id);
+        let pending = Arc::new(Mutex::new(pending_data(&id, &initial)));
 
-        let data = ArcAsyncDerived::new_with_initial(initial, fun);
+        let fun = Arc::new(fun);
+        let data = ArcAsyncDerived::new_with_initial(initial, move || {
+            load::<T, Ser, _>(Arc::clone(&fun), pending.lock().take())
+        });
 
         if let Some(shared_context) = Owner::shared_context() {
             let value = data.clone();
@@ -157,6 +163,7 @@ where
     }
 
     #[inline(always)]
+    #[allow(unused_variables)]
     fn initial_value(id: &SerializedDataId) -> AsyncState<T> {
         #[cfg(feature = "hydration")]
         {
@@ -178,6 +185,56 @@ where
     }
 }
 
+/// If this resource's value is still being streamed from the server while
+/// hydrating, returns a [`Future`] that resolves when it arrives.
+#[allow(unused_variables)]
+fn pending_data<T>(
+    id: &SerializedDataId,
+    initial: &AsyncState<T>,
+) -> Option<PinnedFuture<Option<String>>> {
+    #[cfg(feature = "hydration")]
+    {
+        if let Some(shared_context) = Owner::shared_context() {
+            if shared_context.get_is_hydrating()
+                && matches!(initial, AsyncState::Loading)
+            {
+                return Some(shared_context.await_data(id));
+            }
+        }
+    }
+    None
+}
+
+/// Loads a resource, using the value sent by the server if it is `pending`.
+///
+/// The fetcher isn't called at all while waiting for the server's value, so
+/// that it doesn't start loading the same data again. It is only called if
+/// that value can't be used.
+async fn load<T, Ser, Fut>(
+    fun: Arc<impl Fn() -> Fut>,
+    pending: Option<PinnedFuture<Option<String>>>,
+) -> T
+where
+    Ser: Serializer,
+    T: SerializableData<Ser>,
+    T::DeErr: Debug,
+    Fut: Future<Output = T>,
+{
+    if let Some(pending) = pending {
+        if let Some(data) = pending.await {
+            match T::de(&data) {
+                Ok(value) => return value,
+                Err(e) => {
+                    crate::log(&format!(
+                        "couldn't deserialize from {data:?}: {e:?}"
+                    ));
+                }
+            }
+        }
+    }
+    fun().await
+}
+
 impl<T, Ser> IntoFuture for ArcResource<T, Ser>
 where
     T: Clone + 'static,
@@ -301,7 +358,7 @@ where
 
 impl<T, Ser> Resource<T, Ser>
 where
-    Ser: Serializer,
+    Ser: Serializer + 'static,
     T: SerializableData<Ser> + Send + Sync + 'static,
     T::SerErr: Debug,
     T::DeErr: Debug,
@@ -318,8 +375,12 @@ where
             .unwrap_or_default();
 
         let initial = Self::initial_value(&id);
+        let pending = Arc::new(Mutex::new(pending_data(&id, &initial)));
 
-        let data = AsyncDerived::new_with_initial(initial, fun);
+        let fun = Arc::new(fun);
+        let data = AsyncDerived::new_with_initial(initial, move || {
+            load::<T, Ser, _>(Arc::clone(&fun), pending.lock().take())
+        });
 
         if let Some(shared_context) = Owner::shared_context() {
             let value = data;
@@ -346,6 +407,7 @@ where
     }
 
     #[inline(always)]
+    #[allow(unused_variables)]
     fn initial_value(id: &SerializedDataId) -> AsyncState<T> {
         #[cfg(feature = "hydration")]
         {
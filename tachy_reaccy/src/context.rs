@@ -1,7 +1,29 @@
 use crate::arena::Owner;
-use std::any::{Any, TypeId};
+use std::{
+    any::{type_name, Any, TypeId},
+    panic::Location,
+};
+use thiserror::Error;
+
+/// An error that can occur when providing context.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ContextError {
+    /// `provide_context` was called outside a reactive [`Owner`], so there
+    /// is nowhere to store the value.
+    #[error(
+        "At {location}, you tried to provide a context of type {type_name}, \
+         but there is no reactive Owner to provide it to."
+    )]
+    NoOwner {
+        type_name: &'static str,
+        location: &'static Location<'static>,
+    },
+}
 
 impl Owner {
+    // Contexts are keyed by type. Providing the same type again on the same
+    // owner replaces the old value; providing it on a child owner shadows the
+    // parent's value for that child and its descendants only.
     fn provide_context<T: Send + Sync + 'static>(&self, value: T) {
         self.inner
             .write()
@@ -12,35 +34,95 @@ impl Owner {
     fn use_context<T: Clone + 'static>(&self) -> Option<T> {
         let ty = TypeId::of::<T>();
         let inner = self.inner.read();
+        if let Some(context) = inner.contexts.get(&ty) {
+            return context.downcast_ref::<T>().cloned();
+        }
         let mut parent = inner.parent.as_ref().and_then(|p| p.upgrade());
-        let contexts = &self.inner.read().contexts;
-        if let Some(context) = contexts.get(&ty) {
-            context.downcast_ref::<T>().cloned()
-        } else {
-            while let Some(ref this_parent) = parent.clone() {
-                let this_parent = this_parent.read();
-                let contexts = &this_parent.contexts;
-                let value = contexts.get(&ty);
-                let downcast = value
-                    .and_then(|context| context.downcast_ref::<T>().cloned());
-                if let Some(value) = downcast {
-                    return Some(value);
-                } else {
-                    parent =
-                        this_parent.parent.as_ref().and_then(|p| p.upgrade());
-                }
+        drop(inner);
+        while let Some(this_parent) = parent {
+            let this_parent = this_parent.read();
+            let downcast = this_parent
+                .contexts
+                .get(&ty)
+                .and_then(|context| context.downcast_ref::<T>().cloned());
+            if let Some(value) = downcast {
+                return Some(value);
             }
-            None
+            parent = this_parent.parent.as_ref().and_then(|p| p.upgrade());
         }
+        None
     }
 }
 
+/// Provides a value of type `T` to the current [`Owner`] and all its
+/// descendants.
+///
+/// If there is no current owner, this warns and does nothing. Use
+/// [`try_provide_context`] to detect that case.
+#[track_caller]
 pub fn provide_context<T: Send + Sync + 'static>(value: T) {
-    if let Some(owner) = Owner::current() {
-        owner.provide_context(value);
+    if let Err(e) = try_provide_context(value) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!("{e}");
+        #[cfg(not(feature = "tracing"))]
+        crate::log(&e.to_string());
+    }
+}
+
+/// Provides a value of type `T` to the current [`Owner`] and all its
+/// descendants, returning an error if there is no current owner.
+///
+/// ## Errors
+/// Returns [`ContextError::NoOwner`] if there is no current owner, as there
+/// is nowhere to store the value.
+#[track_caller]
+pub fn try_provide_context<T: Send + Sync + 'static>(
+    value: T,
+) -> Result<(), ContextError> {
+    match Owner::current() {
+        Some(owner) => {
+            owner.provide_context(value);
+            Ok(())
+        }
+        None => Err(ContextError::NoOwner {
+            type_name: type_name::<T>(),
+            location: Location::caller(),
+        }),
     }
 }
 
+/// Returns the nearest context of type `T`, searching the current [`Owner`]
+/// and then each of its ancestors.
 pub fn use_context<T: Clone + 'static>() -> Option<T> {
     Owner::current().and_then(|owner| owner.use_context())
 }
+
+/// Returns the nearest context of type `T`, like [`use_context`].
+///
+/// ## Panics
+/// Panics with the type name, the caller's location and, in debug builds,
+/// where the current [`Owner`] was created, if no context of type `T` has
+/// been provided.
+#[track_caller]
+pub fn expect_context<T: Clone + 'static>() -> T {
+    let location = Location::caller();
+    let owner = Owner::current();
+    owner
+        .as_ref()
+        .and_then(|owner| owner.use_context())
+        .unwrap_or_else(|| {
+            let ty = type_name::<T>();
+            match owner.and_then(|owner| owner.defined_at()) {
+                Some(defined_at) => panic!(
+                    "At {location}, you tried to access a context of type \
+                     {ty}, but it was not provided by the Owner defined at \
+                     {defined_at} or any of its ancestors."
+                ),
+                None => panic!(
+                    "At {location}, you tried to access a context of type \
+                     {ty}, but it was not provided by this Owner or any of \
+                     its ancestors."
+                ),
+            }
+        })
+}
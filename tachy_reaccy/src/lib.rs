@@ -23,12 +23,17 @@ use std::{cell::RefCell, pin::Pin};
 pub mod prelude {
     pub use crate::{
         async_signal::{AsyncDerived, Resource},
-        context::{provide_context, use_context},
+        context::{
+            expect_context, provide_context, try_provide_context, use_context,
+        },
         effect::Effect,
         memo::{ArcMemo, Memo},
         signal::{signal, ArcRwSignal, ReadSignal, RwSignal},
         signal_traits::*,
-        store::{StoreField, StoreFieldIndex, StoreFieldIterator},
+        store::{
+            OptionStoreExt, StoreField, StoreFieldIndex, StoreFieldIterator,
+            StoreFieldMap,
+        },
         Owner, Root,
     };
 }
@@ -67,12 +72,14 @@ pub fn untrack<T>(fun: impl FnOnce() -> T) -> T {
     value
 }
 
-#[cfg(feature = "web")]
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
 pub fn log(s: &str) {
     web_sys::console::log_1(&wasm_bindgen::JsValue::from_str(s));
 }
 
-#[cfg(not(feature = "web"))]
+// the console is only available in the browser, so native tests still log
+// when the `web` feature is enabled
+#[cfg(not(all(feature = "web", target_arch = "wasm32")))]
 pub fn log(s: &str) {
     println!("{s}");
 }
@@ -62,6 +62,10 @@ impl<T: Send + Sync + 'static> StoredData for Memo<T> {
         self.inner.get()
     }
 
+    fn stored_defined_at(&self) -> Option<&'static Location<'static>> {
+        self.inner.defined_at()
+    }
+
     fn dispose(&self) {
         self.inner.dispose();
     }
@@ -1,6 +1,11 @@
 use core::str::FromStr;
 use serde::{de::DeserializeOwned, Serialize};
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be serialized with `{Ser}`",
+    note = "resource values and the props of islands must implement \
+            `SerializableData` for the serializer they use"
+)]
 pub trait SerializableData<Ser: Serializer>: Sized {
     type SerErr;
     type DeErr;
@@ -1,13 +1,36 @@
-use super::{SerializedDataId, SharedContext};
+use super::{SerializedDataId, SharedContext, SharedState};
 use crate::{PinnedFuture, PinnedStream};
 use core::fmt::Debug;
-use js_sys::Array;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use wasm_bindgen::prelude::wasm_bindgen;
+use futures::channel::oneshot;
+use js_sys::{Array, Object, Reflect};
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use wasm_bindgen::{closure::Closure, prelude::wasm_bindgen, JsValue};
 
+// defined by the resource script that is included in the hydration scripts,
+// and filled in by the scripts the server streams in as resources resolve
 #[wasm_bindgen]
 extern "C" {
     static __RESOLVED_RESOURCES: Array;
+
+    static __PENDING_RESOURCES: Array;
+
+    fn __awaitResource(id: u32, resolve: JsValue);
+
+    static __SHARED_STATE: Object;
+}
+
+thread_local! {
+    // every island on the page has its own root, but they share this state
+    static SHARED_STATE: RefCell<HashMap<&'static str, Arc<dyn Any + Send + Sync>>> =
+        Default::default();
 }
 
 #[derive(Default)]
@@ -50,19 +73,58 @@ impl SharedContext for HydrateSharedContext {
         __RESOLVED_RESOURCES.get(id.0 as u32).as_string()
     }
 
-    fn await_data(&self, _id: &SerializedDataId) -> Option<String> {
-        todo!()
+    fn await_data(
+        &self,
+        id: &SerializedDataId,
+    ) -> PinnedFuture<Option<String>> {
+        if let Some(data) = self.read_data(id) {
+            return Box::pin(async move { Some(data) });
+        }
+        let is_pending =
+            __PENDING_RESOURCES.includes(&JsValue::from(id.0 as u32), 0);
+        if !is_pending {
+            return Box::pin(async { None });
+        }
+
+        let (tx, rx) = oneshot::channel();
+        let resolve = Closure::once_into_js(move |data: JsValue| {
+            if let Some(data) = data.as_string() {
+                _ = tx.send(data);
+            }
+        });
+        __awaitResource(id.0 as u32, resolve);
+        Box::pin(async move { rx.await.ok() })
     }
 
     fn pending_data(&self) -> Option<PinnedStream<String>> {
         None
     }
 
+    fn shared_state(
+        &self,
+        name: &'static str,
+        init: &mut dyn FnMut(Option<String>) -> SharedState,
+    ) -> Arc<dyn Any + Send + Sync> {
+        let existing =
+            SHARED_STATE.with(|state| state.borrow().get(name).cloned());
+        if let Some(value) = existing {
+            return value;
+        }
+
+        let data = Reflect::get(&__SHARED_STATE, &JsValue::from_str(name))
+            .ok()
+            .and_then(|data| data.as_string());
+        let value = init(data).value;
+        SHARED_STATE
+            .with(|state| state.borrow_mut().insert(name, Arc::clone(&value)));
+        value
+    }
+
     fn get_is_hydrating(&self) -> bool {
         self.is_hydrating.load(Ordering::Relaxed)
     }
 
     fn set_is_hydrating(&self, is_hydrating: bool) {
-        self.is_hydrating.store(true, Ordering::Relaxed)
+        self.is_hydrating.store(is_hydrating, Ordering::Relaxed)
     }
 }
@@ -6,7 +6,7 @@ use crate::{PinnedFuture, PinnedStream};
 pub use hydrate::*;
 use serde::{Deserialize, Serialize};
 pub use ssr::*;
-use std::fmt::Debug;
+use std::{any::Any, fmt::Debug, sync::Arc};
 
 pub trait SharedContext: Debug {
     /// Returns the next in a series of IDs that is unique to a particular request and response.
@@ -33,11 +33,12 @@ pub trait SharedContext: Debug {
 
     /// Returns a [`Future`] that resolves with a `String` that should
     /// be deserialized using [`Serializable::de`] once the given piece of server
-    /// data has resolved.
+    /// data has resolved, or with [`None`] if the server is not going to send it.
     ///
     /// On the server and in client-side rendered implementations, this should
     /// return a [`Future`] that is immediately ready with [`None`].
-    fn await_data(&self, id: &SerializedDataId) -> Option<String>;
+    fn await_data(&self, id: &SerializedDataId)
+        -> PinnedFuture<Option<String>>;
 
     /// Returns some [`Stream`] of HTML that contains JavaScript `<script>` tags defining
     /// all values being serialized from the server to the client, with their serialized values
@@ -46,11 +47,40 @@ pub trait SharedContext: Debug {
     /// In browser implementations, this return `None`.
     fn pending_data(&self) -> Option<PinnedStream<String>>;
 
+    /// Returns the state that the islands on a page share under `name`.
+    ///
+    /// The first time a name is used, the state is created by calling `init`
+    /// with the value the server serialized for it, if any, and registered.
+    /// On the server, registered state is serialized as part of
+    /// [`pending_data`](SharedContext::pending_data), so that it can be
+    /// used to seed the same state in the browser.
+    fn shared_state(
+        &self,
+        name: &'static str,
+        init: &mut dyn FnMut(Option<String>) -> SharedState,
+    ) -> Arc<dyn Any + Send + Sync>;
+
     fn get_is_hydrating(&self) -> bool;
 
     fn set_is_hydrating(&self, is_hydrating: bool);
 }
 
+/// State that is shared between islands, registered with
+/// [`SharedContext::shared_state`].
+pub struct SharedState {
+    /// The shared value, such as a signal.
+    pub value: Arc<dyn Any + Send + Sync>,
+    /// Serializes the current value, to send it from the server to the
+    /// browser.
+    pub serialize: Box<dyn Fn() -> Option<String> + Send + Sync>,
+}
+
+impl Debug for SharedState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedState").finish_non_exhaustive()
+    }
+}
+
 #[derive(
     Clone, Debug, PartialEq, Eq, Hash, Default, Deserialize, Serialize,
 )]
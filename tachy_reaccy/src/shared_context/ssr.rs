@@ -1,4 +1,4 @@
-use super::{SerializedDataId, SharedContext};
+use super::{SerializedDataId, SharedContext, SharedState};
 use crate::{PinnedFuture, PinnedStream};
 use futures::{
     stream::{self, FuturesUnordered},
@@ -6,9 +6,14 @@ use futures::{
 };
 use parking_lot::RwLock;
 use std::{
+    any::Any,
+    collections::HashMap,
     fmt::{Debug, Write},
     mem,
-    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 #[derive(Default)]
@@ -17,6 +22,7 @@ pub struct SsrSharedContext {
     is_hydrating: AtomicBool,
     sync_buf: RwLock<Vec<ResolvedData>>,
     async_buf: RwLock<Vec<(SerializedDataId, PinnedFuture<String>)>>,
+    shared_state: RwLock<HashMap<&'static str, SharedState>>,
 }
 
 impl SsrSharedContext {
@@ -42,6 +48,7 @@ impl Debug for SsrSharedContext {
             .field("is_hydrating", &self.is_hydrating)
             .field("sync_buf", &self.sync_buf)
             .field("async_buf", &self.async_buf.read().len())
+            .field("shared_state", &self.shared_state.read().keys())
             .finish()
     }
 }
@@ -61,31 +68,40 @@ impl SharedContext for SsrSharedContext {
         let async_data = mem::take(&mut *self.async_buf.write());
 
         // 1) initial, synchronous setup chunk
-        let mut initial_chunk = String::new();
+        let mut initial_chunk = BOOTSTRAP_SCRIPT.to_string();
         // resolved synchronous resources
-        initial_chunk.push_str("__RESOLVED_RESOURCES=[");
         for resolved in sync_data {
             resolved.write_to_buf(&mut initial_chunk);
-            initial_chunk.push(',');
         }
-        initial_chunk.push_str("];");
 
-        // pending async resources
-        initial_chunk.push_str("__PENDING_RESOURCES=[");
+        // pending async resources, which the client will wait for rather
+        // than loading them itself
+        initial_chunk.push_str("__PENDING_RESOURCES.push(");
         for (id, _) in &async_data {
             write!(&mut initial_chunk, "{},", id.0).unwrap();
         }
-        initial_chunk.push_str("];");
-
-        // resolvers
-        initial_chunk.push_str("__RESOURCE_RESOLVERS=[];");
+        initial_chunk.push_str(");");
+
+        // state shared between islands, as it is after the page has rendered
+        for (name, state) in self.shared_state.read().iter() {
+            if let Some(data) = (state.serialize)() {
+                let data = escape_script(&data);
+                write!(
+                    &mut initial_chunk,
+                    "__SHARED_STATE[{name:?}] = {data};"
+                )
+                .unwrap();
+            }
+        }
 
-        // 2) async resources as they resolve
+        // 2) async resources as they resolve, which also wakes anything on
+        // the client that is waiting for them
         let async_data = async_data
             .into_iter()
             .map(|(id, data)| async move {
-                let data = data.await;
-                format!("__RESOLVED_RESOURCES[{}] = {data:?};", id.0)
+                let mut buf = String::new();
+                ResolvedData(id, data.await).write_to_buf(&mut buf);
+                buf
             })
             .collect::<FuturesUnordered<_>>();
 
@@ -98,8 +114,21 @@ impl SharedContext for SsrSharedContext {
         None
     }
 
-    fn await_data(&self, _id: &SerializedDataId) -> Option<String> {
-        None
+    fn await_data(
+        &self,
+        _id: &SerializedDataId,
+    ) -> PinnedFuture<Option<String>> {
+        Box::pin(async { None })
+    }
+
+    fn shared_state(
+        &self,
+        name: &'static str,
+        init: &mut dyn FnMut(Option<String>) -> SharedState,
+    ) -> Arc<dyn Any + Send + Sync> {
+        let mut shared_state = self.shared_state.write();
+        let state = shared_state.entry(name).or_insert_with(|| init(None));
+        Arc::clone(&state.value)
     }
 
     fn get_is_hydrating(&self) -> bool {
@@ -107,18 +136,38 @@ impl SharedContext for SsrSharedContext {
     }
 
     fn set_is_hydrating(&self, is_hydrating: bool) {
-        self.is_hydrating.store(true, Ordering::Relaxed)
+        self.is_hydrating.store(is_hydrating, Ordering::Relaxed)
     }
 }
 
+/// Defines the globals the streamed scripts write to, unless the resource
+/// script from the hydration scripts has already done so.
+///
+/// That script may be missing or only load after the first chunk. Until it
+/// runs, resolved resources are just stored for it to pick up.
+const BOOTSTRAP_SCRIPT: &str = concat!(
+    "window.__RESOLVED_RESOURCES = window.__RESOLVED_RESOURCES || [];",
+    "window.__PENDING_RESOURCES = window.__PENDING_RESOURCES || [];",
+    "window.__SHARED_STATE = window.__SHARED_STATE || {};",
+    "window.__resolveResource = window.__resolveResource || ",
+    "function (id, data) { __RESOLVED_RESOURCES[id] = data; };",
+);
+
 #[derive(Debug)]
 struct ResolvedData(SerializedDataId, String);
 
 impl ResolvedData {
     pub fn write_to_buf(&self, buf: &mut String) {
         let ResolvedData(id, ser) = self;
-        // escapes < to prevent it being interpreted as another opening HTML tag
-        let ser = ser.replace('<', "\\u003c");
-        write!(buf, "{}: {:?}", id.0, ser).unwrap();
+        let ser = escape_script(ser);
+        write!(buf, "__resolveResource({}, {ser});", id.0).unwrap();
     }
 }
+
+/// Formats `data` as a JavaScript string literal that can be included in a
+/// `<script>` tag.
+fn escape_script(data: &str) -> String {
+    // escapes < inside the string literal, to prevent it being
+    // interpreted as another opening HTML tag
+    format!("{data:?}").replace('<', "\\u003c")
+}
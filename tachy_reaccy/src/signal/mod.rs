@@ -9,7 +9,7 @@ use crate::{
 pub use arc_signal::ArcRwSignal;
 pub use read::*;
 pub mod trigger;
-use std::{fmt::Debug, sync::Arc};
+use std::{fmt::Debug, panic::Location, sync::Arc};
 pub use write::*;
 
 pub fn signal<T>(initial_value: T) -> (ReadSignal<T>, WriteSignal<T>)
@@ -33,6 +33,7 @@ pub struct RwSignal<T: Send + Sync + 'static> {
 }
 
 impl<T: Send + Sync + 'static> RwSignal<T> {
+    #[track_caller]
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(level = "debug", skip_all,)
@@ -105,7 +106,7 @@ impl<T: Send + Sync + 'static> Debug for RwSignal<T> {
 
 impl<T: Send + Sync + 'static> SignalIsDisposed for RwSignal<T> {
     fn is_disposed(&self) -> bool {
-        self.inner.exists()
+        !self.inner.exists()
     }
 }
 
@@ -116,6 +117,10 @@ impl<T: Send + Sync + 'static> StoredData for RwSignal<T> {
         self.inner.get()
     }
 
+    fn stored_defined_at(&self) -> Option<&'static Location<'static>> {
+        self.inner.defined_at()
+    }
+
     fn dispose(&self) {
         self.inner.dispose();
     }
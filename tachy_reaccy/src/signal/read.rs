@@ -25,6 +25,10 @@ impl<T: Send + Sync + 'static> StoredData for ReadSignal<T> {
         self.inner.get()
     }
 
+    fn stored_defined_at(&self) -> Option<&'static Location<'static>> {
+        self.inner.defined_at()
+    }
+
     fn dispose(&self) {
         self.inner.dispose();
     }
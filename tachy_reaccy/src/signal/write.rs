@@ -3,6 +3,7 @@ use crate::{
     arena::{Stored, StoredData},
     prelude::{SignalIsDisposed, SignalUpdate, SignalUpdateUntracked, Trigger},
 };
+use std::panic::Location;
 
 pub struct WriteSignal<T: Send + Sync + 'static> {
     pub(crate) inner: Stored<ArcWriteSignal<T>>,
@@ -15,6 +16,10 @@ impl<T: Send + Sync + 'static> StoredData for WriteSignal<T> {
         self.inner.get()
     }
 
+    fn stored_defined_at(&self) -> Option<&'static Location<'static>> {
+        self.inner.defined_at()
+    }
+
     fn dispose(&self) {
         self.inner.dispose();
     }
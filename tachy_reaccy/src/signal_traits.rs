@@ -63,6 +63,7 @@ pub trait SignalGetUntracked: SignalWithUntracked
 where
     Self::Value: Clone,
 {
+    #[track_caller]
     fn try_get_untracked(&self) -> Option<Self::Value> {
         self.try_with_untracked(Self::Value::clone)
     }
@@ -85,6 +86,7 @@ pub trait SignalGet: SignalWith
 where
     Self::Value: Clone,
 {
+    #[track_caller]
     fn try_get(&self) -> Option<Self::Value> {
         self.try_with(Self::Value::clone)
     }
@@ -110,6 +112,7 @@ pub trait Trigger {
 pub trait SignalUpdateUntracked {
     type Value;
 
+    #[track_caller]
     fn try_update_untracked<U>(
         &self,
         fun: impl FnOnce(&mut Self::Value) -> U,
@@ -119,10 +122,12 @@ pub trait SignalUpdateUntracked {
 pub trait SignalUpdate {
     type Value;
 
+    #[track_caller]
     fn update(&self, fun: impl FnOnce(&mut Self::Value)) {
         self.try_update(fun);
     }
 
+    #[track_caller]
     fn try_update<U>(
         &self,
         fun: impl FnOnce(&mut Self::Value) -> U,
@@ -135,6 +140,7 @@ where
 {
     type Value = T::Value;
 
+    #[track_caller]
     fn try_update<U>(
         &self,
         fun: impl FnOnce(&mut Self::Value) -> U,
@@ -146,6 +152,7 @@ where
 }
 
 pub trait SignalSet: SignalUpdate + SignalIsDisposed {
+    #[track_caller]
     fn set(&self, value: impl Into<Self::Value>) {
         self.update(|n| *n = value.into());
     }
@@ -1,10 +1,40 @@
+use crate::async_signal::ScopedFuture;
 use cfg_if::cfg_if;
-use std::future::Future;
+use std::{cell::RefCell, future::Future, pin::Pin, rc::Rc};
+
+// Spawned tasks carry the `Owner` that was current when they were spawned, so
+// that `use_context` and `Owner::on_cleanup` keep working after an `.await`.
+
+type LocalExecutor = Rc<dyn Fn(Pin<Box<dyn Future<Output = ()>>>)>;
+
+thread_local! {
+    static LOCAL_EXECUTOR: RefCell<Option<LocalExecutor>> = RefCell::new(None);
+}
+
+/// Runs every task that is spawned on this thread, with either [`spawn`] or
+/// [`spawn_local`], on `executor` instead of the default executor.
+///
+/// This lets a test harness, like the one for a mock renderer, run effects
+/// and resources itself, so that it can tell when they have all settled.
+pub fn set_local_executor(
+    executor: impl Fn(Pin<Box<dyn Future<Output = ()>>>) + 'static,
+) {
+    LOCAL_EXECUTOR.with(|e| *e.borrow_mut() = Some(Rc::new(executor)));
+}
+
+fn local_executor() -> Option<LocalExecutor> {
+    LOCAL_EXECUTOR.with(|e| e.borrow().clone())
+}
 
 pub fn spawn_local<F>(fut: F)
 where
     F: Future<Output = ()> + 'static,
 {
+    let fut = ScopedFuture::new_untracked(fut);
+    if let Some(executor) = local_executor() {
+        executor(Box::pin(fut));
+        return;
+    }
     cfg_if! {
         if #[cfg(target_arch = "wasm32")] {
             wasm_bindgen_futures::spawn_local(fut)
@@ -29,6 +59,11 @@ pub fn spawn<F>(fut: F)
 where
     F: Future<Output = ()> + Send + 'static,
 {
+    let fut = ScopedFuture::new_untracked(fut);
+    if let Some(executor) = local_executor() {
+        executor(Box::pin(fut));
+        return;
+    }
     cfg_if! {
         if #[cfg(target_arch = "wasm32")] {
             wasm_bindgen_futures::spawn_local(fut)
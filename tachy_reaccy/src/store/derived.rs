@@ -0,0 +1,460 @@
+use super::{ArcStore, StoreField, StorePath, StorePathSegment};
+use crate::{
+    arena::{Owner, Stored, StoredData},
+    signal::trigger::ArcTrigger,
+    signal_traits::{DefinedAt, SignalIsDisposed, SignalWithUntracked},
+    source::{
+        AnySource, AnySubscriber, ReactiveNode, ReactiveNodeState, SourceSet,
+        Subscriber, Track,
+    },
+    untrack, unwrap_signal,
+};
+use parking_lot::{
+    MappedRwLockReadGuard, MappedRwLockWriteGuard, RwLock, RwLockReadGuard,
+    RwLockWriteGuard,
+};
+use std::{
+    fmt::Debug,
+    iter,
+    panic::Location,
+    sync::{Arc, Weak},
+};
+
+/// A memoized projection over one or more stores.
+///
+/// The projection tracks exactly the [`StorePath`]s it reads, so it only
+/// reruns when one of those fields is triggered. Its output is itself a
+/// [`StoreField`], which means it can be indexed, iterated with
+/// [`iter`](super::StoreFieldIterator::iter) or
+/// [`iter_keyed`](super::KeyedStoreFieldIterator::iter_keyed), and read
+/// field-by-field without cloning the whole value.
+///
+/// Writing through a derived store only changes the cached value, which
+/// will be replaced the next time the projection reruns.
+pub struct ArcDerivedStore<T> {
+    #[cfg(debug_assertions)]
+    defined_at: &'static Location<'static>,
+    store: ArcStore<T>,
+    inner: Arc<RwLock<DerivedStoreInner<T>>>,
+}
+
+struct DerivedStoreInner<T> {
+    #[allow(clippy::type_complexity)]
+    fun: Arc<dyn Fn() -> T + Send + Sync>,
+    store: ArcStore<T>,
+    owner: Owner,
+    state: ReactiveNodeState,
+    sources: SourceSet,
+    any_subscriber: AnySubscriber,
+    disposed: bool,
+}
+
+impl<T> Clone for ArcDerivedStore<T> {
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            defined_at: self.defined_at,
+            store: self.store.clone(),
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> Debug for ArcDerivedStore<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArcDerivedStore")
+            .field("type", &std::any::type_name::<T>())
+            .field("data", &self.inner.data_ptr())
+            .finish()
+    }
+}
+
+impl<T> ArcDerivedStore<T>
+where
+    T: PartialEq + Send + Sync + 'static,
+{
+    #[track_caller]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all,)
+    )]
+    pub fn new(fun: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        // the initial value is read without tracking; the projection will
+        // rerun and subscribe to its sources the first time it is read
+        let store = ArcStore::new(untrack(&fun));
+        let inner = Arc::new_cyclic(|weak| {
+            let any_subscriber = AnySubscriber(
+                weak.as_ptr() as usize,
+                Weak::clone(weak) as Weak<dyn Subscriber + Send + Sync>,
+            );
+
+            RwLock::new(DerivedStoreInner {
+                fun: Arc::new(fun),
+                store: store.clone(),
+                owner: Owner::new(),
+                state: ReactiveNodeState::Dirty,
+                sources: Default::default(),
+                any_subscriber,
+                disposed: false,
+            })
+        });
+
+        // the projection stops rerunning once the owner it was created in
+        // is cleaned up
+        Owner::on_cleanup({
+            let inner = Arc::downgrade(&inner);
+            move || {
+                if let Some(inner) = inner.upgrade() {
+                    dispose(&inner);
+                }
+            }
+        });
+        Self {
+            #[cfg(debug_assertions)]
+            defined_at: Location::caller(),
+            store,
+            inner,
+        }
+    }
+}
+
+impl<T: PartialEq + Send + Sync + 'static> DerivedStoreInner<T> {
+    // Every path that has been read from the derived store has its own
+    // trigger. We can't know which of those paths has changed until the
+    // projection reruns, so all of them need to be checked.
+    fn triggers(&self) -> Vec<ArcTrigger> {
+        self.store.signals.read().0.values().cloned().collect()
+    }
+}
+
+fn dispose<T>(inner: &RwLock<DerivedStoreInner<T>>) {
+    let (owner, any_subscriber) = {
+        let mut lock = inner.write();
+        lock.disposed = true;
+        (lock.owner.clone(), lock.any_subscriber.clone())
+    };
+    any_subscriber.clear_sources(&any_subscriber);
+    owner.with_cleanup(|| {});
+}
+
+impl<T: PartialEq + Send + Sync + 'static> ReactiveNode
+    for RwLock<DerivedStoreInner<T>>
+{
+    fn mark_dirty(&self) {
+        self.write().state = ReactiveNodeState::Dirty;
+        self.mark_subscribers_check();
+    }
+
+    fn mark_check(&self) {
+        {
+            let mut lock = self.write();
+            if lock.state == ReactiveNodeState::Clean {
+                lock.state = ReactiveNodeState::Check;
+            }
+        }
+        self.mark_subscribers_check();
+    }
+
+    fn mark_subscribers_check(&self) {
+        let triggers = self.read().triggers();
+        for trigger in triggers {
+            trigger.mark_subscribers_check();
+        }
+    }
+
+    fn update_if_necessary(&self) -> bool {
+        let (state, sources, disposed) = {
+            let inner = self.read();
+            (inner.state, inner.sources.clone(), inner.disposed)
+        };
+        if disposed {
+            return false;
+        }
+
+        let needs_update = match state {
+            ReactiveNodeState::Clean => false,
+            ReactiveNodeState::Dirty => true,
+            ReactiveNodeState::Check => (&sources).into_iter().any(|source| {
+                source.update_if_necessary()
+                    || self.read().state == ReactiveNodeState::Dirty
+            }),
+        };
+
+        if needs_update {
+            let (fun, owner, store, any_subscriber) = {
+                let lock = self.read();
+                (
+                    lock.fun.clone(),
+                    lock.owner.clone(),
+                    lock.store.clone(),
+                    lock.any_subscriber.clone(),
+                )
+            };
+
+            any_subscriber.clear_sources(&any_subscriber);
+            let new_value =
+                owner.with_cleanup(|| any_subscriber.with_observer(|| fun()));
+
+            let changed = {
+                let mut value = store.value.write();
+                if *value != new_value {
+                    *value = new_value;
+                    true
+                } else {
+                    false
+                }
+            };
+            self.write().state = ReactiveNodeState::Clean;
+
+            // anything that has read one of the paths of the derived store
+            // needs to know that the value behind it has been replaced
+            if changed {
+                for trigger in self.read().triggers() {
+                    trigger.notify();
+                }
+            }
+            changed
+        } else {
+            self.write().state = ReactiveNodeState::Clean;
+            false
+        }
+    }
+}
+
+impl<T: PartialEq + Send + Sync + 'static> Subscriber
+    for RwLock<DerivedStoreInner<T>>
+{
+    fn add_source(&self, source: AnySource) {
+        self.write().sources.insert(source);
+    }
+
+    fn clear_sources(&self, subscriber: &AnySubscriber) {
+        self.write().sources.clear_sources(subscriber);
+    }
+}
+
+impl<T> DefinedAt for ArcDerivedStore<T> {
+    #[inline(always)]
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(debug_assertions)]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            None
+        }
+    }
+}
+
+impl<T: PartialEq + Send + Sync + 'static> Track for ArcDerivedStore<T> {
+    fn track(&self) {
+        self.store.get_trigger(StorePath::from(Vec::new())).track();
+    }
+}
+
+impl<T: PartialEq + Send + Sync + 'static> SignalWithUntracked
+    for ArcDerivedStore<T>
+{
+    type Value = T;
+
+    fn try_with_untracked<U>(
+        &self,
+        fun: impl FnOnce(&Self::Value) -> U,
+    ) -> Option<U> {
+        self.inner.update_if_necessary();
+        Some(fun(&self.store.value.read()))
+    }
+}
+
+impl<T> SignalIsDisposed for ArcDerivedStore<T> {
+    fn is_disposed(&self) -> bool {
+        self.inner.read().disposed
+    }
+}
+
+impl<T: PartialEq + Send + Sync + 'static> StoreField<T>
+    for ArcDerivedStore<T>
+{
+    type Orig = T;
+
+    fn data(&self) -> Arc<RwLock<Self::Orig>> {
+        self.store.data()
+    }
+
+    fn get_trigger(&self, path: StorePath) -> ArcTrigger {
+        self.store.get_trigger(path)
+    }
+
+    fn path(&self) -> impl Iterator<Item = StorePathSegment> {
+        iter::empty()
+    }
+
+    fn reader(
+        &self,
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockReadGuard<'a, T>>
+           + Send
+           + Sync
+           + 'static {
+        reader(Arc::clone(&self.inner))
+    }
+
+    fn writer(
+        self,
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockWriteGuard<'a, T>>
+           + Send
+           + Sync
+           + 'static {
+        writer(self.inner)
+    }
+}
+
+// The accessors only hold on to the projection itself, so that they can be
+// returned from a `DerivedStore` whose arena slot is only borrowed.
+fn reader<T: PartialEq + Send + Sync + 'static>(
+    inner: Arc<RwLock<DerivedStoreInner<T>>>,
+) -> impl for<'a> Fn(&'a RwLock<T>) -> Option<MappedRwLockReadGuard<'a, T>>
+       + Send
+       + Sync
+       + 'static {
+    move |lock| {
+        inner.update_if_necessary();
+        let guard = lock.read();
+        Some(RwLockReadGuard::map(guard, |n| n))
+    }
+}
+
+fn writer<T: PartialEq + Send + Sync + 'static>(
+    inner: Arc<RwLock<DerivedStoreInner<T>>>,
+) -> impl for<'a> Fn(&'a RwLock<T>) -> Option<MappedRwLockWriteGuard<'a, T>>
+       + Send
+       + Sync
+       + 'static {
+    move |lock| {
+        inner.update_if_necessary();
+        let guard = lock.write();
+        Some(RwLockWriteGuard::map(guard, |n| n))
+    }
+}
+
+pub struct DerivedStore<T: Send + Sync + 'static> {
+    inner: Stored<ArcDerivedStore<T>>,
+}
+
+impl<T: PartialEq + Send + Sync + 'static> DerivedStore<T> {
+    #[track_caller]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all,)
+    )]
+    pub fn new(fun: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        Self {
+            inner: Stored::new(ArcDerivedStore::new(fun)),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> Copy for DerivedStore<T> {}
+
+impl<T: Send + Sync + 'static> Clone for DerivedStore<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Send + Sync + 'static> Debug for DerivedStore<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DerivedStore")
+            .field("type", &std::any::type_name::<T>())
+            .field("store", &self.inner)
+            .finish()
+    }
+}
+
+impl<T: Send + Sync + 'static> SignalIsDisposed for DerivedStore<T> {
+    fn is_disposed(&self) -> bool {
+        self.inner
+            .with_value(|inner| inner.is_disposed())
+            .unwrap_or(true)
+    }
+}
+
+impl<T: Send + Sync + 'static> StoredData for DerivedStore<T> {
+    type Data = ArcDerivedStore<T>;
+
+    fn get_value(&self) -> Option<Self::Data> {
+        self.inner.get()
+    }
+
+    fn stored_defined_at(&self) -> Option<&'static Location<'static>> {
+        self.inner.defined_at()
+    }
+
+    fn dispose(&self) {
+        self.inner.dispose();
+    }
+}
+
+impl<T: PartialEq + Send + Sync + 'static> Track for DerivedStore<T> {
+    fn track(&self) {
+        if let Some(inner) = self.inner.get() {
+            inner.track();
+        }
+    }
+}
+
+impl<T: PartialEq + Send + Sync + 'static> StoreField<T> for DerivedStore<T> {
+    type Orig = T;
+
+    fn data(&self) -> Arc<RwLock<Self::Orig>> {
+        self.inner
+            .get()
+            .map(|inner| inner.data())
+            .unwrap_or_else(unwrap_signal!(self))
+    }
+
+    fn get_trigger(&self, path: StorePath) -> ArcTrigger {
+        self.inner
+            .get()
+            .map(|inner| inner.get_trigger(path))
+            .unwrap_or_else(unwrap_signal!(self))
+    }
+
+    fn path(&self) -> impl Iterator<Item = StorePathSegment> {
+        iter::empty()
+    }
+
+    fn reader(
+        &self,
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockReadGuard<'a, T>>
+           + Send
+           + Sync
+           + 'static {
+        let inner = self
+            .inner
+            .with_value(|store| Arc::clone(&store.inner))
+            .unwrap_or_else(unwrap_signal!(self));
+        reader(inner)
+    }
+
+    fn writer(
+        self,
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockWriteGuard<'a, T>>
+           + Send
+           + Sync
+           + 'static {
+        let inner = self
+            .inner
+            .with_value(|store| Arc::clone(&store.inner))
+            .unwrap_or_else(unwrap_signal!(self));
+        writer(inner)
+    }
+}
@@ -0,0 +1,306 @@
+use super::{ArcStore, Store, StoreField, StorePath};
+use crate::{
+    arena::{Stored, StoredData},
+    signal_traits::DefinedAt,
+    unwrap_signal,
+};
+use parking_lot::RwLock;
+use std::{collections::VecDeque, mem, sync::Arc};
+
+/// An opt-in undo/redo history for a store.
+///
+/// Each write made through a [`StoreField`] records an inverse patch for the
+/// [`StorePath`] that was written, along with a snapshot of the store from
+/// before the write. Undoing or redoing a change only restores the paths it
+/// touched, and only notifies the triggers for those paths.
+///
+/// Only the snapshot needs `T: Clone`, so that bound is only required by
+/// [`ArcStore::with_history`], and not by writes to the store's fields.
+pub(crate) struct History<T> {
+    snapshot: fn(&T) -> T,
+    depth: usize,
+    undo: VecDeque<Patch<T>>,
+    redo: Vec<Patch<T>>,
+    // the number of writes currently in progress; a write through a field
+    // is also a write to each of its parents, but only the outermost one is
+    // recorded
+    writing: usize,
+    // the changes that have been grouped together by `ArcStore::transaction`
+    transaction: Option<Patch<T>>,
+    transaction_depth: usize,
+}
+
+/// Swaps one field of the store with the same field of a snapshot of the
+/// store, taken before the write.
+///
+/// Calling it once undoes the write it was recorded for, and calling it again
+/// redoes it.
+pub type Undo<T> = Box<dyn Fn(&RwLock<T>, &RwLock<T>) + Send + Sync>;
+
+struct Change<T> {
+    path: StorePath,
+    undo: Undo<T>,
+    saved: RwLock<T>,
+}
+
+struct Patch<T> {
+    changes: Vec<Change<T>>,
+}
+
+impl<T> History<T> {
+    pub fn new(depth: usize) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            snapshot: T::clone,
+            depth,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            writing: 0,
+            transaction: None,
+            transaction_depth: 0,
+        }
+    }
+
+    /// Returns `true` if this is the outermost write.
+    pub fn begin_write(&mut self) -> bool {
+        self.writing += 1;
+        self.writing == 1
+    }
+
+    pub fn end_write(&mut self, path: StorePath, undo: Option<(Undo<T>, T)>) {
+        self.writing = self.writing.saturating_sub(1);
+        let Some((undo, saved)) = undo else {
+            return;
+        };
+        let change = Change {
+            path,
+            undo,
+            saved: RwLock::new(saved),
+        };
+        if self.transaction_depth > 0 {
+            self.transaction
+                .get_or_insert_with(|| Patch {
+                    changes: Vec::new(),
+                })
+                .changes
+                .push(change);
+        } else {
+            self.push(Patch {
+                changes: vec![change],
+            });
+        }
+    }
+
+    pub fn begin_transaction(&mut self) {
+        self.transaction_depth += 1;
+    }
+
+    pub fn end_transaction(&mut self) {
+        self.transaction_depth = self.transaction_depth.saturating_sub(1);
+        if self.transaction_depth == 0 {
+            if let Some(patch) = self.transaction.take() {
+                self.push(patch);
+            }
+        }
+    }
+
+    fn push(&mut self, patch: Patch<T>) {
+        self.redo.clear();
+        self.undo.push_back(patch);
+        while self.undo.len() > self.depth {
+            self.undo.pop_front();
+        }
+    }
+}
+
+/// Creates the inverse patch for a write to `field`.
+pub(crate) fn undo_write<F, T>(field: &F) -> Undo<F::Orig>
+where
+    F: StoreField<T> + Clone + 'static,
+{
+    let write = field.clone().writer();
+    Box::new(move |lock, saved| {
+        if let (Some(mut current), Some(mut saved)) =
+            (write(lock), write(saved))
+        {
+            mem::swap(&mut *current, &mut *saved)
+        }
+    })
+}
+
+impl<T> ArcStore<T> {
+    /// Creates a store that keeps an undo/redo history of up to `depth`
+    /// changes.
+    #[track_caller]
+    pub fn with_history(value: T, depth: usize) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let mut store = Self::new(value);
+        store.history = Some(Arc::new(RwLock::new(History::new(depth))));
+        store
+    }
+
+    /// Creates the inverse patch for a write to the whole store.
+    pub(crate) fn undo_root_write(&self) -> Option<Undo<T>> {
+        self.history.as_ref()?;
+        Some(Box::new(|lock, saved| {
+            mem::swap(&mut *lock.write(), &mut *saved.write())
+        }))
+    }
+
+    pub(crate) fn record_in_history<U>(
+        &self,
+        path: StorePath,
+        undo: impl FnOnce() -> Option<Undo<T>>,
+        write: impl FnOnce() -> U,
+    ) -> U {
+        let Some(history) = &self.history else {
+            return write();
+        };
+        let outermost = history.write().begin_write();
+        let undo = if outermost {
+            undo().map(|undo| {
+                let snapshot = history.read().snapshot;
+                let saved = snapshot(&self.value.read());
+                (undo, saved)
+            })
+        } else {
+            None
+        };
+        let value = write();
+        history.write().end_write(path, undo);
+        value
+    }
+
+    /// Groups every change made inside `fun` into a single history entry.
+    pub fn transaction<U>(&self, fun: impl FnOnce() -> U) -> U {
+        self.with_history_mut(History::begin_transaction);
+        let value = fun();
+        self.with_history_mut(History::end_transaction);
+        value
+    }
+
+    /// Reverts the most recent change, returning `false` if there was
+    /// nothing to undo.
+    pub fn undo(&self) -> bool {
+        self.apply(
+            |history| history.undo.pop_back(),
+            |history, patch| history.redo.push(patch),
+            true,
+        )
+    }
+
+    /// Reapplies the most recently undone change, returning `false` if there
+    /// was nothing to redo.
+    pub fn redo(&self) -> bool {
+        self.apply(
+            |history| history.redo.pop(),
+            |history, patch| history.undo.push_back(patch),
+            false,
+        )
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.with_history_mut(|history| !history.undo.is_empty())
+            .unwrap_or(false)
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.with_history_mut(|history| !history.redo.is_empty())
+            .unwrap_or(false)
+    }
+
+    fn with_history_mut<U>(
+        &self,
+        fun: impl FnOnce(&mut History<T>) -> U,
+    ) -> Option<U> {
+        self.history
+            .as_ref()
+            .map(|history| fun(&mut history.write()))
+    }
+
+    fn apply(
+        &self,
+        take: impl FnOnce(&mut History<T>) -> Option<Patch<T>>,
+        put: impl FnOnce(&mut History<T>, Patch<T>),
+        reverse: bool,
+    ) -> bool {
+        let Some(mut patch) = self.with_history_mut(take).flatten() else {
+            return false;
+        };
+
+        // changes are undone in the opposite order to that in which they
+        // were made, so that a path written twice ends up with its oldest
+        // value
+        if reverse {
+            patch.changes.reverse();
+        }
+        for change in &patch.changes {
+            (change.undo)(&self.value, &change.saved);
+        }
+        if reverse {
+            patch.changes.reverse();
+        }
+        let paths = patch
+            .changes
+            .iter()
+            .map(|change| change.path.clone())
+            .collect::<Vec<_>>();
+        self.with_history_mut(|history| put(history, patch));
+
+        // notify the triggers directly, so this change isn't recorded again
+        for path in paths {
+            self.get_trigger(path).notify();
+        }
+        true
+    }
+}
+
+impl<T: Send + Sync + 'static> Store<T> {
+    /// Creates a store that keeps an undo/redo history of up to `depth`
+    /// changes.
+    #[track_caller]
+    pub fn with_history(value: T, depth: usize) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            inner: Stored::new(ArcStore::with_history(value, depth)),
+        }
+    }
+
+    /// Groups every change made inside `fun` into a single history entry.
+    #[track_caller]
+    pub fn transaction<U>(&self, fun: impl FnOnce() -> U) -> U {
+        self.get_value()
+            .unwrap_or_else(unwrap_signal!(self))
+            .transaction(fun)
+    }
+
+    /// Reverts the most recent change, returning `false` if there was
+    /// nothing to undo.
+    pub fn undo(&self) -> bool {
+        self.get_value().map(|inner| inner.undo()).unwrap_or(false)
+    }
+
+    /// Reapplies the most recently undone change, returning `false` if there
+    /// was nothing to redo.
+    pub fn redo(&self) -> bool {
+        self.get_value().map(|inner| inner.redo()).unwrap_or(false)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.get_value()
+            .map(|inner| inner.can_undo())
+            .unwrap_or(false)
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.get_value()
+            .map(|inner| inner.can_redo())
+            .unwrap_or(false)
+    }
+}
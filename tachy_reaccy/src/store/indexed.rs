@@ -1,4 +1,7 @@
-use super::{StoreField, StorePath, StorePathSegment};
+use super::{
+    history::{undo_write, Undo},
+    StoreField, StorePath, StorePathSegment,
+};
 use crate::{
     prelude::{
         DefinedAt, SignalIsDisposed, SignalUpdateUntracked,
@@ -89,6 +92,16 @@ where
         self.inner.get_trigger(path)
     }
 
+    #[inline(always)]
+    fn record_write<U>(
+        &self,
+        path: StorePath,
+        undo: impl FnOnce() -> Option<Undo<Self::Orig>>,
+        write: impl FnOnce() -> U,
+    ) -> U {
+        self.inner.record_write(path, undo, write)
+    }
+
     #[inline(always)]
     fn path(&self) -> impl Iterator<Item = StorePathSegment> {
         self.inner.path().chain(iter::once((&self.idx).into()))
@@ -98,7 +111,7 @@ where
         &self,
     ) -> impl for<'a> Fn(
         &'a RwLock<Self::Orig>,
-    ) -> MappedRwLockReadGuard<'a, Prev::Output>
+    ) -> Option<MappedRwLockReadGuard<'a, Prev::Output>>
            + Send
            + Sync
            + 'static {
@@ -106,9 +119,9 @@ where
         let idx = self.idx.clone();
         move |lock| {
             let inner = inner.reader();
-            let lock = inner(lock);
+            let lock = inner(lock)?;
             let idx = idx.clone();
-            MappedRwLockReadGuard::map(lock, |prev| &prev[idx])
+            Some(MappedRwLockReadGuard::map(lock, |prev| &prev[idx]))
         }
     }
 
@@ -116,15 +129,15 @@ where
         self,
     ) -> impl for<'a> Fn(
         &'a RwLock<Self::Orig>,
-    ) -> MappedRwLockWriteGuard<'a, Prev::Output>
+    ) -> Option<MappedRwLockWriteGuard<'a, Prev::Output>>
            + Send
            + Sync
            + 'static {
         move |lock| {
             let inner = self.inner.clone().writer();
-            let lock = inner(lock);
+            let lock = inner(lock)?;
             let idx = self.idx.clone();
-            MappedRwLockWriteGuard::map(lock, |prev| &mut prev[idx])
+            Some(MappedRwLockWriteGuard::map(lock, |prev| &mut prev[idx]))
         }
     }
 }
@@ -195,10 +208,16 @@ where
 
 impl<Inner, Prev, Idx> SignalUpdateUntracked for AtIndex<Inner, Prev, Idx>
 where
-    Inner: StoreField<Prev> + SignalUpdateUntracked<Value = Prev>,
+    Inner: StoreField<Prev>
+        + SignalUpdateUntracked<Value = Prev>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
     Prev: Index<Idx> + IndexMut<Idx> + 'static,
     Prev::Output: Sized,
-    Idx: Clone,
+    for<'a> &'a Idx: Into<StorePathSegment>,
+    Idx: Clone + Send + Sync + 'static,
 {
     type Value = Prev::Output;
 
@@ -206,10 +225,16 @@ where
         &self,
         fun: impl FnOnce(&mut Self::Value) -> U,
     ) -> Option<U> {
-        self.inner.try_update_untracked(|prev: &mut Prev| {
-            let this = &mut prev[self.idx.clone()];
-            fun(this)
-        })
+        self.record_write(
+            self.path().collect(),
+            || Some(undo_write(self)),
+            || {
+                self.inner.try_update_untracked(|prev: &mut Prev| {
+                    let this = &mut prev[self.idx.clone()];
+                    fun(this)
+                })
+            },
+        )
     }
 }
 
@@ -230,7 +255,7 @@ where
 
         // get the current length of the field by accessing slice
         let reader = self.reader();
-        let len = reader(&self.data()).as_ref().len();
+        let len = reader(&self.data()).map_or(0, |prev| prev.as_ref().len());
 
         // return the iterator
         StoreFieldIter {
@@ -1,4 +1,7 @@
-use super::{StoreField, StorePath, StorePathSegment};
+use super::{
+    history::{undo_write, Undo},
+    StoreField, StorePath, StorePathSegment,
+};
 use crate::{
     prelude::{
         DefinedAt, SignalIsDisposed, SignalUpdateUntracked,
@@ -116,6 +119,16 @@ where
         self.inner.get_trigger(path)
     }
 
+    #[inline(always)]
+    fn record_write<U>(
+        &self,
+        path: StorePath,
+        undo: impl FnOnce() -> Option<Undo<Self::Orig>>,
+        write: impl FnOnce() -> U,
+    ) -> U {
+        self.inner.record_write(path, undo, write)
+    }
+
     #[inline(always)]
     fn path(&self) -> impl Iterator<Item = StorePathSegment> {
         let segment = {
@@ -123,14 +136,14 @@ where
             let inner_reader = self.inner.reader();
             let inner_data = inner_reader(&data);
             let value = inner_data
-                .into_iter()
-                .enumerate()
-                .find_map(|(idx, row)| {
-                    if (self.key_fn)(row) == self.key {
-                        Some(idx.into())
-                    } else {
-                        None
-                    }
+                .and_then(|inner_data| {
+                    inner_data.into_iter().enumerate().find_map(|(idx, row)| {
+                        if (self.key_fn)(row) == self.key {
+                            Some(idx.into())
+                        } else {
+                            None
+                        }
+                    })
                 })
                 .unwrap(); // TODO
             value
@@ -142,7 +155,7 @@ where
         &self,
     ) -> impl for<'a> Fn(
         &'a RwLock<Self::Orig>,
-    ) -> MappedRwLockReadGuard<'a, Prev::Output>
+    ) -> Option<MappedRwLockReadGuard<'a, Prev::Output>>
            + Send
            + Sync
            + 'static {
@@ -151,10 +164,11 @@ where
         let key_fn = self.key_fn;
         move |lock| {
             let inner = inner.reader();
-            let lock = inner(lock);
-            MappedRwLockReadGuard::map(lock, |prev| {
-                (&prev).into_iter().find(|row| key_fn(row) == key).unwrap()
+            let lock = inner(lock)?;
+            MappedRwLockReadGuard::try_map(lock, |prev| {
+                prev.into_iter().find(|row| key_fn(row) == key)
             })
+            .ok()
         }
     }
 
@@ -162,15 +176,15 @@ where
         self,
     ) -> impl for<'a> Fn(
         &'a RwLock<Self::Orig>,
-    ) -> MappedRwLockWriteGuard<'a, Prev::Output>
+    ) -> Option<MappedRwLockWriteGuard<'a, Prev::Output>>
            + Send
            + Sync
            + 'static {
         move |lock| {
             let inner = self.inner.clone().writer();
-            let lock = inner(lock);
+            let lock = inner(lock)?;
             let key = self.key.clone();
-            MappedRwLockWriteGuard::map(lock, |prev| &mut prev[key])
+            Some(MappedRwLockWriteGuard::map(lock, |prev| &mut prev[key]))
         }
     }
 }
@@ -247,10 +261,16 @@ where
 impl<Inner, Prev, Row, Key> SignalUpdateUntracked
     for AtKey<Inner, Prev, Row, Key>
 where
-    Inner: StoreField<Prev> + SignalUpdateUntracked<Value = Prev>,
-    Prev: Index<Key> + IndexMut<Key> + 'static,
-    Prev::Output: Sized,
-    Key: Clone,
+    Inner: StoreField<Prev>
+        + SignalUpdateUntracked<Value = Prev>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+    Prev: Index<Key, Output = Row> + IndexMut<Key, Output = Row> + 'static,
+    Key: Clone + PartialEq + Send + Sync + 'static,
+    for<'a> &'a Prev: IntoIterator<Item = &'a Row>,
+    Row: 'static,
 {
     type Value = Prev::Output;
 
@@ -258,10 +278,16 @@ where
         &self,
         fun: impl FnOnce(&mut Self::Value) -> U,
     ) -> Option<U> {
-        self.inner.try_update_untracked(|prev: &mut Prev| {
-            let this = &mut prev[self.key.clone()];
-            fun(this)
-        })
+        self.record_write(
+            self.path().collect(),
+            || Some(undo_write(self)),
+            || {
+                self.inner.try_update_untracked(|prev: &mut Prev| {
+                    let this = &mut prev[self.key.clone()];
+                    fun(this)
+                })
+            },
+        )
     }
 }
 
@@ -288,7 +314,7 @@ where
 
         // get the current length of the field by accessing slice
         let reader = self.reader();
-        let len = reader(&self.data()).as_ref().len();
+        let len = reader(&self.data()).map_or(0, |prev| prev.as_ref().len());
 
         // return the iterator
         KeyedStoreFieldIter {
@@ -324,7 +350,7 @@ where
         if self.idx < self.len {
             let data = self.field.data();
             let reader = self.field.reader();
-            let key = (self.key_fn)(&reader(&data)[self.idx]);
+            let key = (self.key_fn)(&reader(&data)?[self.idx]);
             let field = self.field.clone().key(key, self.key_fn);
             self.idx += 1;
             Some(field)
@@ -0,0 +1,269 @@
+use super::{
+    history::{undo_write, Undo},
+    StoreField, StorePath, StorePathSegment,
+};
+use crate::{
+    prelude::{
+        DefinedAt, SignalIsDisposed, SignalUpdateUntracked,
+        SignalWithUntracked, Trigger,
+    },
+    signal::trigger::ArcTrigger,
+    source::Track,
+};
+use parking_lot::{MappedRwLockReadGuard, MappedRwLockWriteGuard, RwLock};
+use rustc_hash::FxHasher;
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash, Hasher},
+    iter,
+    marker::PhantomData,
+    panic::Location,
+    sync::Arc,
+};
+
+pub trait StoreFieldMap<Inner, K, V, S> {
+    /// Returns a field for the entry with the given key.
+    ///
+    /// Each key has its own trigger, so updating one entry only notifies the
+    /// subscribers that read that entry.
+    fn at_key(self, key: K) -> AtMapKey<Inner, K, V, S>;
+}
+
+impl<Inner, K, V, S> StoreFieldMap<Inner, K, V, S> for Inner
+where
+    Inner: StoreField<HashMap<K, V, S>> + Send + Sync + Clone + 'static,
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    #[track_caller]
+    fn at_key(self, key: K) -> AtMapKey<Inner, K, V, S> {
+        AtMapKey {
+            #[cfg(debug_assertions)]
+            defined_at: Location::caller(),
+            inner: self,
+            key,
+            ty: PhantomData,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AtMapKey<Inner, K, V, S> {
+    #[cfg(debug_assertions)]
+    defined_at: &'static Location<'static>,
+    inner: Inner,
+    key: K,
+    ty: PhantomData<fn() -> (V, S)>,
+}
+
+impl<Inner, K, V, S> Clone for AtMapKey<Inner, K, V, S>
+where
+    Inner: Clone,
+    K: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            defined_at: self.defined_at,
+            inner: self.inner.clone(),
+            key: self.key.clone(),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<Inner, K, V, S> Copy for AtMapKey<Inner, K, V, S>
+where
+    Inner: Copy,
+    K: Copy,
+{
+}
+
+impl<Inner, K, V, S> AtMapKey<Inner, K, V, S>
+where
+    K: Hash,
+{
+    // map keys don't have a position, so the path segment is derived from a
+    // stable hash of the key itself
+    fn path_segment(&self) -> StorePathSegment {
+        let mut hasher = FxHasher::default();
+        self.key.hash(&mut hasher);
+        (hasher.finish() as usize).into()
+    }
+}
+
+impl<Inner, K, V, S> StoreField<V> for AtMapKey<Inner, K, V, S>
+where
+    Inner: StoreField<HashMap<K, V, S>> + Send + Sync + Clone + 'static,
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: 'static,
+    S: BuildHasher + 'static,
+{
+    type Orig = Inner::Orig;
+
+    #[inline(always)]
+    fn data(&self) -> Arc<RwLock<Self::Orig>> {
+        self.inner.data()
+    }
+
+    #[inline(always)]
+    fn get_trigger(&self, path: StorePath) -> ArcTrigger {
+        self.inner.get_trigger(path)
+    }
+
+    #[inline(always)]
+    fn record_write<U>(
+        &self,
+        path: StorePath,
+        undo: impl FnOnce() -> Option<Undo<Self::Orig>>,
+        write: impl FnOnce() -> U,
+    ) -> U {
+        self.inner.record_write(path, undo, write)
+    }
+
+    #[inline(always)]
+    fn path(&self) -> impl Iterator<Item = StorePathSegment> {
+        self.inner.path().chain(iter::once(self.path_segment()))
+    }
+
+    fn reader(
+        &self,
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockReadGuard<'a, V>>
+           + Send
+           + Sync
+           + 'static {
+        let inner = self.inner.clone();
+        let key = self.key.clone();
+        move |lock| {
+            let inner = inner.reader();
+            let lock = inner(lock)?;
+            MappedRwLockReadGuard::try_map(lock, |prev| prev.get(&key)).ok()
+        }
+    }
+
+    fn writer(
+        self,
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockWriteGuard<'a, V>>
+           + Send
+           + Sync
+           + 'static {
+        move |lock| {
+            let inner = self.inner.clone().writer();
+            let lock = inner(lock)?;
+            MappedRwLockWriteGuard::try_map(lock, |prev| {
+                prev.get_mut(&self.key)
+            })
+            .ok()
+        }
+    }
+}
+
+impl<Inner, K, V, S> DefinedAt for AtMapKey<Inner, K, V, S> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(debug_assertions)]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            None
+        }
+    }
+}
+
+impl<Inner, K, V, S> Track for AtMapKey<Inner, K, V, S>
+where
+    Inner: StoreField<HashMap<K, V, S>> + Send + Sync + Clone + 'static,
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: 'static,
+    S: BuildHasher + 'static,
+{
+    fn track(&self) {
+        let trigger = self.get_trigger(self.path().collect());
+        trigger.track();
+    }
+}
+
+impl<Inner, K, V, S> SignalWithUntracked for AtMapKey<Inner, K, V, S>
+where
+    Inner: StoreField<HashMap<K, V, S>>
+        + SignalWithUntracked<Value = HashMap<K, V, S>>,
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    type Value = V;
+
+    fn try_with_untracked<U>(
+        &self,
+        fun: impl FnOnce(&Self::Value) -> U,
+    ) -> Option<U> {
+        self.inner
+            .try_with_untracked(|prev| prev.get(&self.key).map(fun))
+            .flatten()
+    }
+}
+
+impl<Inner, K, V, S> SignalIsDisposed for AtMapKey<Inner, K, V, S>
+where
+    Inner: StoreField<HashMap<K, V, S>>
+        + SignalWithUntracked<Value = HashMap<K, V, S>>,
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    // an entry whose key has been removed from the map can no longer be read
+    fn is_disposed(&self) -> bool {
+        self.inner
+            .try_with_untracked(|prev| !prev.contains_key(&self.key))
+            .unwrap_or(true)
+    }
+}
+
+impl<Inner, K, V, S> Trigger for AtMapKey<Inner, K, V, S>
+where
+    Inner: StoreField<HashMap<K, V, S>> + Send + Sync + Clone + 'static,
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: 'static,
+    S: BuildHasher + 'static,
+{
+    fn trigger(&self) {
+        self.get_trigger(self.path().collect()).notify();
+    }
+}
+
+impl<Inner, K, V, S> SignalUpdateUntracked for AtMapKey<Inner, K, V, S>
+where
+    Inner: StoreField<HashMap<K, V, S>>
+        + SignalUpdateUntracked<Value = HashMap<K, V, S>>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: 'static,
+    S: BuildHasher + 'static,
+{
+    type Value = V;
+
+    fn try_update_untracked<U>(
+        &self,
+        fun: impl FnOnce(&mut Self::Value) -> U,
+    ) -> Option<U> {
+        // writing to a key that isn't in the map changes nothing
+        let has_key = || self.reader()(&self.data()).is_some();
+        self.record_write(
+            self.path().collect(),
+            || has_key().then(|| undo_write(self)),
+            || {
+                self.inner
+                    .try_update_untracked(|prev| {
+                        prev.get_mut(&self.key).map(fun)
+                    })
+                    .flatten()
+            },
+        )
+    }
+}
@@ -8,10 +8,18 @@ use crate::{
 use parking_lot::{MappedRwLockReadGuard, MappedRwLockWriteGuard, RwLock};
 use rustc_hash::FxHashMap;
 use std::{fmt::Debug, panic::Location, sync::Arc};
+mod derived;
+pub use derived::*;
+mod history;
+use history::History;
 mod indexed;
 pub use indexed::*;
 mod keyed;
 pub use keyed::*;
+mod map;
+pub use map::*;
+mod option;
+pub use option::*;
 mod path;
 pub use path::*;
 mod stored;
@@ -22,6 +30,7 @@ pub struct Store<T: Send + Sync + 'static> {
 }
 
 impl<T: Send + Sync + 'static> Store<T> {
+    #[track_caller]
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(level = "debug", skip_all,)
@@ -52,7 +61,7 @@ impl<T: Debug + Send + Sync + 'static> Debug for Store<T> {
 
 impl<T: Send + Sync + 'static> SignalIsDisposed for Store<T> {
     fn is_disposed(&self) -> bool {
-        self.inner.exists()
+        !self.inner.exists()
     }
 }
 
@@ -63,6 +72,10 @@ impl<T: Send + Sync + 'static> StoredData for Store<T> {
         self.inner.get()
     }
 
+    fn stored_defined_at(&self) -> Option<&'static Location<'static>> {
+        self.inner.defined_at()
+    }
+
     fn dispose(&self) {
         self.inner.dispose();
     }
@@ -73,6 +86,7 @@ pub struct ArcStore<T> {
     defined_at: &'static Location<'static>,
     pub(crate) value: Arc<RwLock<T>>,
     signals: Arc<RwLock<TriggerMap>>,
+    history: Option<Arc<RwLock<History<T>>>>,
 }
 
 #[derive(Debug, Default)]
@@ -95,6 +109,7 @@ impl TriggerMap {
 }
 
 impl<T> ArcStore<T> {
+    #[track_caller]
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(level = "trace", skip_all,)
@@ -105,6 +120,7 @@ impl<T> ArcStore<T> {
             defined_at: Location::caller(),
             value: Arc::new(RwLock::new(value)),
             signals: Default::default(),
+            history: None,
             /* inner: Arc::new(RwLock::new(SubscriberSet::new())), */
         }
     }
@@ -128,6 +144,7 @@ impl<T> Clone for ArcStore<T> {
             defined_at: self.defined_at,
             value: Arc::clone(&self.value),
             signals: Arc::clone(&self.signals),
+            history: self.history.clone(),
         }
     }
 }
@@ -156,7 +173,11 @@ impl<T> SignalUpdateUntracked for ArcStore<T> {
         &self,
         fun: impl FnOnce(&mut Self::Value) -> U,
     ) -> Option<U> {
-        Some(fun(&mut self.value.write()))
+        self.record_write(
+            self.path().collect(),
+            || self.undo_root_write(),
+            || Some(fun(&mut self.value.write())),
+        )
     }
 }
 
@@ -175,12 +196,14 @@ where
     data: Arc<RwLock<Orig>>,
     trigger: ArcTrigger,
     read: Arc<
-        dyn for<'a> Fn(&'a RwLock<Orig>) -> MappedRwLockReadGuard<'a, T>
+        dyn for<'a> Fn(&'a RwLock<Orig>) -> Option<MappedRwLockReadGuard<'a, T>>
             + Send
             + Sync,
     >,
     write: Arc<
-        dyn for<'a> Fn(&'a RwLock<Orig>) -> MappedRwLockWriteGuard<'a, T>
+        dyn for<'a> Fn(
+                &'a RwLock<Orig>,
+            ) -> Option<MappedRwLockWriteGuard<'a, T>>
             + Send
             + Sync,
     >,
@@ -285,7 +308,7 @@ impl<Orig, T> SignalWithUntracked for ArcRwStoreField<Orig, T> {
         &self,
         fun: impl FnOnce(&Self::Value) -> U,
     ) -> Option<U> {
-        Some(fun(&*(self.read)(&self.data)))
+        (self.read)(&self.data).map(|value| fun(&*value))
     }
 }
 
@@ -302,7 +325,7 @@ impl<Orig, T> SignalUpdateUntracked for ArcRwStoreField<Orig, T> {
         &self,
         fun: impl FnOnce(&mut Self::Value) -> U,
     ) -> Option<U> {
-        Some(fun(&mut *(self.write)(&self.data)))
+        (self.write)(&self.data).map(|mut value| fun(&mut *value))
     }
 }
 
@@ -321,7 +344,7 @@ where
     data: Arc<RwLock<Orig>>,
     trigger: ArcTrigger,
     read: Arc<
-        dyn for<'a> Fn(&'a RwLock<Orig>) -> MappedRwLockReadGuard<'a, T>
+        dyn for<'a> Fn(&'a RwLock<Orig>) -> Option<MappedRwLockReadGuard<'a, T>>
             + Send
             + Sync,
     >,
@@ -365,7 +388,7 @@ impl<Orig, T> SignalWithUntracked for ArcReadStoreField<Orig, T> {
         &self,
         fun: impl FnOnce(&Self::Value) -> U,
     ) -> Option<U> {
-        Some(fun(&*(self.read)(&self.data)))
+        (self.read)(&self.data).map(|value| fun(&*value))
     }
 }
 
@@ -378,7 +401,9 @@ where
     data: Arc<RwLock<Orig>>,
     trigger: ArcTrigger,
     write: Arc<
-        dyn for<'a> Fn(&'a RwLock<Orig>) -> MappedRwLockWriteGuard<'a, T>
+        dyn for<'a> Fn(
+                &'a RwLock<Orig>,
+            ) -> Option<MappedRwLockWriteGuard<'a, T>>
             + Send
             + Sync,
     >,
@@ -422,7 +447,7 @@ impl<Orig, T> SignalUpdateUntracked for ArcWriteStoreField<Orig, T> {
         &self,
         fun: impl FnOnce(&mut Self::Value) -> U,
     ) -> Option<U> {
-        Some(fun(&mut *(self.write)(&self.data)))
+        (self.write)(&self.data).map(|mut value| fun(&mut *value))
     }
 }
 
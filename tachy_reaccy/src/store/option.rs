@@ -0,0 +1,29 @@
+use super::{track_field, StoreField, Subfield};
+
+pub trait OptionStoreExt<T>: StoreField<Option<T>> {
+    /// Reactively checks whether the field currently holds a value.
+    fn is_some(&self) -> bool;
+
+    /// Returns a subfield for the inner value if the field is currently
+    /// `Some(_)`, reactively tracking whether it is `Some` or `None`.
+    ///
+    /// If the field is later set back to `None`, the subfield is treated as
+    /// disposed: `try_get` and `try_update` return `None`.
+    fn some(self) -> Option<Subfield<Self, Option<T>, T>>;
+}
+
+impl<Inner, T> OptionStoreExt<T> for Inner
+where
+    Inner: StoreField<Option<T>>,
+{
+    fn is_some(&self) -> bool {
+        track_field(self, Option::is_some).unwrap_or(false)
+    }
+
+    #[track_caller]
+    fn some(self) -> Option<Subfield<Self, Option<T>, T>> {
+        self.is_some().then(|| {
+            Subfield::new(self, 0.into(), Option::as_ref, Option::as_mut)
+        })
+    }
+}
@@ -1,11 +1,12 @@
 use super::{
+    history::{undo_write, Undo},
     ArcReadStoreField, ArcRwStoreField, ArcStore, ArcWriteStoreField,
     ReadStoreField, RwStoreField, Store, WriteStoreField,
 };
 use crate::{
     arena::Stored,
     prelude::{
-        DefinedAt, SignalIsDisposed, SignalUpdate, SignalUpdateUntracked,
+        DefinedAt, SignalIsDisposed, SignalUpdateUntracked,
         SignalWithUntracked, Trigger,
     },
     signal::trigger::ArcTrigger,
@@ -72,16 +73,41 @@ pub trait StoreField<T>: Sized {
 
     fn path(&self) -> impl Iterator<Item = StorePathSegment>;
 
+    /// Runs `write`, recording the inverse patch created by `undo` as the
+    /// change to `path` if the underlying store keeps a history.
+    ///
+    /// A write through a field is also a write to each of its parents, but
+    /// only the outermost of them calls `undo`.
+    #[doc(hidden)]
+    fn record_write<U>(
+        &self,
+        path: StorePath,
+        undo: impl FnOnce() -> Option<Undo<Self::Orig>>,
+        write: impl FnOnce() -> U,
+    ) -> U {
+        _ = (path, undo);
+        write()
+    }
+
+    /// Returns a function that borrows this field out of the store's data,
+    /// or returns `None` if the field is not currently there (for example,
+    /// an enum variant or map entry that has since been replaced).
     fn reader(
         &self,
-    ) -> impl for<'a> Fn(&'a RwLock<Self::Orig>) -> MappedRwLockReadGuard<'a, T>
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockReadGuard<'a, T>>
            + Send
            + Sync
            + 'static;
 
+    /// Returns a function that mutably borrows this field out of the store's
+    /// data, or returns `None` if the field is not currently there.
     fn writer(
         self,
-    ) -> impl for<'a> Fn(&'a RwLock<Self::Orig>) -> MappedRwLockWriteGuard<'a, T>
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockWriteGuard<'a, T>>
            + Send
            + Sync
            + 'static;
@@ -172,6 +198,23 @@ pub trait StoreField<T>: Sized {
     }
 }
 
+/// Tracks changes to the whole field, and then inspects its current value,
+/// returning `None` if the field itself is not currently in the store.
+///
+/// This is used by [`OptionStoreExt`](super::OptionStoreExt) and by the
+/// variant checks that `#[derive(Store)]` generates for `enum`s.
+#[doc(hidden)]
+pub fn track_field<F, T, U>(field: &F, fun: impl FnOnce(&T) -> U) -> Option<U>
+where
+    F: StoreField<T>,
+{
+    field.get_trigger(field.path().collect()).track();
+    let data = field.data();
+    let reader = field.reader();
+    let value = reader(&data)?;
+    Some(fun(&value))
+}
+
 impl<T> StoreField<T> for ArcStore<T> {
     type Orig = T;
 
@@ -185,31 +228,44 @@ impl<T> StoreField<T> for ArcStore<T> {
         trigger
     }
 
+    fn record_write<U>(
+        &self,
+        path: StorePath,
+        undo: impl FnOnce() -> Option<Undo<Self::Orig>>,
+        write: impl FnOnce() -> U,
+    ) -> U {
+        self.record_in_history(path, undo, write)
+    }
+
     fn path(&self) -> impl Iterator<Item = StorePathSegment> {
         iter::empty()
     }
 
     fn reader(
         &self,
-    ) -> impl for<'a> Fn(&'a RwLock<Self::Orig>) -> MappedRwLockReadGuard<'a, T>
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockReadGuard<'a, T>>
            + Send
            + Sync
            + 'static {
         |lock| {
             let guard = lock.read();
-            RwLockReadGuard::map(guard, |n| n)
+            Some(RwLockReadGuard::map(guard, |n| n))
         }
     }
 
     fn writer(
         self,
-    ) -> impl for<'a> Fn(&'a RwLock<Self::Orig>) -> MappedRwLockWriteGuard<'a, T>
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockWriteGuard<'a, T>>
            + Send
            + Sync
            + 'static {
         |lock| {
             let guard = lock.write();
-            RwLockWriteGuard::map(guard, |n| n)
+            Some(RwLockWriteGuard::map(guard, |n| n))
         }
     }
 }
@@ -244,31 +300,47 @@ impl<T: Send + Sync + 'static> StoreField<T> for Store<T> {
             .unwrap_or_else(unwrap_signal!(self))
     }
 
+    fn record_write<U>(
+        &self,
+        path: StorePath,
+        undo: impl FnOnce() -> Option<Undo<Self::Orig>>,
+        write: impl FnOnce() -> U,
+    ) -> U {
+        match self.inner.get() {
+            Some(inner) => inner.record_write(path, undo, write),
+            None => write(),
+        }
+    }
+
     fn path(&self) -> impl Iterator<Item = StorePathSegment> {
         iter::empty()
     }
 
     fn reader(
         &self,
-    ) -> impl for<'a> Fn(&'a RwLock<Self::Orig>) -> MappedRwLockReadGuard<'a, T>
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockReadGuard<'a, T>>
            + Send
            + Sync
            + 'static {
         |lock| {
             let guard = lock.read();
-            RwLockReadGuard::map(guard, |n| n)
+            Some(RwLockReadGuard::map(guard, |n| n))
         }
     }
 
     fn writer(
         self,
-    ) -> impl for<'a> Fn(&'a RwLock<Self::Orig>) -> MappedRwLockWriteGuard<'a, T>
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockWriteGuard<'a, T>>
            + Send
            + Sync
            + 'static {
         |lock| {
             let guard = lock.write();
-            RwLockWriteGuard::map(guard, |n| n)
+            Some(RwLockWriteGuard::map(guard, |n| n))
         }
     }
 }
@@ -282,8 +354,8 @@ where
     defined_at: &'static Location<'static>,
     path_segment: StorePathSegment,
     inner: Inner,
-    read: fn(&Prev) -> &T,
-    write: fn(&mut Prev) -> &mut T,
+    read: fn(&Prev) -> Option<&T>,
+    write: fn(&mut Prev) -> Option<&mut T>,
     ty: PhantomData<T>,
 }
 
@@ -313,12 +385,15 @@ impl<Inner, Prev, T> Subfield<Inner, Prev, T>
 where
     Inner: StoreField<Prev>,
 {
+    /// Creates a subfield of `inner`, which `read` and `write` borrow out of
+    /// its value. They return `None` when the subfield is not currently
+    /// there, as with the fields of an enum variant.
     #[track_caller]
     pub fn new(
         inner: Inner,
         path_segment: StorePathSegment,
-        read: fn(&Prev) -> &T,
-        write: fn(&mut Prev) -> &mut T,
+        read: fn(&Prev) -> Option<&T>,
+        write: fn(&mut Prev) -> Option<&mut T>,
     ) -> Self {
         Self {
             #[cfg(debug_assertions)]
@@ -352,9 +427,20 @@ where
         self.inner.get_trigger(path)
     }
 
+    fn record_write<U>(
+        &self,
+        path: StorePath,
+        undo: impl FnOnce() -> Option<Undo<Self::Orig>>,
+        write: impl FnOnce() -> U,
+    ) -> U {
+        self.inner.record_write(path, undo, write)
+    }
+
     fn reader(
         &self,
-    ) -> impl for<'a> Fn(&'a RwLock<Self::Orig>) -> MappedRwLockReadGuard<'a, T>
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockReadGuard<'a, T>>
            + Send
            + Sync
            + 'static {
@@ -362,21 +448,23 @@ where
         let read = self.read;
         move |lock| {
             let inner = inner.reader();
-            let lock = inner(lock);
-            MappedRwLockReadGuard::map(lock, |inner| (read)(inner))
+            let lock = inner(lock)?;
+            MappedRwLockReadGuard::try_map(lock, read).ok()
         }
     }
 
     fn writer(
         self,
-    ) -> impl for<'a> Fn(&'a RwLock<Self::Orig>) -> MappedRwLockWriteGuard<'a, T>
+    ) -> impl for<'a> Fn(
+        &'a RwLock<Self::Orig>,
+    ) -> Option<MappedRwLockWriteGuard<'a, T>>
            + Send
            + Sync
            + 'static {
         move |lock| {
             let inner = self.inner.clone().writer();
-            let lock = inner(lock);
-            MappedRwLockWriteGuard::map(lock, |inner| (self.write)(inner))
+            let lock = inner(lock)?;
+            MappedRwLockWriteGuard::try_map(lock, self.write).ok()
         }
     }
 }
@@ -419,16 +507,21 @@ where
         &self,
         fun: impl FnOnce(&Self::Value) -> U,
     ) -> Option<U> {
-        self.inner.try_with_untracked(|prev| fun((self.read)(prev)))
+        self.inner
+            .try_with_untracked(|prev| (self.read)(prev).map(fun))
+            .flatten()
     }
 }
 
 impl<Inner, Prev, T> SignalIsDisposed for Subfield<Inner, Prev, T>
 where
-    Inner: StoreField<Prev>,
+    Inner: StoreField<Prev> + SignalWithUntracked<Value = Prev>,
 {
+    // a subfield whose enum variant has been replaced can no longer be read
     fn is_disposed(&self) -> bool {
-        false
+        self.inner
+            .try_with_untracked(|prev| (self.read)(prev).is_none())
+            .unwrap_or(true)
     }
 }
 
@@ -445,7 +538,14 @@ where
 
 impl<Inner, Prev, T> SignalUpdateUntracked for Subfield<Inner, Prev, T>
 where
-    Inner: StoreField<Prev> + SignalUpdateUntracked<Value = Prev>,
+    Inner: StoreField<Prev>
+        + SignalUpdateUntracked<Value = Prev>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+    Prev: 'static,
+    T: 'static,
 {
     type Value = T;
 
@@ -453,9 +553,16 @@ where
         &self,
         fun: impl FnOnce(&mut Self::Value) -> U,
     ) -> Option<U> {
-        self.inner.try_update_untracked(|prev| {
-            let this = (self.write)(prev);
-            fun(this)
-        })
+        // writing to a variant the enum doesn't hold changes nothing
+        let is_present = || self.reader()(&self.data()).is_some();
+        self.record_write(
+            self.path().collect(),
+            || is_present().then(|| undo_write(self)),
+            || {
+                self.inner
+                    .try_update_untracked(|prev| (self.write)(prev).map(fun))
+                    .flatten()
+            },
+        )
     }
 }
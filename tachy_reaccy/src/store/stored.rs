@@ -5,7 +5,7 @@ use crate::{
     source::Track,
     unwrap_signal,
 };
-use std::fmt::Debug;
+use std::{fmt::Debug, panic::Location};
 
 pub struct RwStoreField<Orig: Send + Sync + 'static, T: Send + Sync + 'static> {
     pub(crate) inner: Stored<ArcRwStoreField<Orig, T>>,
@@ -82,7 +82,7 @@ impl<Orig: Send + Sync + 'static, T: Send + Sync + 'static> SignalIsDisposed
     for RwStoreField<Orig, T>
 {
     fn is_disposed(&self) -> bool {
-        self.inner.exists()
+        !self.inner.exists()
     }
 }
 
@@ -105,6 +105,10 @@ impl<Orig: Send + Sync + 'static, T: Send + Sync + 'static> StoredData
         self.inner.get()
     }
 
+    fn stored_defined_at(&self) -> Option<&'static Location<'static>> {
+        self.inner.defined_at()
+    }
+
     fn dispose(&self) {
         self.inner.dispose();
     }
@@ -124,6 +128,10 @@ impl<Orig: Send + Sync + 'static, T: Send + Sync + 'static> StoredData
         self.inner.get()
     }
 
+    fn stored_defined_at(&self) -> Option<&'static Location<'static>> {
+        self.inner.defined_at()
+    }
+
     fn dispose(&self) {
         self.inner.dispose();
     }
@@ -168,6 +176,10 @@ impl<Orig: Send + Sync + 'static, T: Send + Sync + 'static> StoredData
         self.inner.get()
     }
 
+    fn stored_defined_at(&self) -> Option<&'static Location<'static>> {
+        self.inner.defined_at()
+    }
+
     fn dispose(&self) {
         self.inner.dispose();
     }
@@ -0,0 +1,71 @@
+use tachy_reaccy::{
+    context::{
+        expect_context, provide_context, try_provide_context, use_context,
+        ContextError,
+    },
+    spawn::spawn_local,
+    Owner, Root,
+};
+
+pub async fn tick() {
+    tokio::time::sleep(std::time::Duration::from_micros(1)).await;
+}
+
+#[test]
+fn child_context_shadows_parent() {
+    Root::new(|| {
+        provide_context(1_i32);
+        let child = Owner::new();
+        child.with(|| {
+            assert_eq!(use_context::<i32>(), Some(1));
+            provide_context(2_i32);
+            assert_eq!(use_context::<i32>(), Some(2));
+        });
+        assert_eq!(use_context::<i32>(), Some(1));
+    });
+}
+
+#[test]
+fn try_provide_context_errors_without_owner() {
+    assert!(matches!(
+        try_provide_context(1_i32),
+        Err(ContextError::NoOwner { .. })
+    ));
+    assert_eq!(use_context::<i32>(), None);
+}
+
+#[test]
+fn provide_context_does_nothing_without_owner() {
+    provide_context(1_i32);
+    assert_eq!(use_context::<i32>(), None);
+}
+
+#[test]
+#[should_panic(expected = "i32")]
+fn expect_context_panics_with_type_name() {
+    Root::new(|| expect_context::<i32>());
+}
+
+#[test]
+#[should_panic(expected = "not provided by the Owner defined at")]
+fn expect_context_panics_with_where_the_owner_was_defined() {
+    Owner::new().with(|| expect_context::<i32>());
+}
+
+#[tokio::test]
+async fn context_survives_await_in_spawned_task() {
+    let local = tokio::task::LocalSet::new();
+    local
+        .run_until(async {
+            let (tx, rx) = futures::channel::oneshot::channel();
+            Root::new(|| {
+                provide_context(String::from("hello"));
+                spawn_local(async move {
+                    tick().await;
+                    _ = tx.send(use_context::<String>());
+                });
+            });
+            assert_eq!(rx.await.unwrap().as_deref(), Some("hello"));
+        })
+        .await;
+}
@@ -0,0 +1,142 @@
+use parking_lot::RwLock;
+use std::{
+    mem,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use tachy_reaccy::{
+    prelude::*,
+    store::{ArcDerivedStore, ArcStore, DerivedStore, StoreField, Subfield},
+    Owner,
+};
+
+pub async fn tick() {
+    tokio::time::sleep(std::time::Duration::from_micros(1)).await;
+}
+
+struct Todos {
+    items: Vec<i32>,
+    show_all: bool,
+}
+
+#[test]
+fn derived_store_only_reruns_for_paths_it_reads() {
+    let store = ArcStore::new(Todos {
+        items: vec![1, 2, 3, 4],
+        show_all: false,
+    });
+    let items = Subfield::new(
+        store.clone(),
+        0.into(),
+        |todos: &Todos| Some(&todos.items),
+        |todos: &mut Todos| Some(&mut todos.items),
+    );
+    let show_all = Subfield::new(
+        store.clone(),
+        1.into(),
+        |todos: &Todos| Some(&todos.show_all),
+        |todos: &mut Todos| Some(&mut todos.show_all),
+    );
+
+    let runs = Arc::new(AtomicUsize::new(0));
+    let evens = ArcDerivedStore::new({
+        let runs = Arc::clone(&runs);
+        let items = items.clone();
+        move || {
+            runs.fetch_add(1, Ordering::Relaxed);
+            items.with(|items| {
+                items
+                    .iter()
+                    .copied()
+                    .filter(|n| n % 2 == 0)
+                    .collect::<Vec<_>>()
+            })
+        }
+    });
+
+    assert_eq!(evens.get_untracked(), vec![2, 4]);
+    let after_first_read = runs.load(Ordering::Relaxed);
+
+    // a path the projection never read doesn't cause it to rerun
+    show_all.set(true);
+    assert_eq!(evens.get_untracked(), vec![2, 4]);
+    assert_eq!(runs.load(Ordering::Relaxed), after_first_read);
+
+    items.update(|items| items.push(6));
+    assert_eq!(evens.clone().index(2_usize).get_untracked(), 6);
+    assert_eq!(runs.load(Ordering::Relaxed), after_first_read + 1);
+    assert_eq!(evens.iter().count(), 3);
+}
+
+#[tokio::test]
+async fn effect_reading_derived_store_reruns_when_projection_changes() {
+    let store = ArcStore::new(Todos {
+        items: vec![1, 2, 3],
+        show_all: false,
+    });
+    let items = Subfield::new(
+        store.clone(),
+        0.into(),
+        |todos: &Todos| Some(&todos.items),
+        |todos: &mut Todos| Some(&mut todos.items),
+    );
+    let show_all = Subfield::new(
+        store.clone(),
+        1.into(),
+        |todos: &Todos| Some(&todos.show_all),
+        |todos: &mut Todos| Some(&mut todos.show_all),
+    );
+
+    let evens = ArcDerivedStore::new({
+        let items = items.clone();
+        move || {
+            items.with(|items| {
+                items
+                    .iter()
+                    .copied()
+                    .filter(|n| n % 2 == 0)
+                    .collect::<Vec<_>>()
+            })
+        }
+    });
+
+    let seen = Arc::new(RwLock::new(Vec::new()));
+    mem::forget(Effect::new_sync({
+        let evens = evens.clone();
+        let seen = Arc::clone(&seen);
+        move |_| {
+            seen.write().push(evens.get());
+        }
+    }));
+
+    tick().await;
+    assert_eq!(*seen.read(), vec![vec![2]]);
+
+    // a path the projection never read doesn't reach the effect
+    show_all.set(true);
+    tick().await;
+    assert_eq!(*seen.read(), vec![vec![2]]);
+
+    items.update(|items| items.push(4));
+    tick().await;
+    assert_eq!(seen.read().last(), Some(&vec![2, 4]));
+
+    // the reader of a derived store can outlive the borrow it came from
+    let reader = evens.reader();
+    assert_eq!(*reader(&evens.data()).unwrap(), vec![2, 4]);
+}
+
+#[test]
+fn derived_store_is_disposed_with_its_owner() {
+    let owner = Owner::new();
+    let (derived, arc_derived) =
+        owner.with(|| (DerivedStore::new(|| 1), ArcDerivedStore::new(|| 2)));
+    assert!(!derived.is_disposed());
+    assert!(!arc_derived.is_disposed());
+
+    drop(owner);
+    assert!(derived.is_disposed());
+    assert!(arc_derived.is_disposed());
+}
@@ -0,0 +1,123 @@
+use std::panic::AssertUnwindSafe;
+use tachy_reaccy::{prelude::*, Owner};
+
+#[test]
+fn owner_releases_children_on_drop() {
+    let owner = Owner::new();
+    let (a, b) = owner.with(|| {
+        let a = RwSignal::new(0);
+        let child = Owner::new();
+        let b = child.with(|| RwSignal::new(1));
+        // the child stays alive until its parent is cleaned up
+        Owner::on_cleanup(move || drop(child));
+        (a, b)
+    });
+    assert!(!a.is_disposed());
+    assert!(!b.is_disposed());
+
+    let check = owner.leak_check();
+    drop(owner);
+    check.assert_released();
+
+    assert!(a.is_disposed());
+    assert!(b.is_disposed());
+    assert_eq!(a.try_get(), None);
+}
+
+#[test]
+#[should_panic(expected = "still alive")]
+fn leak_check_catches_live_owner() {
+    let owner = Owner::new();
+    let check = owner.leak_check();
+    check.assert_released();
+}
+
+#[test]
+#[should_panic(expected = "already been disposed")]
+fn reading_disposed_signal_reports_location() {
+    let owner = Owner::new();
+    let a = owner.with(|| RwSignal::new(0));
+    drop(owner);
+    a.get();
+}
+
+#[test]
+fn disposed_signal_reports_where_it_was_defined() {
+    let owner = Owner::new();
+    let a = owner.with(|| RwSignal::new(0));
+    drop(owner);
+
+    let panic =
+        std::panic::catch_unwind(AssertUnwindSafe(|| a.get())).unwrap_err();
+    let msg = panic.downcast_ref::<String>().unwrap();
+    assert!(msg.starts_with(&format!("At {}:", file!())), "{msg}");
+    assert!(msg.contains(&format!("defined at {}:", file!())), "{msg}");
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn disposed_signal_warns_where_it_was_accessed_and_defined() {
+    let owner = Owner::new();
+    let a = owner.with(|| RwSignal::new(0));
+    drop(owner);
+
+    let warnings = capture_warnings(|| {
+        assert_eq!(a.try_get(), None);
+        a.update(|n| *n += 1);
+    });
+    assert_eq!(warnings.len(), 2);
+    for (warning, action) in warnings.iter().zip(["access", "update"]) {
+        let accessed_at = format!("At {}:", file!());
+        let defined_at = format!("defined at {}:", file!());
+        assert!(warning.starts_with(&accessed_at), "{warning}");
+        assert!(warning.contains(&format!("tried to {action}")), "{warning}");
+        assert!(warning.contains(&defined_at), "{warning}");
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn capture_warnings(fun: impl FnOnce()) -> Vec<String> {
+    use std::sync::{Arc, Mutex};
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Level, Metadata, Subscriber,
+    };
+
+    #[derive(Clone, Default)]
+    struct Warnings(Arc<Mutex<Vec<String>>>);
+
+    impl Visit for Warnings {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "message" {
+                self.0.lock().unwrap().push(format!("{value:?}"));
+            }
+        }
+    }
+
+    impl Subscriber for Warnings {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            *metadata.level() == Level::WARN
+        }
+
+        fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut self.clone());
+        }
+
+        fn enter(&self, _span: &span::Id) {}
+
+        fn exit(&self, _span: &span::Id) {}
+    }
+
+    let warnings = Warnings::default();
+    tracing::subscriber::with_default(warnings.clone(), fun);
+    let warnings = warnings.0.lock().unwrap();
+    warnings.clone()
+}
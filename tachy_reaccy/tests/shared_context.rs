@@ -0,0 +1,89 @@
+use futures::StreamExt;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tachy_reaccy::shared_context::{
+    SharedContext, SharedState, SsrSharedContext,
+};
+
+#[tokio::test]
+async fn streamed_resources_resolve_on_the_client() {
+    let sc = SsrSharedContext::new();
+    let a = sc.next_id();
+    let b = sc.next_id();
+    sc.write_async(a, Box::pin(async { "\"a\"".to_string() }));
+    sc.write_async(b, Box::pin(async { "</script>".to_string() }));
+
+    let chunks = sc.pending_data().unwrap().collect::<Vec<_>>().await;
+    assert!(chunks[0].ends_with("__PENDING_RESOURCES.push(0,1,);"));
+    let mut resolved = chunks[1..].to_vec();
+    resolved.sort();
+    assert_eq!(
+        resolved,
+        [
+            r#"__resolveResource(0, "\"a\"");"#,
+            r#"__resolveResource(1, "\u003c/script>");"#
+        ]
+    );
+}
+
+#[tokio::test]
+async fn first_chunk_defines_the_globals_it_uses() {
+    let sc = SsrSharedContext::new();
+    let id = sc.next_id();
+    sc.write_async(id, Box::pin(async { "1".to_string() }));
+
+    // the resource script may not have run yet, so each global the chunks
+    // use is defined if it is missing, without replacing an existing one
+    let chunks = sc.pending_data().unwrap().collect::<Vec<_>>().await;
+    for global in [
+        "__RESOLVED_RESOURCES",
+        "__PENDING_RESOURCES",
+        "__SHARED_STATE",
+        "__resolveResource",
+    ] {
+        let guard = format!("window.{global} = window.{global} || ");
+        assert!(chunks[0].contains(&guard), "{global} is not defined");
+    }
+    let defined_at = chunks[0].find("window.__PENDING_RESOURCES =").unwrap();
+    let used_at = chunks[0].find("__PENDING_RESOURCES.push").unwrap();
+    assert!(defined_at < used_at);
+    assert_eq!(chunks[1], "__resolveResource(0, \"1\");");
+}
+
+#[tokio::test]
+async fn server_does_not_await_data() {
+    let sc = SsrSharedContext::new();
+    let id = sc.next_id();
+    assert_eq!(sc.await_data(&id).await, None);
+}
+
+#[tokio::test]
+async fn shared_state_is_registered_once_and_serialized() {
+    let sc = SsrSharedContext::new();
+    let count = Arc::new(AtomicUsize::new(1));
+    let mut inits = 0;
+    let mut init = |data: Option<String>| {
+        assert_eq!(data, None);
+        inits += 1;
+        let count = Arc::clone(&count);
+        SharedState {
+            value: Arc::new(Arc::clone(&count)),
+            serialize: Box::new(move || {
+                Some(format!("<{}>", count.load(Ordering::Relaxed)))
+            }),
+        }
+    };
+    let first = sc.shared_state("cart", &mut init);
+    let second = sc.shared_state("cart", &mut init);
+    assert_eq!(inits, 1);
+    assert!(Arc::ptr_eq(&first, &second));
+
+    // the value is serialized as it is when the data is sent
+    count.store(3, Ordering::Relaxed);
+    let chunks = sc.pending_data().unwrap().collect::<Vec<_>>().await;
+    assert!(chunks[0].ends_with(
+        r#"__PENDING_RESOURCES.push();__SHARED_STATE["cart"] = "\u003c3>";"#
+    ));
+}
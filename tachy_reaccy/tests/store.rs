@@ -0,0 +1,320 @@
+use std::{
+    collections::HashMap,
+    mem,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use tachy_reaccy::{
+    prelude::*,
+    store::{ArcStore, StoreField, Subfield},
+};
+
+pub async fn tick() {
+    tokio::time::sleep(std::time::Duration::from_micros(1)).await;
+}
+
+// counts the runs of an effect that reads `fun`
+fn count_runs(fun: impl Fn() + Send + Sync + 'static) -> Arc<AtomicUsize> {
+    let runs = Arc::new(AtomicUsize::new(0));
+    mem::forget(Effect::new_sync({
+        let runs = Arc::clone(&runs);
+        move |_| {
+            fun();
+            runs.fetch_add(1, Ordering::Relaxed);
+        }
+    }));
+    runs
+}
+
+#[test]
+fn option_field_yields_subfield_when_some() {
+    let store = ArcStore::new(None::<String>);
+    assert!(!store.is_some());
+    assert!(store.clone().some().is_none());
+
+    store.try_update_untracked(|value| *value = Some("Alice".to_string()));
+    let name = store.clone().some().expect("field should be Some");
+    name.update(|name| name.push_str(" Smith"));
+    assert_eq!(name.get_untracked(), "Alice Smith");
+}
+
+#[test]
+fn map_field_is_addressed_by_key() {
+    let store = ArcStore::new(HashMap::from([(1, "one"), (2, "two")]));
+    let one = store.clone().at_key(1);
+    let two = store.clone().at_key(2);
+    assert_eq!(one.get_untracked(), "one");
+
+    two.set("zwei");
+    assert_eq!(two.get_untracked(), "zwei");
+    assert_eq!(one.get_untracked(), "one");
+    assert_eq!(store.clone().at_key(3).try_get_untracked(), None);
+}
+
+#[test]
+fn removed_map_key_is_treated_as_disposed() {
+    let store = ArcStore::new(HashMap::from([(1, "one")]));
+    let one = store.clone().at_key(1);
+    assert!(!one.is_disposed());
+
+    store.update(|map| {
+        map.remove(&1);
+    });
+    assert!(one.is_disposed());
+    assert_eq!(one.try_get_untracked(), None);
+    assert_eq!(one.try_update(|value| *value = "uno"), None);
+    assert_eq!(one.try_set("uno"), Some("uno"));
+    assert!(store.get_untracked().is_empty());
+}
+
+#[test]
+fn subfield_of_a_cleared_option_is_treated_as_disposed() {
+    let store = ArcStore::new(Some("Alice".to_string()));
+    let name = store.clone().some().expect("field should be Some");
+
+    store.set(None);
+    assert!(name.is_disposed());
+    assert_eq!(name.try_get_untracked(), None);
+    assert_eq!(name.try_update(|name| name.push_str(" Smith")), None);
+    assert_eq!(store.get_untracked(), None);
+}
+
+#[tokio::test]
+async fn writing_a_map_key_leaves_other_keys_untouched() {
+    let store = ArcStore::new(HashMap::from([(1, "one"), (2, "two")]));
+    let one = store.clone().at_key(1);
+    let two = store.clone().at_key(2);
+    let one_runs = count_runs({
+        let one = one.clone();
+        move || _ = one.get()
+    });
+    let two_runs = count_runs({
+        let two = two.clone();
+        move || _ = two.get()
+    });
+
+    tick().await;
+    assert_eq!(one_runs.load(Ordering::Relaxed), 1);
+    assert_eq!(two_runs.load(Ordering::Relaxed), 1);
+
+    two.set("zwei");
+    tick().await;
+    assert_eq!(one_runs.load(Ordering::Relaxed), 1);
+    assert_eq!(two_runs.load(Ordering::Relaxed), 2);
+}
+
+// fields without history don't need to be `Clone` to be written
+#[derive(Debug, PartialEq)]
+struct NotClone(usize);
+
+#[test]
+fn fields_that_are_not_clone_can_be_updated() {
+    let store = ArcStore::new(Some(NotClone(1)));
+    let field = store.clone().some().expect("field should be Some");
+    field.update(|value| value.0 += 1);
+    field.with_untracked(|value| assert_eq!(value, &NotClone(2)));
+
+    let store = ArcStore::new(vec![NotClone(1)]);
+    let row = store.clone().index(0);
+    row.update(|value| value.0 += 1);
+    row.with_untracked(|value| assert_eq!(value, &NotClone(2)));
+
+    let store = ArcStore::new(HashMap::from([(1, NotClone(1))]));
+    let one = store.clone().at_key(1);
+    one.update(|value| value.0 += 1);
+    one.with_untracked(|value| assert_eq!(value, &NotClone(2)));
+}
+
+#[derive(Clone)]
+struct Document {
+    title: String,
+    body: String,
+}
+
+#[test]
+fn store_history_undoes_and_redoes_changes() {
+    let store = ArcStore::with_history(
+        Document {
+            title: "Untitled".to_string(),
+            body: String::new(),
+        },
+        10,
+    );
+    let title = Subfield::new(
+        store.clone(),
+        0.into(),
+        |doc: &Document| Some(&doc.title),
+        |doc: &mut Document| Some(&mut doc.title),
+    );
+    let body = Subfield::new(
+        store.clone(),
+        1.into(),
+        |doc: &Document| Some(&doc.body),
+        |doc: &mut Document| Some(&mut doc.body),
+    );
+    assert!(!store.can_undo());
+
+    title.set("Draft".to_string());
+    store.transaction(|| {
+        body.set("Hello".to_string());
+        body.update(|body| body.push_str(", world!"));
+    });
+    assert_eq!(body.get_untracked(), "Hello, world!");
+
+    // the transaction is undone as a single change
+    assert!(store.undo());
+    assert_eq!(body.get_untracked(), "");
+    assert_eq!(title.get_untracked(), "Draft");
+
+    assert!(store.undo());
+    assert_eq!(title.get_untracked(), "Untitled");
+    assert!(!store.undo());
+
+    assert!(store.redo());
+    assert_eq!(title.get_untracked(), "Draft");
+
+    // a new change clears the redo stack
+    title.set("Final".to_string());
+    assert!(!store.can_redo());
+}
+
+#[test]
+fn store_history_respects_depth() {
+    let store = ArcStore::with_history(0, 2);
+    for n in 1..=5 {
+        store.set(n);
+    }
+    assert!(store.undo());
+    assert!(store.undo());
+    assert!(!store.undo());
+    assert_eq!(store.get_untracked(), 3);
+}
+
+#[tokio::test]
+async fn writing_a_field_leaves_sibling_fields_untouched() {
+    let store = ArcStore::new(Document {
+        title: "Untitled".to_string(),
+        body: String::new(),
+    });
+    let title = Subfield::new(
+        store.clone(),
+        0.into(),
+        |doc: &Document| Some(&doc.title),
+        |doc: &mut Document| Some(&mut doc.title),
+    );
+    let body = Subfield::new(
+        store.clone(),
+        1.into(),
+        |doc: &Document| Some(&doc.body),
+        |doc: &mut Document| Some(&mut doc.body),
+    );
+    let title_runs = count_runs({
+        let title = title.clone();
+        move || _ = title.get()
+    });
+    let body_runs = count_runs({
+        let body = body.clone();
+        move || _ = body.get()
+    });
+
+    tick().await;
+    assert_eq!(title_runs.load(Ordering::Relaxed), 1);
+    assert_eq!(body_runs.load(Ordering::Relaxed), 1);
+
+    body.set("Hello".to_string());
+    tick().await;
+    assert_eq!(title_runs.load(Ordering::Relaxed), 1);
+    assert_eq!(body_runs.load(Ordering::Relaxed), 2);
+
+    title.set("Draft".to_string());
+    tick().await;
+    assert_eq!(title_runs.load(Ordering::Relaxed), 2);
+    assert_eq!(body_runs.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn store_history_undo_leaves_other_paths_alone() {
+    let store = ArcStore::with_history(
+        Document {
+            title: "Untitled".to_string(),
+            body: String::new(),
+        },
+        10,
+    );
+    let title = Subfield::new(
+        store.clone(),
+        0.into(),
+        |doc: &Document| Some(&doc.title),
+        |doc: &mut Document| Some(&mut doc.title),
+    );
+    let body = Subfield::new(
+        store.clone(),
+        1.into(),
+        |doc: &Document| Some(&doc.body),
+        |doc: &mut Document| Some(&mut doc.body),
+    );
+
+    title.set("Draft".to_string());
+    // a write that isn't part of the history
+    body.clone().arc_rw().set("Hello".to_string());
+
+    // undoing the write to the title doesn't revert the body
+    assert!(store.undo());
+    assert_eq!(title.get_untracked(), "Untitled");
+    assert_eq!(body.get_untracked(), "Hello");
+
+    assert!(store.redo());
+    assert_eq!(title.get_untracked(), "Draft");
+    assert_eq!(body.get_untracked(), "Hello");
+}
+
+#[test]
+fn store_history_records_untracked_writes() {
+    let store = ArcStore::with_history(
+        Document {
+            title: "Untitled".to_string(),
+            body: String::new(),
+        },
+        10,
+    );
+    let title = Subfield::new(
+        store.clone(),
+        0.into(),
+        |doc: &Document| Some(&doc.title),
+        |doc: &mut Document| Some(&mut doc.title),
+    );
+    let body = Subfield::new(
+        store.clone(),
+        1.into(),
+        |doc: &Document| Some(&doc.body),
+        |doc: &mut Document| Some(&mut doc.body),
+    );
+
+    title.try_update_untracked(|title| *title = "Draft".to_string());
+    body.set("Hello".to_string());
+
+    // each write is its own change, even without a trigger in between
+    assert!(store.undo());
+    assert_eq!(title.get_untracked(), "Draft");
+    assert_eq!(body.get_untracked(), "");
+    assert!(store.undo());
+    assert_eq!(title.get_untracked(), "Untitled");
+    assert!(!store.undo());
+}
+
+#[test]
+fn store_history_undoes_map_keys() {
+    let store = ArcStore::with_history(HashMap::from([(1, "one")]), 10);
+    let one = store.clone().at_key(1);
+    let two = store.clone().at_key(2);
+
+    // writing to a missing key isn't a change
+    two.set("two");
+    assert!(!store.can_undo());
+
+    one.set("eins");
+    assert!(store.undo());
+    assert_eq!(one.get_untracked(), "one");
+}
@@ -1,3 +1,4 @@
+use convert_case::{Case, Casing};
 use proc_macro2::Span;
 use proc_macro_error::{abort, abort_call_site, proc_macro_error};
 use quote::{quote, ToTokens};
@@ -5,8 +6,8 @@ use syn::{
     parse::{Parse, ParseStream, Parser},
     punctuated::Punctuated,
     token::Comma,
-    Data, Field, Fields, Generics, Ident, Meta, MetaList, Result, Visibility,
-    WhereClause, Type, Token, Index,
+    Data, Field, Fields, Generics, Ident, Meta, MetaList, Result, Variant,
+    Visibility, WhereClause, Type, Token, Index,
 };
 
 #[proc_macro_error]
@@ -21,35 +22,47 @@ struct Model {
     pub vis: Visibility,
     pub struct_name: Ident,
     pub generics: Generics,
-    pub fields: Vec<Field>,
+    pub ty: ModelTy,
+}
+
+enum ModelTy {
+    Struct { fields: Vec<Field> },
+    Enum { variants: Vec<Variant> },
 }
 
 impl Parse for Model {
     fn parse(input: ParseStream) -> Result<Self> {
         let input = syn::DeriveInput::parse(input)?;
 
-        let syn::Data::Struct(s) = input.data else {
-            abort_call_site!("only structs can be used with `Store`");
-        };
-
-        let (fields) = match s.fields {
-            syn::Fields::Unit => {
-                abort!(s.semi_token, "unit structs are not supported");
+        let ty = match input.data {
+            syn::Data::Struct(s) => {
+                let fields = match s.fields {
+                    syn::Fields::Unit => {
+                        abort!(s.semi_token, "unit structs are not supported");
+                    }
+                    syn::Fields::Named(fields) => {
+                        fields.named.into_iter().collect::<Vec<_>>()
+                    }
+                    syn::Fields::Unnamed(fields) => fields
+                            .unnamed
+                            .into_iter()
+                            .collect::<Vec<_>>(),
+                };
+                ModelTy::Struct { fields }
             }
-            syn::Fields::Named(fields) => {
-                fields.named.into_iter().collect::<Vec<_>>()
+            syn::Data::Enum(e) => ModelTy::Enum {
+                variants: e.variants.into_iter().collect(),
+            },
+            syn::Data::Union(_) => {
+                abort_call_site!("unions cannot be used with `Store`");
             }
-            syn::Fields::Unnamed(fields) => fields
-                    .unnamed
-                    .into_iter()
-                    .collect::<Vec<_>>(),
         };
 
         Ok(Self {
             vis: input.vis,
             struct_name: input.ident,
             generics: input.generics,
-            fields,
+            ty,
         })
     }
 }
@@ -118,8 +131,8 @@ fn field_to_tokens(idx: usize, include_body: bool, modes: Option<&[SubfieldMode]
                 #library_path::Subfield::new(
                     self,
                     #idx.into(),
-                    |prev| &prev.#locator,
-                    |prev| &mut prev.#locator,
+                    |prev| Some(&prev.#locator),
+                    |prev| Some(&mut prev.#locator),
                 )
             }
         }
@@ -130,6 +143,84 @@ fn field_to_tokens(idx: usize, include_body: bool, modes: Option<&[SubfieldMode]
     }
 }
 
+// For each variant, generates an `is_<variant>` check that tracks the whole
+// field, and an accessor for each of its fields that returns a subfield if
+// the enum currently holds that variant.
+fn variant_to_tokens(
+    variant: &Variant,
+    next_segment: &mut usize,
+    library_path: &proc_macro2::TokenStream,
+    generics: &Generics,
+    any_store_field: &Ident,
+    enum_name: &Ident,
+) -> Vec<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let variant_ident = &variant.ident;
+    let snake = variant_ident.to_string().to_case(Case::Snake);
+    let is_variant = Ident::new(&format!("is_{snake}"), variant_ident.span());
+    let matches_variant = quote! {
+        |prev: &#enum_name #generics| matches!(prev, #enum_name::#variant_ident { .. })
+    };
+
+    let mut tokens = vec![(
+        quote! { fn #is_variant(&self) -> bool; },
+        quote! {
+            fn #is_variant(&self) -> bool {
+                #library_path::track_field(self, #matches_variant).unwrap_or(false)
+            }
+        },
+    )];
+
+    let single_field = variant.fields.len() == 1;
+    for (idx, field) in variant.fields.iter().enumerate() {
+        let ty = &field.ty;
+        let (ident, locator) = match &field.ident {
+            Some(field_ident) => (
+                Ident::new(&format!("{snake}_{field_ident}"), field_ident.span()),
+                quote! { #field_ident },
+            ),
+            None => {
+                let ident = if single_field {
+                    Ident::new(&snake, variant_ident.span())
+                } else {
+                    Ident::new(&format!("{snake}_{idx}"), variant_ident.span())
+                };
+                let idx = Index::from(idx);
+                (ident, quote! { #idx })
+            }
+        };
+        let segment = *next_segment;
+        *next_segment += 1;
+
+        let signature = quote! {
+            fn #ident(self) -> Option<#library_path::Subfield<#any_store_field, #enum_name #generics, #ty>>
+        };
+        tokens.push((
+            quote! { #signature; },
+            quote! {
+                #signature {
+                    #library_path::track_field(&self, #matches_variant).unwrap_or(false).then(|| {
+                        #library_path::Subfield::new(
+                            self,
+                            #segment.into(),
+                            |prev| match prev {
+                                #enum_name::#variant_ident { #locator: this, .. } => Some(this),
+                                #[allow(unreachable_patterns)]
+                                _ => None,
+                            },
+                            |prev| match prev {
+                                #enum_name::#variant_ident { #locator: this, .. } => Some(this),
+                                #[allow(unreachable_patterns)]
+                                _ => None,
+                            },
+                        )
+                    })
+                }
+            },
+        ));
+    }
+    tokens
+}
+
 impl ToTokens for Model {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let library_path = quote! { ::tachys::tachy_reaccy::store };
@@ -137,7 +228,7 @@ impl ToTokens for Model {
             vis,
             struct_name,
             generics,
-            fields,
+            ty,
         } = &self;
         let any_store_field = Ident::new("AnyStoreField", Span::call_site());
         let trait_name = Ident::new(
@@ -167,31 +258,41 @@ impl ToTokens for Model {
         };
 
         // define an extension trait that matches this struct
-        let all_field_data = fields.iter().enumerate().map(|(idx, field)| {
-            let Field { ident, ty, attrs, .. } = &field;
-            let modes = attrs.iter().find_map(|attr| {
-                attr.meta.path().is_ident("store").then(|| {
-                    match &attr.meta {
-                        Meta::List(list) => {
-                            match Punctuated::<SubfieldMode, Comma>::parse_terminated.parse2(list.tokens.clone()) {
-                                Ok(modes) => Some(modes.iter().cloned().collect::<Vec<_>>()),
-                                Err(e) => abort!(list, e)
-                            }
-                        },
-                        _ => None
-                    }
-                })
-            }).flatten();
+        let all_field_data: Vec<_> = match ty {
+            ModelTy::Struct { fields } => fields.iter().enumerate().map(|(idx, field)| {
+                let Field { ident, ty, attrs, .. } = &field;
+                let modes = attrs.iter().find_map(|attr| {
+                    attr.meta.path().is_ident("store").then(|| {
+                        match &attr.meta {
+                            Meta::List(list) => {
+                                match Punctuated::<SubfieldMode, Comma>::parse_terminated.parse2(list.tokens.clone()) {
+                                    Ok(modes) => Some(modes.iter().cloned().collect::<Vec<_>>()),
+                                    Err(e) => abort!(list, e)
+                                }
+                            },
+                            _ => None
+                        }
+                    })
+                }).flatten();
 
-            (
-                field_to_tokens(idx, false, modes.as_deref(), &library_path, ident.as_ref(), generics, &any_store_field, struct_name, ty),
-                field_to_tokens(idx, true, modes.as_deref(), &library_path, ident.as_ref(), generics, &any_store_field, struct_name, ty),
-            )
-                       
-        });
+                (
+                    field_to_tokens(idx, false, modes.as_deref(), &library_path, ident.as_ref(), generics, &any_store_field, struct_name, ty),
+                    field_to_tokens(idx, true, modes.as_deref(), &library_path, ident.as_ref(), generics, &any_store_field, struct_name, ty),
+                )
+            }).collect(),
+            ModelTy::Enum { variants } => {
+                let mut next_segment = 0;
+                variants
+                    .iter()
+                    .flat_map(|variant| {
+                        variant_to_tokens(variant, &mut next_segment, &library_path, generics, &any_store_field, struct_name)
+                    })
+                    .collect()
+            }
+        };
 
         // implement that trait for all StoreFields
-        let (trait_fields, read_fields) = all_field_data.unzip();
+        let (trait_fields, read_fields): (Vec<_>, Vec<_>) = all_field_data.into_iter().unzip();
 
         // read access
         tokens.extend(quote! {
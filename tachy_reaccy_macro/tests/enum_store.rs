@@ -0,0 +1,60 @@
+use tachy_reaccy_macro::Store;
+use tachys::tachy_reaccy::{prelude::*, store::ArcStore};
+
+#[derive(Store, Debug, Clone, PartialEq)]
+enum Shape {
+    Circle(f64),
+    Rect { width: f64, height: f64 },
+    Empty,
+}
+
+#[test]
+fn enum_store_checks_current_variant() {
+    let store = ArcStore::new(Shape::Circle(1.0));
+    assert!(store.is_circle());
+    assert!(!store.is_rect());
+    assert!(!store.is_empty());
+
+    store.set(Shape::Empty);
+    assert!(!store.is_circle());
+    assert!(store.is_empty());
+}
+
+#[test]
+fn enum_store_yields_variant_fields_only_for_current_variant() {
+    let store = ArcStore::new(Shape::Rect {
+        width: 2.0,
+        height: 3.0,
+    });
+    assert!(store.clone().circle().is_none());
+
+    let width = store.clone().rect_width().expect("should be a Rect");
+    let height = store.clone().rect_height().expect("should be a Rect");
+    width.set(4.0);
+    assert_eq!(width.get_untracked(), 4.0);
+    assert_eq!(height.get_untracked(), 3.0);
+    assert_eq!(
+        store.get_untracked(),
+        Shape::Rect {
+            width: 4.0,
+            height: 3.0
+        }
+    );
+
+    store.set(Shape::Circle(5.0));
+    assert!(store.clone().rect_width().is_none());
+    let radius = store.clone().circle().expect("should be a Circle");
+    assert_eq!(radius.get_untracked(), 5.0);
+}
+
+#[test]
+fn enum_store_variant_field_is_disposed_when_variant_changes() {
+    let store = ArcStore::new(Shape::Circle(1.0));
+    let radius = store.clone().circle().expect("should be a Circle");
+
+    store.set(Shape::Empty);
+    assert!(radius.is_disposed());
+    assert_eq!(radius.try_get_untracked(), None);
+    assert_eq!(radius.try_update(|radius| *radius = 2.0), None);
+    assert_eq!(store.get_untracked(), Shape::Empty);
+}
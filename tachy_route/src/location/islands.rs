@@ -0,0 +1,292 @@
+//! Client-side navigation for apps that use islands.
+//!
+//! In islands mode, each page is rendered on the server. Rather than doing a
+//! full page load, the HTML for the next page is fetched and swapped into the
+//! current `<body>`. Any island that is rendered with the same component and
+//! props on both pages is kept, along with its state; all the other islands
+//! are hydrated by the island script once they have been swapped in.
+
+use super::{BrowserUrl, Location, LocationChange, Url};
+use js_sys::Reflect;
+use std::cell::Cell;
+use tachydom::dom::{document, window};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{
+    Document, DomParser, Element, HtmlElement, Response, SupportedType,
+};
+
+const ISLAND_SELECTOR: &str = "leptos-island";
+const CHILDREN_SELECTOR: &str = "leptos-children";
+const PROP_ATTRIBUTE_PREFIX: &str = "data-prop-";
+// defined by the island script, once the WASM module has loaded
+const HYDRATE_ISLANDS: &str = "__hydrateIslands";
+
+thread_local! {
+    // incremented for each navigation, so that a page that finishes loading
+    // after a later navigation has started is ignored
+    static NAVIGATION: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Loads the page for the given location change and swaps it in, then
+/// updates the history, the scroll position and the router's URL.
+pub(crate) fn navigate(
+    location: &BrowserUrl,
+    change: LocationChange,
+    url: Url,
+) {
+    // if only the hash has changed, there's no need to load anything
+    let current = window().location();
+    if Some(&url.pathname) == current.pathname().ok().as_ref()
+        && Some(&url.search)
+            == current
+                .search()
+                .ok()
+                .map(|s| s.trim_start_matches('?').to_string())
+                .as_ref()
+    {
+        location.navigate(&change);
+        location.run_navigation_hook(url);
+        return;
+    }
+
+    let location = location.clone();
+    let href = change.value.clone();
+    load(&href, move |page| {
+        swap(&page);
+        location.navigate(&change);
+        location.run_navigation_hook(url);
+        focus();
+    });
+}
+
+/// Loads the page at the current URL and swaps it in, after a back or
+/// forward navigation, then updates the router's URL.
+///
+/// The browser restores the scroll position itself.
+pub(crate) fn restore(location: &BrowserUrl) {
+    let current = window().location();
+    let href = current.pathname().unwrap_or_default()
+        + &current.search().unwrap_or_default();
+    let location = location.clone();
+    load(&href, move |page| {
+        swap(&page);
+        BrowserUrl::scroll_to_el(false);
+        if let Ok(url) = BrowserUrl::try_current() {
+            location.run_navigation_hook(url);
+        }
+    });
+}
+
+/// Fetches and parses the page at `href`, falling back to a full page load
+/// if that fails.
+fn load(href: &str, on_load: impl FnOnce(Document) + 'static) {
+    let id = NAVIGATION.with(|n| {
+        n.set(n.get() + 1);
+        n.get()
+    });
+
+    let fallback = {
+        let href = href.to_string();
+        move || {
+            // unless a later navigation has already started
+            if NAVIGATION.with(Cell::get) == id {
+                _ = window().location().set_href(&href);
+            }
+        }
+    };
+    let on_error = {
+        let fallback = fallback.clone();
+        move || {
+            let fallback = fallback.clone();
+            Closure::once(move |_: JsValue| fallback())
+        }
+    };
+
+    let on_text = Closure::once({
+        let fallback = fallback.clone();
+        move |html: JsValue| {
+            // a later navigation has already started
+            if NAVIGATION.with(Cell::get) != id {
+                return;
+            }
+            let page = html.as_string().and_then(|html| {
+                DomParser::new()
+                    .and_then(|parser| {
+                        parser.parse_from_string(&html, SupportedType::TextHtml)
+                    })
+                    .ok()
+            });
+            match page {
+                Some(page) => on_load(page),
+                None => fallback(),
+            }
+        }
+    });
+    let on_response = Closure::once({
+        let on_error = on_error.clone();
+        move |res: JsValue| {
+            let res = res.unchecked_into::<Response>();
+            match res.text() {
+                Ok(text) if res.ok() => {
+                    let on_error = on_error();
+                    _ = text.then2(&on_text, &on_error);
+                    on_text.forget();
+                    on_error.forget();
+                }
+                _ => fallback(),
+            }
+        }
+    });
+
+    let on_error = on_error();
+    _ = window().fetch_with_str(href).then2(&on_response, &on_error);
+    on_response.forget();
+    on_error.forget();
+}
+
+/// Replaces the current `<body>` with the new page's, keeping the islands
+/// that haven't changed.
+fn swap(page: &Document) {
+    let (Some(old_body), Some(new_body)) = (document().body(), page.body())
+    else {
+        return;
+    };
+
+    // islands are matched in order, so that a page with several copies of
+    // the same island keeps each of them in place
+    let mut old_islands = islands(&old_body);
+    if let Ok(new_islands) = new_body.query_selector_all(ISLAND_SELECTOR) {
+        for i in 0..new_islands.length() {
+            let Some(new_island) = new_islands
+                .get(i)
+                .and_then(|node| node.dyn_into::<Element>().ok())
+            else {
+                continue;
+            };
+            // the island was inside one that has already been replaced
+            if !new_body.contains(Some(&new_island)) {
+                continue;
+            }
+            let key = island_key(&new_island);
+            let Some(pos) = old_islands.iter().position(|(old_key, old)| {
+                *old_key == key && old_body.contains(Some(old))
+            }) else {
+                continue;
+            };
+            let (_, old_island) = old_islands.remove(pos);
+            update_children(&old_island, &new_island);
+            _ = new_island.replace_with_with_node_1(&old_island);
+        }
+    }
+
+    if !page.title().is_empty() {
+        document().set_title(&page.title());
+    }
+    _ = old_body.replace_with_with_node_1(&new_body);
+    for script in scripts(&new_body) {
+        rerun_script(&script);
+    }
+
+    // hydrate any new islands
+    if let Ok(hydrate) =
+        Reflect::get(&window(), &JsValue::from_str(HYDRATE_ISLANDS))
+    {
+        if let Some(hydrate) = hydrate.dyn_ref::<js_sys::Function>() {
+            _ = hydrate.call1(&JsValue::UNDEFINED, &new_body);
+        }
+    }
+}
+
+/// Identifies an island by its component and serialized props.
+fn island_key(island: &Element) -> String {
+    let mut key = island.get_attribute("data-component").unwrap_or_default();
+    let attrs = island.attributes();
+    let mut props = (0..attrs.length())
+        .filter_map(|i| attrs.item(i))
+        .filter(|attr| attr.name().starts_with(PROP_ATTRIBUTE_PREFIX))
+        .map(|attr| (attr.name(), attr.value()))
+        .collect::<Vec<_>>();
+    props.sort();
+    for (name, value) in props {
+        key.push(' ');
+        key.push_str(&name);
+        key.push('=');
+        key.push_str(&value);
+    }
+    key
+}
+
+fn islands(root: &HtmlElement) -> Vec<(String, Element)> {
+    let Ok(islands) = root.query_selector_all(ISLAND_SELECTOR) else {
+        return Vec::new();
+    };
+    (0..islands.length())
+        .filter_map(|i| islands.get(i)?.dyn_into::<Element>().ok())
+        .map(|island| (island_key(&island), island))
+        .collect()
+}
+
+/// Replaces the server-rendered children of a kept island with those from
+/// the new page, if they have changed.
+fn update_children(old_island: &Element, new_island: &Element) {
+    let (Ok(old), Ok(new)) = (
+        old_island.query_selector_all(CHILDREN_SELECTOR),
+        new_island.query_selector_all(CHILDREN_SELECTOR),
+    ) else {
+        return;
+    };
+    for i in 0..old.length().min(new.length()) {
+        let (Some(old), Some(new)) = (
+            old.get(i).and_then(|node| node.dyn_into::<Element>().ok()),
+            new.get(i).and_then(|node| node.dyn_into::<Element>().ok()),
+        ) else {
+            continue;
+        };
+        if old.inner_html() != new.inner_html() {
+            old.set_inner_html(&new.inner_html());
+        }
+    }
+}
+
+fn scripts(root: &HtmlElement) -> Vec<Element> {
+    let Ok(scripts) = root.query_selector_all("script") else {
+        return Vec::new();
+    };
+    (0..scripts.length())
+        .filter_map(|i| scripts.get(i)?.dyn_into::<Element>().ok())
+        .collect()
+}
+
+/// Scripts that are parsed by a `DOMParser` don't run, so each one is
+/// replaced with a copy, which runs as soon as it is inserted.
+fn rerun_script(script: &Element) {
+    let Ok(copy) = document().create_element("script") else {
+        return;
+    };
+    let attrs = script.attributes();
+    for i in 0..attrs.length() {
+        if let Some(attr) = attrs.item(i) {
+            _ = copy.set_attribute(&attr.name(), &attr.value());
+        }
+    }
+    copy.set_text_content(script.text_content().as_deref());
+    _ = script.replace_with_with_node_1(&copy);
+}
+
+/// Moves focus to the new page's `autofocus` element, if any. Otherwise,
+/// focus is cleared, so that the next tab starts from the top of the page.
+fn focus() {
+    let autofocus = document()
+        .query_selector("[autofocus]")
+        .ok()
+        .flatten()
+        .and_then(|el| el.dyn_into::<HtmlElement>().ok());
+    if let Some(el) = autofocus {
+        _ = el.focus();
+    } else if let Some(active) = document()
+        .active_element()
+        .and_then(|el| el.dyn_into::<HtmlElement>().ok())
+    {
+        _ = active.blur();
+    }
+}
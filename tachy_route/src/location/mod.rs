@@ -10,6 +10,7 @@ use tachydom::{
 use wasm_bindgen::{closure::Closure, JsCast, JsValue};
 use web_sys::{Event, HtmlAnchorElement, MouseEvent, UrlSearchParams};
 
+mod islands;
 pub mod state;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -111,6 +112,7 @@ impl Location for RequestUrl {
 #[derive(Clone, Default)]
 pub struct BrowserUrl {
     navigation_hook: Option<Rc<dyn Fn(Url)>>,
+    islands: bool,
 }
 
 impl Debug for BrowserUrl {
@@ -124,6 +126,19 @@ impl BrowserUrl {
         Self::default()
     }
 
+    /// Creates a location for an app that uses islands.
+    ///
+    /// Rather than doing a full page load, navigating to another page
+    /// fetches its HTML from the server and swaps it into the current
+    /// `<body>`. Islands that are rendered with the same component and props
+    /// on both pages keep their state; new islands are hydrated.
+    pub fn islands() -> Self {
+        Self {
+            islands: true,
+            ..Self::default()
+        }
+    }
+
     fn try_current() -> Result<Url, JsValue> {
         let location = window().location();
         Ok(Url {
@@ -167,6 +182,13 @@ impl BrowserUrl {
         })
     }
 
+    /// Runs the router's navigation hook, if one has been set.
+    fn run_navigation_hook(&self, url: Url) {
+        if let Some(navigation_hook) = &self.navigation_hook {
+            navigation_hook(url);
+        }
+    }
+
     fn unescape(s: &str) -> String {
         js_sys::decode_uri(s).unwrap().into()
     }
@@ -309,13 +331,14 @@ impl Location for BrowserUrl {
                     state: State(state),
                 };
 
-                // run any router-specific hook
-                if let Some(navigate_hook) = &this.navigation_hook {
-                    navigate_hook(url);
+                // complete navigation and run any router-specific hook; in
+                // islands mode, the hook runs once the new page has loaded
+                if this.islands {
+                    islands::navigate(&this, change, url);
+                } else {
+                    this.run_navigation_hook(url);
+                    this.navigate(&change);
                 }
-
-                // complete navigation
-                this.navigate(&change);
             }
         };
 
@@ -334,7 +357,18 @@ impl Location for BrowserUrl {
             );
 
         // handle popstate event (forward/back navigation)
-        if let Some(navigation_hook) = self.navigation_hook.clone() {
+        if self.islands {
+            let this = self.clone();
+            let cb = move || islands::restore(&this);
+            let closure =
+                Closure::wrap(Box::new(cb) as Box<dyn Fn()>).into_js_value();
+            window()
+                .add_event_listener_with_callback(
+                    "popstate",
+                    closure.as_ref().unchecked_ref(),
+                )
+                .expect("couldn't add `popstate` listener to `window`");
+        } else if let Some(navigation_hook) = self.navigation_hook.clone() {
             let cb = move || match Self::try_current() {
                 Ok(url) => navigation_hook(url),
                 Err(e) => {
@@ -203,7 +203,7 @@ where
 
     fn mount(
         &mut self,
-        parent: &<Rndr as Renderer>::Element,
+        parent: &<Rndr as Renderer>::Node,
         marker: Option<&<Rndr as Renderer>::Node>,
     ) {
         self.inner.mount(parent, marker);
@@ -211,7 +211,7 @@ where
 
     fn insert_before_this(
         &self,
-        parent: &<Rndr as Renderer>::Element,
+        parent: &<Rndr as Renderer>::Node,
         child: &mut dyn Mountable<Rndr>,
     ) -> bool {
         self.inner.insert_before_this(parent, child)
@@ -402,7 +402,7 @@ where
 
     fn mount(
         &mut self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         marker: Option<&<R as Renderer>::Node>,
     ) {
         self.view_state.mount(parent, marker);
@@ -410,7 +410,7 @@ where
 
     fn insert_before_this(
         &self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool {
         self.view_state.insert_before_this(parent, child)
@@ -235,7 +235,7 @@ where
 
     fn mount(
         &mut self,
-        parent: &<Rndr as Renderer>::Element,
+        parent: &<Rndr as Renderer>::Node,
         marker: Option<&<Rndr as Renderer>::Node>,
     ) {
         self.write().mount(parent, marker);
@@ -243,7 +243,7 @@ where
 
     fn insert_before_this(
         &self,
-        parent: &<Rndr as Renderer>::Element,
+        parent: &<Rndr as Renderer>::Node,
         child: &mut dyn Mountable<Rndr>,
     ) -> bool {
         self.write().insert_before_this(parent, child)
@@ -0,0 +1,220 @@
+use super::global::AddAttribute;
+use crate::{
+    html::{
+        attribute::Attribute,
+        element::{CreateElement, ElementType, HtmlElement},
+    },
+    renderer::Renderer,
+    view::{either::Either, Position, RenderHtml, ToTemplate},
+};
+use next_tuple::TupleBuilder;
+use std::marker::PhantomData;
+
+/// Adds attributes to a view after it has been created.
+///
+/// This is used to apply the `attr:`, `class:`, `style:` and `on:`
+/// attributes at the place a component is used to the element at the root of
+/// the component. A component that accepts them returns
+/// `impl RenderHtml<Dom> + AddAnyAttr<Dom>`.
+#[diagnostic::on_unimplemented(
+    message = "attributes can't be added to `{Self}`",
+    note = "attributes can only be passed to a component whose root is a \
+            single element, and which returns `impl RenderHtml<Dom> + \
+            AddAnyAttr<Dom>`"
+)]
+pub trait AddAnyAttr<Rndr: Renderer>
+where
+    Rndr::Node: Clone,
+    Rndr::Element: Clone,
+{
+    /// The view, with the new attribute added.
+    type Output<SomeNewAttr: Attribute<Rndr>>: RenderHtml<Rndr>;
+
+    /// Adds the attribute to the view.
+    fn add_any_attr<NewAttr: Attribute<Rndr>>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>;
+}
+
+impl<E, At, Ch, Rndr> AddAnyAttr<Rndr> for HtmlElement<E, At, Ch, Rndr>
+where
+    E: ElementType + CreateElement<Rndr>,
+    At: Attribute<Rndr>,
+    Ch: RenderHtml<Rndr>,
+    Rndr: Renderer,
+    Rndr::Node: Clone,
+    Rndr::Element: Clone,
+{
+    type Output<SomeNewAttr: Attribute<Rndr>> =
+        HtmlElement<E, (At, SomeNewAttr), Ch, Rndr>;
+
+    fn add_any_attr<NewAttr: Attribute<Rndr>>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr> {
+        let HtmlElement {
+            tag,
+            attributes,
+            children,
+            rndr,
+        } = self;
+        HtmlElement {
+            tag,
+            attributes: (attributes, attr),
+            children,
+            rndr,
+        }
+    }
+}
+
+impl<A, B, Rndr> AddAnyAttr<Rndr> for Either<A, B>
+where
+    A: AddAnyAttr<Rndr>,
+    B: AddAnyAttr<Rndr>,
+    Rndr: Renderer,
+    Rndr::Node: Clone,
+    Rndr::Element: Clone,
+{
+    type Output<SomeNewAttr: Attribute<Rndr>> =
+        Either<A::Output<SomeNewAttr>, B::Output<SomeNewAttr>>;
+
+    // only one of the two views exists, so it gets the attribute
+    fn add_any_attr<NewAttr: Attribute<Rndr>>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr> {
+        match self {
+            Either::Left(left) => Either::Left(left.add_any_attr(attr)),
+            Either::Right(right) => Either::Right(right.add_any_attr(attr)),
+        }
+    }
+}
+
+/// Creates an empty list of attributes, which can be built up with the same
+/// methods as an element, e.g., `.class(...)` or `.on(...)`.
+pub fn attributes<Rndr>() -> AttributeList<(), Rndr>
+where
+    Rndr: Renderer,
+{
+    AttributeList {
+        attrs: (),
+        rndr: PhantomData,
+    }
+}
+
+/// A list of attributes that is not attached to any element yet.
+#[derive(Debug)]
+pub struct AttributeList<At, Rndr> {
+    attrs: At,
+    rndr: PhantomData<Rndr>,
+}
+
+impl<At, Rndr, NewAttr> AddAttribute<NewAttr, Rndr> for AttributeList<At, Rndr>
+where
+    At: Attribute<Rndr> + TupleBuilder<NewAttr>,
+    <At as TupleBuilder<NewAttr>>::Output: Attribute<Rndr>,
+    Rndr: Renderer,
+{
+    type Output = AttributeList<<At as TupleBuilder<NewAttr>>::Output, Rndr>;
+
+    fn add_attr(self, attr: NewAttr) -> Self::Output {
+        AttributeList {
+            attrs: self.attrs.next_tuple(attr),
+            rndr: PhantomData,
+        }
+    }
+}
+
+impl<At, Rndr> Attribute<Rndr> for AttributeList<At, Rndr>
+where
+    At: Attribute<Rndr>,
+    Rndr: Renderer,
+{
+    const MIN_LENGTH: usize = At::MIN_LENGTH;
+
+    type State = At::State;
+
+    fn to_html(
+        self,
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        inner_html: &mut String,
+    ) {
+        self.attrs.to_html(buf, class, style, inner_html);
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &Rndr::Element,
+    ) -> Self::State {
+        self.attrs.hydrate::<FROM_SERVER>(el)
+    }
+
+    fn build(self, el: &Rndr::Element) -> Self::State {
+        self.attrs.build(el)
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.attrs.rebuild(state);
+    }
+
+    fn remove(state: Self::State, el: &Rndr::Element) {
+        At::remove(state, el);
+    }
+}
+
+impl<At, Rndr> ToTemplate for AttributeList<At, Rndr>
+where
+    At: ToTemplate,
+{
+    const CLASS: &'static str = At::CLASS;
+    const STYLE: &'static str = At::STYLE;
+
+    fn to_template(
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        inner_html: &mut String,
+        position: &mut Position,
+    ) {
+        At::to_template(buf, class, style, inner_html, position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{attributes, AddAnyAttr};
+    use crate::{
+        html::{
+            attribute::{custom::CustomAttribute, global::ClassAttribute},
+            element::{button, ElementChild},
+        },
+        renderer::mock_dom::MockDom,
+        view::{Render, RenderHtml},
+    };
+
+    #[test]
+    fn attributes_are_added_to_root_element() {
+        let attrs = attributes::<MockDom>()
+            .attr("aria-label", "Close")
+            .class(("primary", true));
+        let html = button::<MockDom>()
+            .class("btn")
+            .child("X")
+            .add_any_attr(attrs)
+            .to_html();
+        assert_eq!(
+            html,
+            "<button aria-label=\"Close\" class=\"btn primary\">X</button>"
+        );
+    }
+
+    #[test]
+    fn added_attributes_are_built() {
+        let attrs = attributes::<MockDom>().attr("data-id", "1");
+        let state = button::<MockDom>().add_any_attr(attrs).build();
+        assert_eq!(state.el.attribute("data-id").as_deref(), Some("1"));
+    }
+}
@@ -38,7 +38,7 @@ where
     R: DomRenderer,
 {
     const MIN_LENGTH: usize = 0;
-    type State = V::State;
+    type State = (K, V::State);
 
     fn to_html(
         self,
@@ -51,19 +51,28 @@ where
     }
 
     fn hydrate<const FROM_SERVER: bool>(self, el: &R::Element) -> Self::State {
-        if !K::KEY.is_empty() {
+        let state = if !K::KEY.is_empty() {
             self.value.hydrate::<FROM_SERVER>(self.key.as_ref(), el)
         } else {
             self.value.build(el, self.key.as_ref())
-        }
+        };
+        (self.key, state)
     }
 
     fn build(self, el: &R::Element) -> Self::State {
-        self.value.build(el, self.key.as_ref())
+        let state = self.value.build(el, self.key.as_ref());
+        (self.key, state)
     }
 
     fn rebuild(self, state: &mut Self::State) {
+        let (key, state) = state;
         self.value.rebuild(self.key.as_ref(), state);
+        *key = self.key;
+    }
+
+    fn remove(state: Self::State, el: &R::Element) {
+        let (key, _) = state;
+        R::remove_attribute(el, key.as_ref());
     }
 }
 
@@ -304,6 +304,7 @@ where
     E::EventType: From<Rndr::Event>,
     F: FnMut(E::EventType) + 'static,
     Rndr: DomRenderer,
+    Rndr::Element: Clone + 'static,
     Self: Sized + AddAttribute<On<Rndr>, Rndr>,
 {
     fn on(
@@ -316,6 +317,19 @@ where {
     }
 }
 
+/// Adds an attribute, or a set of attributes, to an element. This is what a
+/// `{attrs}` block creates in the `view` macro.
+pub trait AttrsAttribute<A, Rndr>
+where
+    A: Attribute<Rndr>,
+    Rndr: Renderer,
+    Self: Sized + AddAttribute<A, Rndr>,
+{
+    fn attrs(self, attrs: A) -> <Self as AddAttribute<A, Rndr>>::Output {
+        self.add_attr(attrs)
+    }
+}
+
 impl<T, Rndr, V> GlobalAttributes<Rndr, V> for T
 where
     T: AddAttribute<Attr<Accesskey, V, Rndr>, Rndr>
@@ -384,5 +398,126 @@ where
     E::EventType: From<Rndr::Event>,
     F: FnMut(E::EventType) + 'static,
     Rndr: DomRenderer,
+    Rndr::Element: Clone + 'static,
+{
+}
+
+impl<T, A, Rndr> AttrsAttribute<A, Rndr> for T
+where
+    T: AddAttribute<A, Rndr>,
+    A: Attribute<Rndr>,
+    Rndr: Renderer,
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AttrsAttribute, ClassAttribute, StyleAttribute};
+    use crate::{
+        html::{
+            attribute::{
+                custom::CustomAttribute, id, spread::IntoSpread, title,
+            },
+            class::class,
+            element::{div, ElementChild},
+            style::style,
+        },
+        renderer::mock_dom::MockDom,
+        view::{Render, RenderHtml},
+    };
+
+    #[test]
+    fn attrs_render_to_html() {
+        let html = div::<MockDom>()
+            .class("card")
+            .attrs((id("main"), class("wide")))
+            .child("Hello")
+            .to_html();
+        assert_eq!(html, "<div id=\"main\" class=\"card wide\">Hello</div>");
+    }
+
+    #[test]
+    fn optional_attrs_are_added_when_some() {
+        let view = |attr: Option<&'static str>| {
+            div::<MockDom>().attrs(attr.map(title))
+        };
+
+        let mut state = view(None).build();
+        assert_eq!(state.el.attribute("title"), None);
+
+        view(Some("Hello")).rebuild(&mut state);
+        assert_eq!(state.el.attribute("title").as_deref(), Some("Hello"));
+    }
+
+    #[test]
+    fn optional_attrs_are_removed_when_none() {
+        let view = |attrs: Option<(&'static str, &'static str)>| {
+            div::<MockDom>()
+                .attr("data-id", "1")
+                .attrs(attrs.map(|(a, b)| (id(a), title(b))))
+        };
+
+        let mut state = view(Some(("main", "Hello"))).build();
+        assert_eq!(state.el.attribute("id").as_deref(), Some("main"));
+        assert_eq!(state.el.attribute("title").as_deref(), Some("Hello"));
+
+        view(None).rebuild(&mut state);
+        assert_eq!(state.el.attribute("id"), None);
+        assert_eq!(state.el.attribute("title"), None);
+        assert_eq!(state.el.attribute("data-id").as_deref(), Some("1"));
+
+        view(Some(("other", "Hi"))).rebuild(&mut state);
+        assert_eq!(state.el.attribute("id").as_deref(), Some("other"));
+        assert_eq!(state.el.attribute("title").as_deref(), Some("Hi"));
+    }
+
+    #[test]
+    fn optional_classes_only_remove_their_own_classes() {
+        let view = |active: bool| {
+            div::<MockDom>()
+                .class("btn")
+                .attrs(active.then(|| class(("active", true))))
+        };
+
+        let mut state = view(true).build();
+        assert_eq!(state.el.attribute("class").as_deref(), Some("btn active"));
+
+        view(false).rebuild(&mut state);
+        assert_eq!(state.el.attribute("class").as_deref(), Some("btn"));
+    }
+
+    #[test]
+    fn optional_styles_only_remove_their_own_properties() {
+        let view = |hidden: bool| {
+            div::<MockDom>()
+                .style(("color", "red"))
+                .attrs(hidden.then(|| style(("display", "none"))))
+        };
+
+        let mut state = view(true).build();
+        assert_eq!(
+            state.el.attribute("style").as_deref(),
+            Some("color: red; display: none;")
+        );
+
+        view(false).rebuild(&mut state);
+        assert_eq!(state.el.attribute("style").as_deref(), Some("color: red;"));
+    }
+
+    #[test]
+    fn optional_spread_keeps_merged_classes() {
+        let view = |wide: bool| {
+            div::<MockDom>().class("card").attrs(wide.then(|| {
+                vec![("class", "wide".to_string()), ("id", "main".to_string())]
+                    .into_spread()
+            }))
+        };
+
+        let mut state = view(true).build();
+        assert_eq!(state.el.attribute("class").as_deref(), Some("card wide"));
+
+        view(false).rebuild(&mut state);
+        assert_eq!(state.el.attribute("class").as_deref(), Some("card"));
+        assert_eq!(state.el.attribute("id"), None);
+    }
+}
@@ -1,7 +1,9 @@
+pub mod any_attr;
 pub mod aria;
 pub mod custom;
 pub mod global;
 mod key;
+pub mod spread;
 mod value;
 use crate::{
     renderer::Renderer,
@@ -11,6 +13,12 @@ pub use key::*;
 use std::{fmt::Debug, marker::PhantomData};
 pub use value::*;
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not an attribute",
+    note = "attributes are created by functions like `id(...)` or \
+            `class(...)`; a tuple of attributes, an `Option` of one, or a \
+            closure that returns one is an attribute too"
+)]
 pub trait Attribute<R: Renderer> {
     const MIN_LENGTH: usize;
 
@@ -29,6 +37,13 @@ pub trait Attribute<R: Renderer> {
     fn build(self, el: &R::Element) -> Self::State;
 
     fn rebuild(self, state: &mut Self::State);
+
+    /// Removes whatever this set on the element, as when an optional
+    /// attribute changes to `None`.
+    ///
+    /// By default, this only drops the state.
+    #[allow(unused)]
+    fn remove(state: Self::State, el: &R::Element) {}
 }
 
 impl<R> Attribute<R> for ()
@@ -56,6 +71,63 @@ where
     fn rebuild(self, _state: &mut Self::State) {}
 }
 
+/// An optional attribute, or set of attributes, is only added if it is
+/// `Some(_)`.
+///
+/// Changing to `None` [removes](Attribute::remove) whatever the attribute
+/// had set on the element.
+impl<A, R> Attribute<R> for Option<A>
+where
+    A: Attribute<R>,
+    R: Renderer,
+    R::Element: Clone,
+{
+    const MIN_LENGTH: usize = 0;
+
+    type State = (R::Element, Option<A::State>);
+
+    fn to_html(
+        self,
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        inner_html: &mut String,
+    ) {
+        if let Some(attr) = self {
+            attr.to_html(buf, class, style, inner_html);
+        }
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(self, el: &R::Element) -> Self::State {
+        let state = self.map(|attr| attr.hydrate::<FROM_SERVER>(el));
+        (el.clone(), state)
+    }
+
+    fn build(self, el: &R::Element) -> Self::State {
+        let state = self.map(|attr| attr.build(el));
+        (el.clone(), state)
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let (el, prev) = state;
+        match (self, prev.as_mut()) {
+            (None, _) => {
+                if let Some(prev) = prev.take() {
+                    A::remove(prev, el);
+                }
+            }
+            (Some(attr), None) => *prev = Some(attr.build(el)),
+            (Some(attr), Some(prev)) => attr.rebuild(prev),
+        }
+    }
+
+    fn remove(state: Self::State, el: &R::Element) {
+        if let (_, Some(state)) = state {
+            A::remove(state, el);
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Attr<K, V, R>(pub K, pub V, PhantomData<R>)
 where
@@ -111,6 +183,10 @@ where
     fn rebuild(self, state: &mut Self::State) {
         V::rebuild(self.1, K::KEY, state);
     }
+
+    fn remove(_state: Self::State, el: &R::Element) {
+        R::remove_attribute(el, K::KEY);
+    }
 }
 
 macro_rules! impl_attr_for_tuples {
@@ -161,6 +237,14 @@ macro_rules! impl_attr_for_tuples {
 					$([<$ty:lower>].rebuild([<view_ $ty:lower>]));*
 				}
 			}
+
+			fn remove(state: Self::State, el: &Rndr::Element) {
+				paste::paste! {
+					let ([<$first:lower>], $([<$ty:lower>],)*) = state;
+					$first::remove([<$first:lower>], el);
+					$($ty::remove([<$ty:lower>], el));*
+				}
+			}
 		}
 	};
 }
@@ -198,6 +282,10 @@ where
     fn rebuild(self, state: &mut Self::State) {
         self.0.rebuild(state);
     }
+
+    fn remove(state: Self::State, el: &Rndr::Element) {
+        A::remove(state, el);
+    }
 }
 
 impl_attr_for_tuples!(A, B);
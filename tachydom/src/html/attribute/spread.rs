@@ -0,0 +1,481 @@
+use super::global::AddAttribute;
+use crate::{
+    html::attribute::{Attribute, AttributeValue},
+    renderer::{DomRenderer, Renderer},
+    view::{Position, ToTemplate},
+};
+use std::{borrow::Cow, fmt::Debug, marker::PhantomData};
+
+/// Adds a set of attributes, which is only known at runtime, to an element.
+pub trait SpreadAttribute<S, Rndr>
+where
+    S: IntoSpread<Rndr>,
+    Rndr: Renderer,
+    Self: Sized + AddAttribute<S::Output, Rndr>,
+{
+    /// Spreads the attributes onto the element. This is what `{..attrs}`
+    /// creates in the `view` macro.
+    ///
+    /// `class` and `style` are merged with any other classes and styles on
+    /// the element, rather than replacing them.
+    fn spread(
+        self,
+        attrs: S,
+    ) -> <Self as AddAttribute<S::Output, Rndr>>::Output {
+        self.add_attr(attrs.into_spread())
+    }
+}
+
+impl<T, S, Rndr> SpreadAttribute<S, Rndr> for T
+where
+    T: AddAttribute<S::Output, Rndr>,
+    S: IntoSpread<Rndr>,
+    Rndr: Renderer,
+{
+}
+
+/// Converts a set of attributes into an attribute that can be spread onto
+/// an element.
+///
+/// This is implemented for a `Vec` of `(name, value)` pairs, and for any
+/// function that returns one, which is updated reactively.
+pub trait IntoSpread<R: Renderer> {
+    /// The attribute that is spread onto the element.
+    type Output: Attribute<R>;
+
+    /// Converts the set of attributes.
+    fn into_spread(self) -> Self::Output;
+}
+
+/// A list of attributes, each with a name that is only known at runtime.
+pub struct Spread<V, R>
+where
+    V: AttributeValue<R>,
+    R: Renderer,
+{
+    attrs: Vec<(Cow<'static, str>, V)>,
+    rndr: PhantomData<R>,
+}
+
+impl<V, R> Debug for Spread<V, R>
+where
+    V: AttributeValue<R> + Debug,
+    R: Renderer,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Spread").field(&self.attrs).finish()
+    }
+}
+
+impl<V, R> Spread<V, R>
+where
+    V: AttributeValue<R>,
+    R: Renderer,
+{
+    /// Creates a list of attributes.
+    pub fn new<K>(attrs: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<Cow<'static, str>>,
+    {
+        Self {
+            attrs: attrs
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+            rndr: PhantomData,
+        }
+    }
+}
+
+impl<V, R> IntoSpread<R> for Spread<V, R>
+where
+    V: AttributeValue<R>,
+    R: DomRenderer,
+    R::Element: Clone,
+{
+    type Output = Self;
+
+    fn into_spread(self) -> Self::Output {
+        self
+    }
+}
+
+impl<K, V, R> IntoSpread<R> for Vec<(K, V)>
+where
+    K: Into<Cow<'static, str>>,
+    V: AttributeValue<R>,
+    R: DomRenderer,
+    R::Element: Clone,
+{
+    type Output = Spread<V, R>;
+
+    fn into_spread(self) -> Self::Output {
+        Spread::new(self)
+    }
+}
+
+/// The state of a [`Spread`], with the state of each attribute, by name.
+pub struct SpreadState<S, R>
+where
+    R: Renderer,
+{
+    el: R::Element,
+    attrs: Vec<(Cow<'static, str>, SpreadAttrState<S>)>,
+}
+
+/// How a single attribute in a [`Spread`] has been applied to the element.
+///
+/// Classes and styles are added to the element's class list and style, so
+/// that they are merged with any others, and only the ones that were added
+/// by the spread are removed.
+enum SpreadAttrState<S> {
+    Attr(S),
+    Class(Vec<String>),
+    Style(Vec<String>),
+}
+
+impl<S> SpreadAttrState<S> {
+    fn build<V, R>(key: &str, value: V, el: &R::Element) -> Self
+    where
+        V: AttributeValue<R, State = S>,
+        R: DomRenderer,
+    {
+        match key {
+            "class" => {
+                let classes = classes(&value_to_string(key, value));
+                let class_list = R::class_list(el);
+                for class in &classes {
+                    R::add_class(&class_list, class);
+                }
+                Self::Class(classes)
+            }
+            "style" => {
+                let props = style_props(&value_to_string(key, value));
+                let style = R::style(el);
+                for (name, value) in &props {
+                    R::set_css_property(&style, name, value);
+                }
+                Self::Style(props.into_iter().map(|(name, _)| name).collect())
+            }
+            _ => Self::Attr(value.build(el, key)),
+        }
+    }
+
+    fn rebuild<V, R>(&mut self, key: &str, value: V, el: &R::Element)
+    where
+        V: AttributeValue<R, State = S>,
+        R: DomRenderer,
+    {
+        match self {
+            Self::Attr(state) => value.rebuild(key, state),
+            Self::Class(prev) => {
+                let classes = classes(&value_to_string(key, value));
+                let class_list = R::class_list(el);
+                for class in prev.iter().filter(|c| !classes.contains(c)) {
+                    R::remove_class(&class_list, class);
+                }
+                for class in &classes {
+                    R::add_class(&class_list, class);
+                }
+                *prev = classes;
+            }
+            Self::Style(prev) => {
+                let props = style_props(&value_to_string(key, value));
+                let style = R::style(el);
+                for name in prev
+                    .iter()
+                    .filter(|name| !props.iter().any(|(n, _)| n == *name))
+                {
+                    R::set_css_property(&style, name, "");
+                }
+                for (name, value) in &props {
+                    R::set_css_property(&style, name, value);
+                }
+                *prev = props.into_iter().map(|(name, _)| name).collect();
+            }
+        }
+    }
+
+    fn remove<R>(self, key: &str, el: &R::Element)
+    where
+        R: DomRenderer,
+    {
+        match self {
+            Self::Attr(_) => R::remove_attribute(el, key),
+            Self::Class(classes) => {
+                let class_list = R::class_list(el);
+                for class in classes {
+                    R::remove_class(&class_list, &class);
+                }
+            }
+            Self::Style(names) => {
+                let style = R::style(el);
+                for name in names {
+                    R::set_css_property(&style, &name, "");
+                }
+            }
+        }
+    }
+}
+
+/// Renders the value on its own, and returns it without the attribute name
+/// and quotes.
+fn value_to_string<V, R>(key: &str, value: V) -> String
+where
+    V: AttributeValue<R>,
+    R: Renderer,
+{
+    let mut attr = String::new();
+    value.to_html(key, &mut attr);
+    attr.strip_prefix(&format!(" {key}=\""))
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn classes(value: &str) -> Vec<String> {
+    value.split_whitespace().map(String::from).collect()
+}
+
+fn style_props(value: &str) -> Vec<(String, String)> {
+    value
+        .split(';')
+        .filter_map(|prop| prop.split_once(':'))
+        .map(|(name, value)| {
+            (name.trim().to_string(), value.trim().to_string())
+        })
+        .collect()
+}
+
+impl<V, R> Attribute<R> for Spread<V, R>
+where
+    V: AttributeValue<R>,
+    R: DomRenderer,
+    R::Element: Clone,
+{
+    const MIN_LENGTH: usize = 0;
+
+    type State = SpreadState<V::State, R>;
+
+    fn to_html(
+        self,
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        _inner_html: &mut String,
+    ) {
+        for (key, value) in self.attrs {
+            match key.as_ref() {
+                // merge the value with the element's other classes or styles
+                "class" => {
+                    let value = value_to_string(&key, value);
+                    if !value.is_empty() {
+                        class.push(' ');
+                        class.push_str(&value);
+                    }
+                }
+                "style" => {
+                    let value = value_to_string(&key, value);
+                    if !value.is_empty() {
+                        style.push_str(&value);
+                        style.push(';');
+                    }
+                }
+                _ => value.to_html(&key, buf),
+            }
+        }
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(self, el: &R::Element) -> Self::State {
+        let attrs = self
+            .attrs
+            .into_iter()
+            .map(|(key, value)| {
+                let state = match key.as_ref() {
+                    // classes and styles were merged into the element's own
+                    // when it was rendered to HTML
+                    "class" if FROM_SERVER => SpreadAttrState::Class(classes(
+                        &value_to_string(&key, value),
+                    )),
+                    "style" if FROM_SERVER => SpreadAttrState::Style(
+                        style_props(&value_to_string(&key, value))
+                            .into_iter()
+                            .map(|(name, _)| name)
+                            .collect(),
+                    ),
+                    "class" | "style" => {
+                        SpreadAttrState::build(&key, value, el)
+                    }
+                    _ => SpreadAttrState::Attr(
+                        value.hydrate::<FROM_SERVER>(&key, el),
+                    ),
+                };
+                (key, state)
+            })
+            .collect();
+        SpreadState {
+            el: el.clone(),
+            attrs,
+        }
+    }
+
+    fn build(self, el: &R::Element) -> Self::State {
+        let attrs = self
+            .attrs
+            .into_iter()
+            .map(|(key, value)| {
+                let state = SpreadAttrState::build(&key, value, el);
+                (key, state)
+            })
+            .collect();
+        SpreadState {
+            el: el.clone(),
+            attrs,
+        }
+    }
+
+    // attributes are matched by name, so each one is updated in place, and
+    // any that are no longer in the list are removed from the element
+    fn rebuild(self, state: &mut Self::State) {
+        let SpreadState { el, attrs } = state;
+        let mut prev = std::mem::take(attrs);
+        for (key, value) in self.attrs {
+            let state = match prev.iter().position(|(prev, _)| *prev == key) {
+                Some(idx) => {
+                    let (_, mut state) = prev.swap_remove(idx);
+                    state.rebuild(&key, value, el);
+                    state
+                }
+                None => SpreadAttrState::build(&key, value, el),
+            };
+            attrs.push((key, state));
+        }
+        for (key, state) in prev {
+            state.remove::<R>(&key, el);
+        }
+    }
+
+    fn remove(state: Self::State, el: &R::Element) {
+        for (key, state) in state.attrs {
+            state.remove::<R>(&key, el);
+        }
+    }
+}
+
+impl<V, R> ToTemplate for Spread<V, R>
+where
+    V: AttributeValue<R>,
+    R: Renderer,
+{
+    // the attributes are only known at runtime
+    fn to_template(
+        _buf: &mut String,
+        _class: &mut String,
+        _style: &mut String,
+        _inner_html: &mut String,
+        _position: &mut Position,
+    ) {
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpreadAttribute;
+    use crate::{
+        html::{
+            attribute::global::{ClassAttribute, StyleAttribute},
+            element::{div, ElementChild},
+        },
+        renderer::mock_dom::MockDom,
+        view::{Render, RenderHtml},
+    };
+
+    #[test]
+    fn spread_attributes_render_to_html() {
+        let html = div::<MockDom>()
+            .class("card")
+            .spread(vec![
+                ("aria-label", "Close".to_string()),
+                ("data-id", "1".to_string()),
+                ("class", "wide".to_string()),
+            ])
+            .child("Hello")
+            .to_html();
+        assert_eq!(
+            html,
+            "<div aria-label=\"Close\" data-id=\"1\" class=\"card wide\">\
+             Hello</div>"
+        );
+    }
+
+    #[test]
+    fn spread_attributes_are_diffed_by_name() {
+        let view =
+            |attrs: Vec<(&'static str, String)>| div::<MockDom>().spread(attrs);
+
+        let mut state = view(vec![
+            ("data-a", "1".to_string()),
+            ("data-b", "2".to_string()),
+        ])
+        .build();
+        assert_eq!(state.el.attribute("data-a").as_deref(), Some("1"));
+        assert_eq!(state.el.attribute("data-b").as_deref(), Some("2"));
+
+        view(vec![
+            ("data-b", "3".to_string()),
+            ("data-c", "4".to_string()),
+        ])
+        .rebuild(&mut state);
+        assert_eq!(state.el.attribute("data-a"), None);
+        assert_eq!(state.el.attribute("data-b").as_deref(), Some("3"));
+        assert_eq!(state.el.attribute("data-c").as_deref(), Some("4"));
+    }
+
+    #[test]
+    fn spread_class_and_style_are_merged_on_build() {
+        let state = div::<MockDom>()
+            .class("card")
+            .style("color: red")
+            .spread(vec![
+                ("class", "wide".to_string()),
+                ("style", "margin: 0".to_string()),
+            ])
+            .build();
+        assert_eq!(state.el.attribute("class").as_deref(), Some("card wide"));
+        assert_eq!(
+            state.el.attribute("style").as_deref(),
+            Some("color: red; margin: 0;")
+        );
+    }
+
+    #[test]
+    fn removing_spread_class_and_style_keeps_the_others() {
+        let view = |attrs: Vec<(&'static str, String)>| {
+            div::<MockDom>()
+                .class("card")
+                .style("color: red")
+                .spread(attrs)
+        };
+
+        let mut state = view(vec![
+            ("class", "wide tall".to_string()),
+            ("style", "margin: 0; padding: 0".to_string()),
+        ])
+        .build();
+
+        view(vec![
+            ("class", "tall".to_string()),
+            ("style", "padding: 0".to_string()),
+        ])
+        .rebuild(&mut state);
+        assert_eq!(state.el.attribute("class").as_deref(), Some("card tall"));
+        assert_eq!(
+            state.el.attribute("style").as_deref(),
+            Some("color: red; padding: 0;")
+        );
+
+        view(vec![]).rebuild(&mut state);
+        assert_eq!(state.el.attribute("class").as_deref(), Some("card"));
+        assert_eq!(state.el.attribute("style").as_deref(), Some("color: red;"));
+    }
+}
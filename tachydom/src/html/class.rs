@@ -57,6 +57,10 @@ where
     fn rebuild(self, state: &mut Self::State) {
         self.class.rebuild(state)
     }
+
+    fn remove(state: Self::State, el: &R::Element) {
+        C::remove(state, el);
+    }
 }
 
 impl<C, R> ToTemplate for Class<C, R>
@@ -93,6 +97,12 @@ pub trait IntoClass<R: DomRenderer> {
     fn build(self, el: &R::Element) -> Self::State;
 
     fn rebuild(self, state: &mut Self::State);
+
+    /// Removes the classes this added to the element.
+    ///
+    /// By default, this only drops the state.
+    #[allow(unused)]
+    fn remove(state: Self::State, el: &R::Element) {}
 }
 
 impl<'a, R> IntoClass<R> for &'a str
@@ -125,6 +135,11 @@ where
         }
         *prev = self;
     }
+
+    fn remove(state: Self::State, el: &R::Element) {
+        let (_, prev) = state;
+        remove_classes::<R>(el, prev);
+    }
 }
 
 impl<R> IntoClass<R> for String
@@ -157,13 +172,18 @@ where
         }
         *prev = self;
     }
+
+    fn remove(state: Self::State, el: &R::Element) {
+        let (_, prev) = state;
+        remove_classes::<R>(el, &prev);
+    }
 }
 
 impl<R> IntoClass<R> for (&'static str, bool)
 where
     R: DomRenderer,
 {
-    type State = (R::ClassList, bool);
+    type State = (R::ClassList, &'static str, bool);
 
     fn to_html(self, class: &mut String) {
         let (name, include) = self;
@@ -178,7 +198,7 @@ where
         if !FROM_SERVER && include {
             R::add_class(&class_list, name);
         }
-        (class_list, self.1)
+        (class_list, name, include)
     }
 
     fn build(self, el: &R::Element) -> Self::State {
@@ -187,21 +207,36 @@ where
         if include {
             R::add_class(&class_list, name);
         }
-        (class_list, self.1)
+        (class_list, name, include)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         let (name, include) = self;
-        let (class_list, prev_include) = state;
-        if include != *prev_include {
+        let (class_list, prev_name, prev_include) = state;
+        if name != *prev_name {
+            if *prev_include {
+                R::remove_class(class_list, prev_name);
+            }
+            if include {
+                R::add_class(class_list, name);
+            }
+        } else if include != *prev_include {
             if include {
                 R::add_class(class_list, name);
             } else {
                 R::remove_class(class_list, name);
             }
         }
+        *prev_name = name;
         *prev_include = include;
     }
+
+    fn remove(state: Self::State, _el: &R::Element) {
+        let (class_list, name, include) = state;
+        if include {
+            R::remove_class(&class_list, name);
+        }
+    }
 }
 
 #[cfg(feature = "nightly")]
@@ -230,6 +265,19 @@ where
     }
 
     fn rebuild(self, _state: &mut Self::State) {}
+
+    fn remove(_state: Self::State, el: &R::Element) {
+        remove_classes::<R>(el, V);
+    }
+}
+
+// a plain class string replaces the whole `class` attribute, but only its own
+// classes are taken away again, so that others added since are kept
+fn remove_classes<R: DomRenderer>(el: &R::Element, classes: &str) {
+    let class_list = R::class_list(el);
+    for name in classes.split_whitespace() {
+        R::remove_class(&class_list, name);
+    }
 }
 
 /* #[cfg(test)]
@@ -5,7 +5,13 @@ use crate::{
 };
 use std::{borrow::Cow, fmt::Debug, marker::PhantomData, rc::Rc, sync::Arc};
 
-// FIXME custom element HTML rendering is broken because tag names aren't static
+/// Creates a custom element, such as a web component.
+///
+/// If the tag is only known at runtime, the element can still be used in a
+/// template: the template contains a placeholder that is swapped for the
+/// real element when the template is cloned. Use a type that implements
+/// [`CustomElementKey`] with a non-empty `KEY`, or declare one with
+/// [`custom_element!`](crate::custom_element), to avoid this.
 pub fn custom<E, Rndr>(tag: E) -> HtmlElement<Custom<E>, (), (), Rndr>
 where
     E: CustomElementKey,
@@ -53,7 +59,10 @@ where
     }
 }
 
-// TODO these are all broken for custom elements
+/// The tag name of a custom element.
+///
+/// `KEY` is the tag name if it is known at compile time, or an empty string
+/// if it is only known at runtime.
 pub trait CustomElementKey: AsRef<str> {
     const KEY: &'static str;
 }
@@ -88,3 +97,135 @@ impl<const K: &'static str> CustomElementKey
 {
     const KEY: &'static str = K;
 }
+
+/// Declares a typed custom element, for wrapping a web component.
+///
+/// This creates a unit struct that is the element's [`CustomElementKey`], a
+/// function that creates the element, and a `{Struct}Attributes` trait with a
+/// method for each of the element's attributes and properties.
+///
+/// Attributes accept any [`AttributeValue`](crate::html::attribute::AttributeValue),
+/// while each property accepts the type it is declared with.
+///
+/// ```rust,ignore
+/// custom_element! {
+///     /// A button from a component library.
+///     pub sl_button SlButton = "sl-button" {
+///         attrs { variant = "variant", size = "size" }
+///         props { loading: bool = "loading" }
+///     }
+/// }
+///
+/// let button = sl_button::<Dom>()
+///     .variant("primary")
+///     .loading(true)
+///     .child("Save");
+/// ```
+#[macro_export]
+macro_rules! custom_element {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $fn_name:ident $ty:ident = $tag:literal {
+            attrs { $($attr:ident = $attr_name:literal),* $(,)? }
+            props { $($prop:ident: $prop_ty:ty = $prop_name:literal),* $(,)? }
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+        $vis struct $ty;
+
+        impl ::core::convert::AsRef<str> for $ty {
+            fn as_ref(&self) -> &str {
+                $tag
+            }
+        }
+
+        impl $crate::html::element::CustomElementKey for $ty {
+            const KEY: &'static str = $tag;
+        }
+
+        $(#[$meta])*
+        $vis fn $fn_name<Rndr>() -> $crate::html::element::HtmlElement<
+            $crate::html::element::Custom<$ty>,
+            (),
+            (),
+            Rndr,
+        >
+        where
+            Rndr: $crate::renderer::Renderer,
+        {
+            $crate::html::element::custom($ty)
+        }
+
+        $crate::paste::paste! {
+            #[doc = concat!("Attributes and properties of `<", $tag, ">`.")]
+            $vis trait [<$ty Attributes>]<Rndr>: Sized
+            where
+                Rndr: $crate::renderer::DomRenderer,
+            {
+                $(
+                    #[doc = concat!("The `", $attr_name, "` attribute.")]
+                    fn $attr<V>(
+                        self,
+                        value: V,
+                    ) -> <Self as $crate::html::attribute::global::AddAttribute<
+                        $crate::html::attribute::custom::CustomAttr<&'static str, V, Rndr>,
+                        Rndr,
+                    >>::Output
+                    where
+                        V: $crate::html::attribute::AttributeValue<Rndr>,
+                        Self: $crate::html::attribute::global::AddAttribute<
+                            $crate::html::attribute::custom::CustomAttr<&'static str, V, Rndr>,
+                            Rndr,
+                        >,
+                    {
+                        $crate::html::attribute::global::AddAttribute::add_attr(
+                            self,
+                            $crate::html::attribute::custom::custom_attribute(
+                                $attr_name,
+                                value,
+                            ),
+                        )
+                    }
+                )*
+
+                $(
+                    #[doc = concat!("The `", $prop_name, "` property.")]
+                    fn $prop(
+                        self,
+                        value: $prop_ty,
+                    ) -> <Self as $crate::html::attribute::global::AddAttribute<
+                        $crate::html::property::Property<&'static str, $prop_ty, Rndr>,
+                        Rndr,
+                    >>::Output
+                    where
+                        $prop_ty: $crate::html::property::IntoProperty<Rndr>,
+                        Self: $crate::html::attribute::global::AddAttribute<
+                            $crate::html::property::Property<&'static str, $prop_ty, Rndr>,
+                            Rndr,
+                        >,
+                    {
+                        $crate::html::attribute::global::AddAttribute::add_attr(
+                            self,
+                            $crate::html::property::property($prop_name, value),
+                        )
+                    }
+                )*
+            }
+
+            impl<At, Ch, Rndr> [<$ty Attributes>]<Rndr>
+                for $crate::html::element::HtmlElement<
+                    $crate::html::element::Custom<$ty>,
+                    At,
+                    Ch,
+                    Rndr,
+                >
+            where
+                At: $crate::html::attribute::Attribute<Rndr>,
+                Ch: $crate::view::Render<Rndr>,
+                Rndr: $crate::renderer::DomRenderer,
+            {
+            }
+        }
+    };
+}
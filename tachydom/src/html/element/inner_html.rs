@@ -1,7 +1,6 @@
 use super::{ElementWithChildren, HtmlElement};
 use crate::{
     html::{attribute::Attribute, element::AddAttribute},
-    prelude::Render,
     renderer::{DomRenderer, Renderer},
 };
 use std::marker::PhantomData;
@@ -67,6 +66,10 @@ where
             *prev = self.value;
         }
     }
+
+    fn remove(_state: Self::State, el: &R::Element) {
+        R::set_inner_html(el, "");
+    }
 }
 
 pub trait InnerHtmlAttribute<T, Rndr>
@@ -1,7 +1,7 @@
 use crate::{
     html::attribute::Attribute,
     hydration::Cursor,
-    renderer::{CastFrom, Renderer},
+    renderer::Renderer,
     ssr::StreamBuilder,
     view::{
         FallibleRender, Mountable, Position, PositionState, Render, RenderHtml,
@@ -162,7 +162,7 @@ where
         let el = Rndr::create_element(self.tag);
         let attrs = self.attributes.build(&el);
         let mut children = self.children.build();
-        children.mount(&el, None);
+        children.mount(el.as_ref(), None);
         ElementState {
             el,
             attrs,
@@ -186,7 +186,7 @@ where
         let el = Rndr::create_element(self.tag);
         let attrs = self.attributes.build(&el);
         let mut children = self.children.try_build()?;
-        children.mount(&el, None);
+        children.mount(el.as_ref(), None);
         Ok(ElementState {
             el,
             attrs,
@@ -233,7 +233,7 @@ where
     fn to_html_with_buf(self, buf: &mut String, position: &mut Position) {
         // opening tag
         buf.push('<');
-        buf.push_str(E::TAG);
+        buf.push_str(self.tag.tag());
 
         // attributes
 
@@ -280,7 +280,7 @@ where
 
             // closing tag
             buf.push_str("</");
-            buf.push_str(E::TAG);
+            buf.push_str(self.tag.tag());
             buf.push('>');
         }
         *position = Position::NextChild;
@@ -296,7 +296,7 @@ where
         let mut buf = String::with_capacity(Self::MIN_LENGTH);
         // opening tag
         buf.push('<');
-        buf.push_str(E::TAG);
+        buf.push_str(self.tag.tag());
 
         // attributes
 
@@ -348,9 +348,10 @@ where
             }
 
             // closing tag
-            let mut buf = String::with_capacity(3 + E::TAG.len());
+            let tag = self.tag.tag();
+            let mut buf = String::with_capacity(3 + tag.len());
             buf.push_str("</");
-            buf.push_str(E::TAG);
+            buf.push_str(tag);
             buf.push('>');
             buffer.push_sync(&buf);
         }
@@ -362,11 +363,9 @@ where
         cursor: &Cursor<Rndr>,
         position: &PositionState,
     ) -> Self::State {
-        // non-Static custom elements need special support in templates
-        // because they haven't been inserted type-wise
-        if E::TAG.is_empty() && !FROM_SERVER {
-            todo!()
-        }
+        // custom elements whose tag is only known at runtime can't be part of
+        // a template, so the template contains a placeholder element instead
+        let from_placeholder = E::TAG.is_empty() && !FROM_SERVER;
 
         let curr_position = position.get();
         if curr_position == Position::FirstChild {
@@ -374,13 +373,63 @@ where
         } else if curr_position != Position::Current {
             cursor.sibling();
         }
-        let el = Rndr::Element::cast_from(cursor.current()).unwrap();
+        let tag = if from_placeholder {
+            CUSTOM_ELEMENT_PLACEHOLDER
+        } else {
+            self.tag.tag()
+        };
+        let el = match cursor.element(tag, curr_position) {
+            Ok(el) => el,
+            Err(mismatch) => {
+                let state = self.build();
+                cursor.replace_mismatched(mismatch, state.el.as_ref());
+                position.set(Position::NextChild);
+                return state;
+            }
+        };
 
-        let attrs = self.attributes.hydrate::<FROM_SERVER>(&el);
+        let HtmlElement {
+            tag,
+            attributes,
+            children,
+            ..
+        } = self;
+        let (el, placeholder) = if from_placeholder {
+            (Rndr::create_element(tag), Some(el))
+        } else {
+            (el, None)
+        };
+        let attrs = if placeholder.is_some() {
+            attributes.build(&el)
+        } else {
+            attributes.hydrate::<FROM_SERVER>(&el)
+        };
 
         // hydrate children
         position.set(Position::FirstChild);
-        let children = self.children.hydrate::<FROM_SERVER>(cursor, position);
+        cursor.enter(if E::TAG.is_empty() {
+            "custom element"
+        } else {
+            E::TAG
+        });
+        let children = children.hydrate::<FROM_SERVER>(cursor, position);
+        cursor.exit();
+
+        // move the children out of the placeholder, and swap it for the
+        // real element
+        if let Some(placeholder) = placeholder {
+            while let Some(child) = Rndr::first_child(placeholder.as_ref()) {
+                Rndr::insert_node(el.as_ref(), &child, None);
+            }
+            if let Some(parent) = Rndr::get_parent(placeholder.as_ref()) {
+                Rndr::insert_node(
+                    &parent,
+                    el.as_ref(),
+                    Some(placeholder.as_ref()),
+                );
+                Rndr::remove_node(&parent, placeholder.as_ref());
+            }
+        }
         cursor.set(el.as_ref().clone());
 
         // go to next sibling
@@ -395,6 +444,19 @@ where
     }
 }
 
+/// Stands in for custom elements whose tag is only known at runtime in
+/// templates, and is replaced by the real element when the template is
+/// hydrated.
+const CUSTOM_ELEMENT_PLACEHOLDER: &str = "tachy-custom-element";
+
+const fn template_tag(tag: &'static str) -> &'static str {
+    if tag.is_empty() {
+        CUSTOM_ELEMENT_PLACEHOLDER
+    } else {
+        tag
+    }
+}
+
 pub struct ElementState<At, Ch, R: Renderer> {
     pub el: R::Element,
     pub attrs: At,
@@ -410,13 +472,13 @@ where
         R::remove(self.el.as_ref());
     }
 
-    fn mount(&mut self, parent: &R::Element, marker: Option<&R::Node>) {
+    fn mount(&mut self, parent: &R::Node, marker: Option<&R::Node>) {
         R::insert_node(parent, self.el.as_ref(), marker);
     }
 
     fn insert_before_this(
         &self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool {
         child.mount(parent, Some(self.el.as_ref()));
@@ -433,7 +495,7 @@ where
 {
     const TEMPLATE: &'static str = str_from_buffer(&const_concat(&[
         "<",
-        E::TAG,
+        template_tag(E::TAG),
         At::TEMPLATE,
         str_from_buffer(&const_concat_with_prefix(
             &[At::CLASS],
@@ -448,7 +510,7 @@ where
         ">",
         Ch::TEMPLATE,
         "</",
-        E::TAG,
+        template_tag(E::TAG),
         ">",
     ]));
 
@@ -460,70 +522,61 @@ where
         inner_html: &mut String,
         position: &mut Position,
     ) {
-        // for custom elements without type known at compile time, do nothing
-        if !E::TAG.is_empty() {
-            // opening tag and attributes
-            let mut class = String::new();
-            let mut style = String::new();
-            let mut inner_html = String::new();
-
-            buf.push('<');
-            buf.push_str(E::TAG);
-            <At as ToTemplate>::to_template(
-                buf,
-                &mut class,
-                &mut style,
-                &mut inner_html,
-                position,
-            );
-
-            if !class.is_empty() {
-                buf.push(' ');
-                buf.push_str("class=\"");
-                buf.push_str(class.trim_start().trim_end());
-                buf.push('"');
-            }
-            if !style.is_empty() {
-                buf.push(' ');
-                buf.push_str("style=\"");
-                buf.push_str(style.trim_start().trim_end());
-                buf.push('"');
-            }
-            buf.push('>');
+        let tag = template_tag(E::TAG);
+        // opening tag and attributes
+        let mut class = String::new();
+        let mut style = String::new();
+        let mut inner_html = String::new();
 
-            // children
-            *position = Position::FirstChild;
-            class.clear();
-            style.clear();
-            inner_html.clear();
-            Ch::to_template(
-                buf,
-                &mut class,
-                &mut style,
-                &mut inner_html,
-                position,
-            );
+        buf.push('<');
+        buf.push_str(tag);
+        <At as ToTemplate>::to_template(
+            buf,
+            &mut class,
+            &mut style,
+            &mut inner_html,
+            position,
+        );
 
-            // closing tag
-            buf.push_str("</");
-            buf.push_str(E::TAG);
-            buf.push('>');
-            *position = Position::NextChild;
+        if !class.is_empty() {
+            buf.push(' ');
+            buf.push_str("class=\"");
+            buf.push_str(class.trim_start().trim_end());
+            buf.push('"');
         }
+        if !style.is_empty() {
+            buf.push(' ');
+            buf.push_str("style=\"");
+            buf.push_str(style.trim_start().trim_end());
+            buf.push('"');
+        }
+        buf.push('>');
+
+        // children
+        *position = Position::FirstChild;
+        class.clear();
+        style.clear();
+        inner_html.clear();
+        Ch::to_template(buf, &mut class, &mut style, &mut inner_html, position);
+
+        // closing tag
+        buf.push_str("</");
+        buf.push_str(tag);
+        buf.push('>');
+        *position = Position::NextChild;
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{main, p, HtmlElement};
+    use super::{custom, main, p, Custom, Div, HtmlElement};
     use crate::{
         html::{
-            attribute::{global::GlobalAttributes, id, src},
-            class::class,
+            attribute::{custom::CustomAttribute, global::GlobalAttributes},
             element::{em, ElementChild, Main},
         },
-        renderer::mock_dom::MockDom,
-        view::{Render, RenderHtml},
+        renderer::{mock_dom::MockDom, Renderer},
+        view::{Render, RenderHtml, ToTemplate},
     };
 
     #[test]
@@ -551,6 +604,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn custom_element_renders_runtime_tag() {
+        let el = main::<MockDom>()
+            .child(custom("my-element").attr("size", "large").child("Hi"));
+        assert_eq!(
+            el.to_html(),
+            "<main><my-element size=\"large\">Hi</my-element></main>"
+        );
+    }
+
+    #[test]
+    fn custom_element_with_runtime_tag_uses_placeholder_in_template() {
+        type View = HtmlElement<
+            Main,
+            (),
+            (HtmlElement<Custom<String>, (), (), MockDom>,),
+            MockDom,
+        >;
+        assert_eq!(
+            View::TEMPLATE,
+            "<main><tachy-custom-element></tachy-custom-element></main>"
+        );
+    }
+
+    #[test]
+    fn custom_element_replaces_placeholder_when_template_is_hydrated() {
+        // builds the contents of a cloned template
+        let root = MockDom::create_element(Div);
+        let placeholder = MockDom::create_element(
+            custom::<_, MockDom>("tachy-custom-element").tag,
+        );
+        let text = MockDom::create_text_node("Hi");
+        MockDom::insert_node(&root, placeholder.as_ref(), None);
+        MockDom::insert_node(&placeholder, text.as_ref(), None);
+
+        let view = custom::<_, MockDom>(String::from("my-element"))
+            .attr("size", "large")
+            .child("Hi");
+        let state = view.hydrate_from::<false>(&root);
+        assert_eq!(
+            root.to_debug_html(),
+            "<div><my-element size=\"large\">Hi</my-element></div>"
+        );
+        assert_eq!(state.el.tag(), "my-element");
+    }
+
+    crate::custom_element! {
+        /// A button from a component library.
+        fancy_button FancyButton = "fancy-button" {
+            attrs { variant = "variant" }
+            props { loading: bool = "loading" }
+        }
+    }
+
+    #[test]
+    fn custom_element_macro_declares_typed_element() {
+        let el = fancy_button::<MockDom>()
+            .variant("primary")
+            .loading(true)
+            .child("Save");
+        assert_eq!(
+            <HtmlElement<Custom<FancyButton>, (), (), MockDom>>::TEMPLATE,
+            "<fancy-button></fancy-button>"
+        );
+        let state = el.build();
+        assert_eq!(
+            state.el.to_debug_html(),
+            "<fancy-button variant=\"primary\">Save</fancy-button>"
+        );
+    }
+
     #[cfg(feature = "nightly")]
     #[test]
     fn html_render_allocates_appropriate_buffer() {
@@ -11,17 +11,46 @@ where
     E: EventDescriptor + 'static,
     E::EventType: 'static,
     R: DomRenderer,
+    R::Element: Clone + 'static,
     E::EventType: From<R::Event>,
 {
     On {
         name: event.name(),
         setup: Box::new(move |el| {
-            let cb = Box::new(move |ev: R::Event| {
-                let specific_event = ev.into();
-                cb(specific_event);
+            let modifiers = event.modifiers();
+            let cb = Box::new({
+                let el = el.clone();
+                move |ev: R::Event| {
+                    if modifiers.self_only && !R::is_event_target(&ev, &el) {
+                        return;
+                    }
+                    if let Some(key) = modifiers.key {
+                        if R::event_key(&ev).as_deref() != Some(key) {
+                            return;
+                        }
+                    }
+                    if modifiers.prevent {
+                        R::prevent_default(&ev);
+                    }
+                    if modifiers.stop {
+                        R::stop_propagation(&ev);
+                    }
+                    cb(ev.into());
+                }
             }) as Box<dyn FnMut(R::Event)>;
 
-            if E::BUBBLES && cfg!(feature = "delegation") {
+            // listeners with options can't share the global handler, so
+            // they are always added to the element itself
+            let options = event.options().as_ref();
+            if options.is_some() || modifiers.needs_options() {
+                R::add_event_listener_with_options(
+                    el,
+                    &event.name(),
+                    options,
+                    &modifiers,
+                    cb,
+                )
+            } else if E::BUBBLES && cfg!(feature = "delegation") {
                 R::add_event_listener_delegated(
                     el,
                     event.name(),
@@ -90,6 +119,13 @@ where
         }
         *prev_cleanup = Some((self.setup)(el));
     }
+
+    fn remove(state: Self::State, _el: &R::Element) {
+        let (el, cleanup) = state;
+        if let Some(cleanup) = cleanup {
+            cleanup(&el);
+        }
+    }
 }
 
 impl<R> ToTemplate for On<R>
@@ -125,12 +161,102 @@ pub trait EventDescriptor: Clone {
     /// The key used for event delegation.
     fn event_delegation_key(&self) -> Cow<'static, str>;
 
-    /// Return the options for this type. This is only used when you create a [`Custom`] event
-    /// handler.
+    /// Return the options for this type, such as `passive`, `once` or
+    /// `capture`.
+    ///
+    /// An event with options is never delegated, and its listener is added
+    /// to the element itself with these options, combined with any
+    /// [`Modifiers`].
     #[inline(always)]
     fn options(&self) -> &Option<web_sys::AddEventListenerOptions> {
         &None
     }
+
+    /// The [`Modifiers`] that change how the listener for this event is
+    /// added and called.
+    #[inline(always)]
+    fn modifiers(&self) -> Modifiers {
+        Modifiers::default()
+    }
+}
+
+/// Modifiers that change how an event listener is added or called, as in
+/// `on:click:prevent:once` in the `view` macro.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    /// Calls `preventDefault()` before the handler.
+    pub prevent: bool,
+    /// Calls `stopPropagation()` before the handler.
+    pub stop: bool,
+    /// Only calls the handler if the event was dispatched on the element
+    /// itself, rather than on one of its children.
+    pub self_only: bool,
+    /// Removes the listener after the first event.
+    pub once: bool,
+    /// Calls the handler during the capture phase.
+    pub capture: bool,
+    /// Promises that the handler won't call `preventDefault()`.
+    pub passive: bool,
+    /// Only calls the handler for keyboard events with this
+    /// [`key`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key),
+    /// such as `"Enter"` or `"Escape"`.
+    pub key: Option<&'static str>,
+}
+
+impl Modifiers {
+    /// Whether the listener needs the `once`, `capture` or `passive`
+    /// options.
+    pub fn needs_options(&self) -> bool {
+        self.once || self.capture || self.passive
+    }
+}
+
+/// An event with [`Modifiers`].
+///
+/// `prevent`, `stop`, `self_only` and `key` are applied before the handler
+/// is called, so they work whether or not the event is delegated. `once`,
+/// `capture` and `passive` are listener options, so an event with any of
+/// them is never delegated.
+#[derive(Copy, Clone, Debug)]
+pub struct Modified<E> {
+    event: E,
+    modifiers: Modifiers,
+}
+
+impl<E> Modified<E> {
+    /// Adds the modifiers to an event.
+    pub fn new(event: E, modifiers: Modifiers) -> Self {
+        Self { event, modifiers }
+    }
+}
+
+impl<E> EventDescriptor for Modified<E>
+where
+    E: EventDescriptor,
+{
+    type EventType = E::EventType;
+
+    const BUBBLES: bool = E::BUBBLES;
+
+    #[inline(always)]
+    fn name(&self) -> Cow<'static, str> {
+        self.event.name()
+    }
+
+    #[inline(always)]
+    fn event_delegation_key(&self) -> Cow<'static, str> {
+        self.event.event_delegation_key()
+    }
+
+    #[inline(always)]
+    fn options(&self) -> &Option<web_sys::AddEventListenerOptions> {
+        self.event.options()
+    }
+
+    #[inline(always)]
+    fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
 }
 
 macro_rules! generate_event_types {
@@ -385,3 +511,219 @@ pub use web_sys::{
     StorageEvent, SubmitEvent, TouchEvent, TransitionEvent, UiEvent,
     WheelEvent,
 };
+
+#[cfg(test)]
+mod tests {
+    use super::{click, keydown, on, Modified, Modifiers};
+    use crate::{
+        html::{
+            attribute::global::{AttrsAttribute, OnAttribute},
+            element::{button, input, main, ElementChild},
+        },
+        renderer::mock_dom::{document, MockDom},
+        view::Render,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    fn counter() -> (Rc<Cell<usize>>, impl FnMut(web_sys::MouseEvent)) {
+        let count = Rc::new(Cell::new(0));
+        (Rc::clone(&count), move |_| count.set(count.get() + 1))
+    }
+
+    #[test]
+    fn prevent_prevents_default() {
+        let (clicks, on_click) = counter();
+        let root = button::<MockDom>()
+            .on(
+                Modified::new(
+                    click,
+                    Modifiers {
+                        prevent: true,
+                        ..Default::default()
+                    },
+                ),
+                on_click,
+            )
+            .build()
+            .el;
+        assert!(!document().dispatch_event(&root, "click", true));
+        assert_eq!(clicks.get(), 1);
+
+        let plain = button::<MockDom>().on(click, |_| {}).build().el;
+        assert!(document().dispatch_event(&plain, "click", true));
+    }
+
+    #[test]
+    fn stop_keeps_event_from_ancestors() {
+        let (outer_clicks, on_outer_click) = counter();
+        let (inner_clicks, on_inner_click) = counter();
+        let root = main::<MockDom>()
+            .on(click, on_outer_click)
+            .child(button().on(
+                Modified::new(
+                    click,
+                    Modifiers {
+                        stop: true,
+                        ..Default::default()
+                    },
+                ),
+                on_inner_click,
+            ))
+            .build()
+            .el;
+        document().dispatch_event(&root.children()[0], "click", true);
+        assert_eq!(inner_clicks.get(), 1);
+        assert_eq!(outer_clicks.get(), 0);
+    }
+
+    #[test]
+    fn self_only_ignores_events_from_children() {
+        let (clicks, on_click) = counter();
+        let root = main::<MockDom>()
+            .on(
+                Modified::new(
+                    click,
+                    Modifiers {
+                        self_only: true,
+                        ..Default::default()
+                    },
+                ),
+                on_click,
+            )
+            .child(button())
+            .build()
+            .el;
+        document().dispatch_event(&root.children()[0], "click", true);
+        assert_eq!(clicks.get(), 0);
+        document().dispatch_event(&root, "click", true);
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn once_only_handles_first_event() {
+        let (clicks, on_click) = counter();
+        let root = button::<MockDom>()
+            .on(
+                Modified::new(
+                    click,
+                    Modifiers {
+                        once: true,
+                        ..Default::default()
+                    },
+                ),
+                on_click,
+            )
+            .build()
+            .el;
+        document().dispatch_event(&root, "click", true);
+        document().dispatch_event(&root, "click", true);
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn key_filters_keyboard_events() {
+        let presses = Rc::new(Cell::new(0));
+        let root = input::<MockDom>()
+            .on(
+                Modified::new(
+                    keydown,
+                    Modifiers {
+                        key: Some("Enter"),
+                        ..Default::default()
+                    },
+                ),
+                {
+                    let presses = Rc::clone(&presses);
+                    move |_| presses.set(presses.get() + 1)
+                },
+            )
+            .build()
+            .el;
+        document().dispatch_key_event(&root, "keydown", "Escape");
+        assert_eq!(presses.get(), 0);
+        document().dispatch_key_event(&root, "keydown", "Enter");
+        assert_eq!(presses.get(), 1);
+        // events that aren't keyboard events have no key
+        document().dispatch_event(&root, "keydown", true);
+        assert_eq!(presses.get(), 1);
+    }
+
+    #[cfg(feature = "delegation")]
+    #[test]
+    fn events_with_options_are_not_delegated() {
+        use super::EventDescriptor;
+        use std::borrow::Cow;
+        use wasm_bindgen::{JsCast, JsValue};
+
+        // a `click` with listener options of its own, rather than modifiers
+        #[derive(Clone)]
+        struct ClickWithOptions(Option<web_sys::AddEventListenerOptions>);
+
+        impl EventDescriptor for ClickWithOptions {
+            type EventType = web_sys::MouseEvent;
+
+            const BUBBLES: bool = true;
+
+            fn name(&self) -> Cow<'static, str> {
+                click.name()
+            }
+
+            fn event_delegation_key(&self) -> Cow<'static, str> {
+                click.event_delegation_key()
+            }
+
+            fn options(&self) -> &Option<web_sys::AddEventListenerOptions> {
+                &self.0
+            }
+        }
+
+        // the parent's listener stops propagation, so only listeners added
+        // to the button itself run; delegated listeners run afterwards
+        let view = |options: Option<web_sys::AddEventListenerOptions>| {
+            let (clicks, on_click) = counter();
+            let root = main::<MockDom>()
+                .on(
+                    Modified::new(
+                        click,
+                        Modifiers {
+                            stop: true,
+                            passive: true,
+                            ..Default::default()
+                        },
+                    ),
+                    |_| {},
+                )
+                .child(button().on(ClickWithOptions(options), on_click))
+                .build()
+                .el;
+            document().dispatch_event(&root.children()[0], "click", true);
+            clicks.get()
+        };
+
+        assert_eq!(view(None), 0);
+        // options can only be created in the browser, but they aren't read
+        // outside of it either
+        let options = JsValue::UNDEFINED.unchecked_into();
+        assert_eq!(view(Some(options)), 1);
+    }
+
+    #[test]
+    fn optional_listener_is_removed_when_none() {
+        let clicks = Rc::new(Cell::new(0));
+        let view = |listen: bool| {
+            let clicks = Rc::clone(&clicks);
+            button::<MockDom>().attrs(
+                listen
+                    .then(|| on(click, move |_| clicks.set(clicks.get() + 1))),
+            )
+        };
+
+        let mut state = view(true).build();
+        document().dispatch_event(&state.el, "click", true);
+        assert_eq!(clicks.get(), 1);
+
+        view(false).rebuild(&mut state);
+        document().dispatch_event(&state.el, "click", true);
+        assert_eq!(clicks.get(), 1);
+    }
+}
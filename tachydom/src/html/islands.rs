@@ -1,38 +1,112 @@
 use crate::{
+    html::element::{custom, CreateElement, Custom},
     hydration::Cursor,
     prelude::{Render, RenderHtml},
-    renderer::Renderer,
+    renderer::{DomRenderer, Renderer},
     ssr::StreamBuilder,
-    view::{Position, PositionState},
+    view::{Mountable, Position, PositionState},
 };
 use std::marker::PhantomData;
 
-// TODO serialized props, too
 pub struct Island<Rndr, View> {
     component: &'static str,
+    trigger: HydrationTrigger,
+    props: Vec<(&'static str, String)>,
     view: View,
     rndr: PhantomData<Rndr>,
 }
 const ISLAND_TAG: &'static str = "leptos-island";
 const ISLAND_CHILDREN_TAG: &'static str = "leptos-children";
+const PROP_ATTRIBUTE_PREFIX: &str = "data-prop-";
+
+/// When an island is hydrated, once the WASM binary has loaded.
+///
+/// The island's HTML is left as it was rendered on the server until then.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum HydrationTrigger {
+    /// As soon as the WASM binary has loaded.
+    #[default]
+    Load,
+    /// When the browser is idle.
+    Idle,
+    /// When the island first scrolls into view.
+    Visible,
+    /// When the user first points at or focuses something in the island.
+    Interaction,
+    /// When the media query first matches.
+    Media(&'static str),
+}
+
+impl HydrationTrigger {
+    fn name(&self) -> &'static str {
+        match self {
+            HydrationTrigger::Load => "load",
+            HydrationTrigger::Idle => "idle",
+            HydrationTrigger::Visible => "visible",
+            HydrationTrigger::Interaction => "interaction",
+            HydrationTrigger::Media(_) => "media",
+        }
+    }
+}
+
+/// The name of the attribute on the island's tag that holds the serialized
+/// prop `name`.
+pub fn prop_attribute(name: &str) -> String {
+    format!("{PROP_ATTRIBUTE_PREFIX}{name}")
+}
 
 impl<Rndr, View> Island<Rndr, View> {
     pub fn new(component: &'static str, view: View) -> Self {
         Island {
             component,
+            trigger: HydrationTrigger::Load,
+            props: Vec::new(),
             view,
             rndr: PhantomData,
         }
     }
 
-    fn open_tag(component: &'static str, buf: &mut String) {
+    /// Adds the serialized props of the island, as pairs of each prop's name
+    /// and its serialized value, which are rendered as attributes of the
+    /// island's tag so the island can be hydrated with them.
+    pub fn with_props(mut self, props: Vec<(&'static str, String)>) -> Self {
+        self.props = props;
+        self
+    }
+
+    /// Sets when the island is hydrated.
+    pub fn with_trigger(mut self, trigger: HydrationTrigger) -> Self {
+        self.trigger = trigger;
+        self
+    }
+
+    fn open_tag(&self, buf: &mut String) {
         buf.push('<');
         buf.push_str(ISLAND_TAG);
         buf.push(' ');
         buf.push_str("data-component=\"");
-        buf.push_str(component);
-        buf.push_str("\">");
-        // TODO insert serialized props
+        buf.push_str(self.component);
+        buf.push('"');
+        // islands without a trigger are hydrated as soon as possible
+        if self.trigger != HydrationTrigger::Load {
+            buf.push_str(" data-hydrate=\"");
+            buf.push_str(self.trigger.name());
+            buf.push('"');
+        }
+        if let HydrationTrigger::Media(query) = self.trigger {
+            buf.push_str(" data-hydrate-media=\"");
+            escape_prop(query, buf);
+            buf.push('"');
+        }
+        for (name, value) in &self.props {
+            buf.push(' ');
+            buf.push_str(PROP_ATTRIBUTE_PREFIX);
+            buf.push_str(name);
+            buf.push_str("=\"");
+            escape_prop(value, buf);
+            buf.push('"');
+        }
+        buf.push('>');
     }
 
     fn close_tag(buf: &mut String) {
@@ -72,9 +146,10 @@ where
         + View::MIN_LENGTH;
 
     fn to_html_with_buf(self, buf: &mut String, position: &mut Position) {
-        Self::open_tag(self.component, buf);
-        self.view.to_html_with_buf(buf, position);
+        self.open_tag(buf);
+        self.view.to_html_with_buf(buf, &mut Position::FirstChild);
         Self::close_tag(buf);
+        *position = Position::NextChild;
     }
 
     fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
@@ -86,17 +161,20 @@ where
     {
         // insert the opening tag synchronously
         let mut tag = String::new();
-        Self::open_tag(self.component, &mut tag);
+        self.open_tag(&mut tag);
         buf.push_sync(&tag);
 
         // streaming render for the view
-        self.view
-            .to_html_async_with_buf::<OUT_OF_ORDER>(buf, position);
+        self.view.to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            &mut Position::FirstChild,
+        );
 
         // and insert the closing tag synchronously
         tag.clear();
         Self::close_tag(&mut tag);
         buf.push_sync(&tag);
+        *position = Position::NextChild;
     }
 
     fn hydrate<const FROM_SERVER: bool>(
@@ -104,8 +182,33 @@ where
         cursor: &Cursor<Rndr>,
         position: &PositionState,
     ) -> Self::State {
+        // an island is either hydrated by its own entry point, with the
+        // cursor already on its tag, or as part of the view of another
+        // island that contains it
+        let curr_position = position.get();
+        if curr_position == Position::FirstChild {
+            cursor.child();
+        } else if curr_position != Position::Current {
+            cursor.sibling();
+        }
+        let island = cursor.current();
+
         position.set(Position::FirstChild);
-        self.view.hydrate::<FROM_SERVER>(cursor, position)
+        let state = self.view.hydrate::<FROM_SERVER>(cursor, position);
+
+        cursor.set(island);
+        position.set(Position::NextChild);
+        state
+    }
+}
+
+fn escape_prop(value: &str, buf: &mut String) {
+    for ch in value.chars() {
+        match ch {
+            '&' => buf.push_str("&amp;"),
+            '"' => buf.push_str("&quot;"),
+            _ => buf.push(ch),
+        }
     }
 }
 
@@ -135,16 +238,88 @@ impl<Rndr, View> IslandChildren<Rndr, View> {
     }
 }
 
+/// The `<leptos-children>` element that holds the children of an island.
+///
+/// The children are only rendered on the server, and are left as they are
+/// in the browser, except for any islands inside them, which are hydrated
+/// on their own. When the page changes, they can be replaced with HTML
+/// rendered by the server with [`set_html`](IslandChildrenState::set_html).
+pub struct IslandChildrenState<Rndr>
+where
+    Rndr: Renderer,
+{
+    el: Rndr::Element,
+}
+
+impl<Rndr> IslandChildrenState<Rndr>
+where
+    Rndr: Renderer,
+{
+    /// The `<leptos-children>` element.
+    pub fn el(&self) -> &Rndr::Element {
+        &self.el
+    }
+}
+
+impl<Rndr> IslandChildrenState<Rndr>
+where
+    Rndr: DomRenderer,
+{
+    /// Replaces the children with a fragment of HTML rendered by the server.
+    ///
+    /// Any islands in the new HTML need to be hydrated again.
+    pub fn set_html(&self, html: &str) {
+        Rndr::set_inner_html(&self.el, html);
+    }
+}
+
+impl<Rndr> Mountable<Rndr> for IslandChildrenState<Rndr>
+where
+    Rndr: Renderer,
+{
+    fn unmount(&mut self) {
+        self.el.unmount();
+    }
+
+    fn mount(&mut self, parent: &Rndr::Node, marker: Option<&Rndr::Node>) {
+        self.el.mount(parent, marker);
+    }
+
+    fn insert_before_this(
+        &self,
+        parent: &Rndr::Node,
+        child: &mut dyn Mountable<Rndr>,
+    ) -> bool {
+        self.el.insert_before_this(parent, child)
+    }
+}
+
+fn create_children_el<Rndr>() -> Rndr::Element
+where
+    Rndr: Renderer,
+    Custom<&'static str>: CreateElement<Rndr>,
+{
+    Rndr::create_element(custom::<_, Rndr>(ISLAND_CHILDREN_TAG).tag)
+}
+
 impl<Rndr, View> Render<Rndr> for IslandChildren<Rndr, View>
 where
     View: Render<Rndr>,
     Rndr: Renderer,
+    Custom<&'static str>: CreateElement<Rndr>,
 {
-    type State = ();
+    type State = IslandChildrenState<Rndr>;
 
-    fn build(self) -> Self::State {}
+    // the children can't be rendered in the browser, so an island that is
+    // rendered there gets an empty element in their place
+    fn build(self) -> Self::State {
+        IslandChildrenState {
+            el: create_children_el::<Rndr>(),
+        }
+    }
 
-    fn rebuild(self, state: &mut Self::State) {}
+    // server-rendered children don't change in the browser
+    fn rebuild(self, _state: &mut Self::State) {}
 }
 
 impl<Rndr, View> RenderHtml<Rndr> for IslandChildren<Rndr, View>
@@ -153,6 +328,7 @@ where
     Rndr: Renderer,
     Rndr::Element: Clone,
     Rndr::Node: Clone,
+    Custom<&'static str>: CreateElement<Rndr>,
 {
     const MIN_LENGTH: usize = ISLAND_CHILDREN_TAG.len() * 2
         + "<>".len()
@@ -161,8 +337,9 @@ where
 
     fn to_html_with_buf(self, buf: &mut String, position: &mut Position) {
         Self::open_tag(buf);
-        self.view.to_html_with_buf(buf, position);
+        self.view.to_html_with_buf(buf, &mut Position::FirstChild);
         Self::close_tag(buf);
+        *position = Position::NextChild;
     }
 
     fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
@@ -178,13 +355,16 @@ where
         buf.push_sync(&tag);
 
         // streaming render for the view
-        self.view
-            .to_html_async_with_buf::<OUT_OF_ORDER>(buf, position);
+        self.view.to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            &mut Position::FirstChild,
+        );
 
         // and insert the closing tag synchronously
         tag.clear();
         Self::close_tag(&mut tag);
         buf.push_sync(&tag);
+        *position = Position::NextChild;
     }
 
     fn hydrate<const FROM_SERVER: bool>(
@@ -201,5 +381,131 @@ where
         } else if curr_position != Position::Current {
             cursor.sibling();
         }
+        let el = match cursor.element(ISLAND_CHILDREN_TAG, curr_position) {
+            Ok(el) => el,
+            Err(mismatch) => {
+                let el = create_children_el::<Rndr>();
+                cursor.replace_mismatched(mismatch, el.as_ref());
+                el
+            }
+        };
+        position.set(Position::NextChild);
+        IslandChildrenState { el }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HydrationTrigger, Island, IslandChildren};
+    use crate::{
+        html::element::{self, p, ElementChild},
+        renderer::{mock_dom::MockDom, Renderer},
+        view::RenderHtml,
+    };
+
+    #[test]
+    fn island_renders_serialized_props() {
+        let island =
+            Island::<MockDom, _>::new("Counter", "Count").with_props(vec![
+                ("count", "1".to_string()),
+                ("label", r#""a" & b"#.into()),
+            ]);
+        assert_eq!(
+            island.to_html(),
+            "<leptos-island data-component=\"Counter\" data-prop-count=\"1\" \
+             data-prop-label=\"&quot;a&quot; &amp; b\">Count</leptos-island>"
+        );
+    }
+
+    #[test]
+    fn island_renders_hydration_trigger() {
+        let island = Island::<MockDom, _>::new("Chart", ())
+            .with_trigger(HydrationTrigger::Visible);
+        assert_eq!(
+            island.to_html(),
+            "<leptos-island data-component=\"Chart\" \
+             data-hydrate=\"visible\"></leptos-island>"
+        );
+
+        let island = Island::<MockDom, _>::new("Menu", ())
+            .with_trigger(HydrationTrigger::Media("(min-width: 800px)"));
+        assert_eq!(
+            island.to_html(),
+            "<leptos-island data-component=\"Menu\" data-hydrate=\"media\" \
+             data-hydrate-media=\"(min-width: 800px)\"></leptos-island>"
+        );
+    }
+
+    #[test]
+    fn island_children_are_left_inert() {
+        let island = Island::<MockDom, _>::new(
+            "Outer",
+            (
+                p().child("Before"),
+                IslandChildren::new(p().child("Server only")),
+                p().child("After"),
+            ),
+        );
+        assert_eq!(
+            island.to_html(),
+            "<leptos-island data-component=\"Outer\"><p>Before</p>\
+             <leptos-children><p>Server only</p></leptos-children>\
+             <p>After</p></leptos-island>"
+        );
+
+        let parent = MockDom::create_element(element::Div);
+        let island = MockDom::create_element(
+            super::custom::<_, MockDom>(super::ISLAND_TAG).tag,
+        );
+        let before = MockDom::create_element(element::P);
+        let children = MockDom::create_element(
+            super::custom::<_, MockDom>(super::ISLAND_CHILDREN_TAG).tag,
+        );
+        let server_only = MockDom::create_element(element::P);
+        let after = MockDom::create_element(element::P);
+        MockDom::insert_node(&parent, island.as_ref(), None);
+        MockDom::insert_node(&island, before.as_ref(), None);
+        MockDom::insert_node(
+            &before,
+            MockDom::create_text_node("Before").as_ref(),
+            None,
+        );
+        MockDom::insert_node(&island, children.as_ref(), None);
+        MockDom::insert_node(&children, server_only.as_ref(), None);
+        MockDom::insert_node(
+            &server_only,
+            MockDom::create_text_node("Server only").as_ref(),
+            None,
+        );
+        MockDom::insert_node(&island, after.as_ref(), None);
+        MockDom::insert_node(
+            &after,
+            MockDom::create_text_node("After").as_ref(),
+            None,
+        );
+
+        let view = Island::<MockDom, _>::new(
+            "Outer",
+            (
+                p().child("Before"),
+                IslandChildren::new(p().child("Rendered on the client")),
+                p().child("After"),
+            ),
+        );
+        let (_, children_state, _) = view.hydrate_from_position::<true>(
+            &island,
+            crate::view::Position::Current,
+        );
+        // the children keep the server-rendered HTML
+        assert_eq!(
+            children.to_debug_html(),
+            "<leptos-children><p>Server only</p></leptos-children>"
+        );
+
+        children_state.set_html("<p>Next page</p>");
+        assert_eq!(
+            children.to_debug_html(),
+            "<leptos-children><p>Next page</p></leptos-children>"
+        );
     }
 }
@@ -11,6 +11,7 @@ pub mod event;
 pub mod islands;
 pub mod node_ref;
 pub mod property;
+pub mod shadow_root;
 pub mod style;
 
 pub struct Doctype<R: Renderer> {
@@ -93,13 +93,20 @@ pub trait IntoProperty<R: DomRenderer> {
 }
 
 macro_rules! prop_type {
+    (&$prop_type:ty) => {
+        prop_type!(@impl ['a] &'a $prop_type);
+    };
     ($prop_type:ty) => {
-        impl<R> IntoProperty<R> for $prop_type
+        prop_type!(@impl [] $prop_type);
+    };
+    (@impl [$($lt:lifetime)?] $prop_type:ty) => {
+        impl<$($lt,)? R> IntoProperty<R> for $prop_type
         where
             R: DomRenderer,
             R::Element: Clone,
+            R::Property: From<$prop_type>,
         {
-            type State = (R::Element, JsValue);
+            type State = (R::Element, R::Property);
 
             fn hydrate<const FROM_SERVER: bool>(
                 self,
@@ -0,0 +1,308 @@
+use crate::{
+    hydration::Cursor,
+    renderer::{CastFrom, DomRenderer, Renderer},
+    ssr::StreamBuilder,
+    view::{Mountable, Position, PositionState, Render, RenderHtml},
+};
+use std::marker::PhantomData;
+
+const OPEN_TAG: &str = "<template shadowrootmode=\"open\">";
+const CLOSE_TAG: &str = "</template>";
+
+/// Renders a view into an open shadow root attached to its parent element,
+/// encapsulating its DOM and any `<style>` elements inside it.
+///
+/// On the server, this is rendered as a declarative shadow root
+/// (`<template shadowrootmode="open">`), which the browser attaches to the
+/// parent element as it parses the HTML, and which is then hydrated.
+pub fn shadow_root<Rndr, View>(view: View) -> ShadowRoot<Rndr, View>
+where
+    Rndr: DomRenderer,
+    View: Render<Rndr>,
+{
+    ShadowRoot {
+        view,
+        rndr: PhantomData,
+    }
+}
+
+pub struct ShadowRoot<Rndr, View> {
+    view: View,
+    rndr: PhantomData<Rndr>,
+}
+
+pub struct ShadowRootState<Rndr, State>
+where
+    Rndr: Renderer,
+{
+    root: Option<Rndr::Node>,
+    state: State,
+}
+
+impl<Rndr, State> ShadowRootState<Rndr, State>
+where
+    Rndr: Renderer,
+{
+    /// The shadow root, once this has been mounted or hydrated.
+    pub fn root(&self) -> Option<&Rndr::Node> {
+        self.root.as_ref()
+    }
+}
+
+impl<Rndr, View> Render<Rndr> for ShadowRoot<Rndr, View>
+where
+    View: Render<Rndr>,
+    Rndr: DomRenderer,
+    Rndr::Node: Clone,
+{
+    type State = ShadowRootState<Rndr, View::State>;
+
+    fn build(self) -> Self::State {
+        ShadowRootState {
+            root: None,
+            state: self.view.build(),
+        }
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.view.rebuild(&mut state.state);
+    }
+}
+
+impl<Rndr, View> RenderHtml<Rndr> for ShadowRoot<Rndr, View>
+where
+    View: RenderHtml<Rndr>,
+    Rndr: DomRenderer,
+    Rndr::Element: Clone,
+    Rndr::Node: Clone,
+{
+    const MIN_LENGTH: usize =
+        OPEN_TAG.len() + CLOSE_TAG.len() + View::MIN_LENGTH;
+
+    fn to_html_with_buf(self, buf: &mut String, _position: &mut Position) {
+        buf.push_str(OPEN_TAG);
+        self.view.to_html_with_buf(buf, &mut Position::FirstChild);
+        buf.push_str(CLOSE_TAG);
+        // the template is not one of the parent's children once it has been
+        // parsed, so the position of the next sibling is left as it was
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        _position: &mut Position,
+    ) where
+        Self: Sized,
+    {
+        buf.push_sync(OPEN_TAG);
+        self.view.to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            &mut Position::FirstChild,
+        );
+        buf.push_sync(CLOSE_TAG);
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor<Rndr>,
+        position: &PositionState,
+    ) -> Self::State {
+        // the cursor is either on the host, if nothing inside it has been
+        // hydrated yet, or on the host's previous child
+        let current = cursor.current();
+        let host = if position.get() == Position::FirstChild {
+            Some(current.clone())
+        } else {
+            Rndr::get_parent(&current)
+        }
+        .and_then(Rndr::Element::cast_from)
+        .expect("a shadow root should be inside an element");
+
+        match Rndr::shadow_root(&host) {
+            Some(root) => {
+                let state = self.view.hydrate::<FROM_SERVER>(
+                    &Cursor::new(root.clone()),
+                    &PositionState::new(Position::FirstChild),
+                );
+                ShadowRootState {
+                    root: Some(root),
+                    state,
+                }
+            }
+            // browsers without support for declarative shadow DOM leave the
+            // template in place, so it is removed and the view is rendered
+            // on the client
+            None => {
+                let template = if position.get() == Position::FirstChild {
+                    Rndr::first_child(&current)
+                } else {
+                    Rndr::next_sibling(&current)
+                };
+                if let Some(template) = template
+                    .and_then(Rndr::Element::cast_from)
+                    .filter(|el| Rndr::tag_name(el) == "template")
+                {
+                    Rndr::remove_node(host.as_ref(), template.as_ref());
+                }
+
+                let mut state = self.build();
+                state.mount(host.as_ref(), None);
+                state
+            }
+        }
+    }
+}
+
+impl<Rndr, State> Mountable<Rndr> for ShadowRootState<Rndr, State>
+where
+    Rndr: DomRenderer,
+    Rndr::Node: Clone,
+    State: Mountable<Rndr>,
+{
+    /// Removes the view from the shadow root.
+    ///
+    /// A shadow root can't be detached from its host, so the (now empty)
+    /// root stays attached, along with anything else that has been added to
+    /// it, like event listeners. Mounting the view again on the same host
+    /// reuses that root.
+    fn unmount(&mut self) {
+        self.state.unmount();
+    }
+
+    fn mount(
+        &mut self,
+        parent: &<Rndr as Renderer>::Node,
+        _marker: Option<&<Rndr as Renderer>::Node>,
+    ) {
+        let host = Rndr::Element::cast_from(parent.clone())
+            .expect("a shadow root should be inside an element");
+        let root = Rndr::attach_shadow(&host);
+        self.state.mount(&root, None);
+        self.root = Some(root);
+    }
+
+    fn insert_before_this(
+        &self,
+        _parent: &<Rndr as Renderer>::Node,
+        _child: &mut dyn Mountable<Rndr>,
+    ) -> bool {
+        // nothing is rendered among the host's own children
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::shadow_root;
+    use crate::{
+        html::element::{div, p, style, Div, ElementChild, Template, P},
+        renderer::{mock_dom::MockDom, DomRenderer, Renderer},
+        view::{Mountable, Render, RenderHtml},
+    };
+
+    #[test]
+    fn shadow_root_renders_declarative_template() {
+        let view = div::<MockDom>().child((
+            shadow_root((style().child("p { color: red; }"), p().child("In"))),
+            "Out",
+        ));
+        assert_eq!(
+            view.to_html(),
+            "<div><template shadowrootmode=\"open\"><style>p { color: red; \
+             }</style><p>In</p></template>Out</div>"
+        );
+    }
+
+    #[test]
+    fn shadow_root_is_attached_when_built() {
+        let view = div::<MockDom>()
+            .child((shadow_root(p().child("In")), p().child("Out")));
+        let state = view.build();
+        assert_eq!(
+            state.el.to_debug_html(),
+            "<div><template shadowrootmode=\"open\"><p>In</p></template><p>\
+             Out</p></div>"
+        );
+    }
+
+    #[test]
+    fn shadow_root_is_emptied_on_unmount_and_reused_on_mount() {
+        let host = MockDom::create_element(Div);
+        let mut state = shadow_root::<MockDom, _>(p().child("In")).build();
+        state.mount(&host, None);
+        let root = state.root().cloned().unwrap();
+        assert_eq!(
+            MockDom::first_child(&root),
+            Some(state.state.el.as_ref().clone())
+        );
+
+        state.unmount();
+        assert_eq!(MockDom::first_child(&root), None);
+        assert_eq!(MockDom::shadow_root(&host), Some(root.clone()));
+
+        state.mount(&host, None);
+        assert_eq!(state.root(), Some(&root));
+        assert_eq!(
+            MockDom::first_child(&root),
+            Some(state.state.el.as_ref().clone())
+        );
+    }
+
+    #[test]
+    fn shadow_root_hydrates_declarative_shadow_root() {
+        // builds <div><template shadowrootmode="open"><p>In</p></template>
+        // <p>Out</p></div>, as the browser would parse it
+        let host = MockDom::create_element(Div);
+        let root = MockDom::attach_shadow(&host);
+        let inner = MockDom::create_element(P);
+        let inner_text = MockDom::create_text_node("In");
+        let outer = MockDom::create_element(P);
+        let outer_text = MockDom::create_text_node("Out");
+        MockDom::insert_node(&root, inner.as_ref(), None);
+        MockDom::insert_node(&inner, inner_text.as_ref(), None);
+        MockDom::insert_node(&host, outer.as_ref(), None);
+        MockDom::insert_node(&outer, outer_text.as_ref(), None);
+        let wrapper = MockDom::create_element(Div);
+        MockDom::insert_node(&wrapper, host.as_ref(), None);
+
+        let view = div::<MockDom>()
+            .child((shadow_root(p().child("In")), p().child("Out")));
+        let state = view.hydrate_from::<true>(&wrapper);
+        let (shadow, out) = &state.children;
+        assert_eq!(shadow.root(), Some(&root));
+        assert_eq!(shadow.state.el, inner);
+        assert_eq!(out.el, outer);
+    }
+
+    #[test]
+    fn shadow_root_replaces_template_without_declarative_shadow_dom() {
+        // builds <div><template shadowrootmode="open"><p>In</p></template>
+        // <p>Out</p></div>, as a browser that doesn't support declarative
+        // shadow DOM would parse it
+        let host = MockDom::create_element(Div);
+        let template = MockDom::create_element(Template);
+        let inner = MockDom::create_element(P);
+        let inner_text = MockDom::create_text_node("In");
+        let outer = MockDom::create_element(P);
+        let outer_text = MockDom::create_text_node("Out");
+        MockDom::insert_node(&host, template.as_ref(), None);
+        MockDom::insert_node(&template, inner.as_ref(), None);
+        MockDom::insert_node(&inner, inner_text.as_ref(), None);
+        MockDom::insert_node(&host, outer.as_ref(), None);
+        MockDom::insert_node(&outer, outer_text.as_ref(), None);
+        let wrapper = MockDom::create_element(Div);
+        MockDom::insert_node(&wrapper, host.as_ref(), None);
+
+        let view = div::<MockDom>()
+            .child((shadow_root(p().child("In")), p().child("Out")));
+        let state = view.hydrate_from::<true>(&wrapper);
+        let (shadow, out) = &state.children;
+        assert_eq!(shadow.root(), MockDom::shadow_root(&host).as_ref());
+        assert_eq!(out.el, outer);
+        assert_eq!(
+            host.to_debug_html(),
+            "<div><template shadowrootmode=\"open\"><p>In</p></template><p>\
+             Out</p></div>"
+        );
+    }
+}
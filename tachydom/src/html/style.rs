@@ -58,6 +58,10 @@ where
     fn rebuild(self, state: &mut Self::State) {
         self.style.rebuild(state)
     }
+
+    fn remove(state: Self::State, el: &R::Element) {
+        S::remove(state, el);
+    }
 }
 
 impl<S, R> ToTemplate for Style<S, R>
@@ -88,6 +92,12 @@ pub trait IntoStyle<R: DomRenderer> {
     fn build(self, el: &R::Element) -> Self::State;
 
     fn rebuild(self, state: &mut Self::State);
+
+    /// Removes the style properties this set on the element.
+    ///
+    /// By default, this only drops the state.
+    #[allow(unused)]
+    fn remove(state: Self::State, el: &R::Element) {}
 }
 
 pub trait StylePropertyValue<R: DomRenderer> {
@@ -132,6 +142,11 @@ where
         }
         *prev = self;
     }
+
+    fn remove(state: Self::State, el: &R::Element) {
+        let (_, prev) = state;
+        remove_properties::<R>(el, prev);
+    }
 }
 
 impl<R> IntoStyle<R> for String
@@ -162,13 +177,18 @@ where
         }
         *prev = self;
     }
+
+    fn remove(state: Self::State, el: &R::Element) {
+        let (_, prev) = state;
+        remove_properties::<R>(el, &prev);
+    }
 }
 
 impl<'a, R> IntoStyle<R> for (&'a str, &'a str)
 where
     R: DomRenderer,
 {
-    type State = (R::CssStyleDeclaration, &'a str);
+    type State = (R::CssStyleDeclaration, &'a str, &'a str);
 
     fn to_html(self, style: &mut String) {
         let (name, value) = self;
@@ -180,31 +200,40 @@ where
 
     fn hydrate<const FROM_SERVER: bool>(self, el: &R::Element) -> Self::State {
         let style = R::style(el);
-        (style, self.1)
+        (style, self.0, self.1)
     }
 
     fn build(self, el: &R::Element) -> Self::State {
         let (name, value) = self;
         let style = R::style(el);
         R::set_css_property(&style, name, value);
-        (style, self.1)
+        (style, name, value)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         let (name, value) = self;
-        let (style, prev) = state;
-        if value != *prev {
+        let (style, prev_name, prev) = state;
+        if name != *prev_name {
+            R::set_css_property(style, prev_name, "");
+            R::set_css_property(style, name, value);
+        } else if value != *prev {
             R::set_css_property(style, name, value);
         }
+        *prev_name = name;
         *prev = value;
     }
+
+    fn remove(state: Self::State, _el: &R::Element) {
+        let (style, name, _) = state;
+        R::set_css_property(&style, name, "");
+    }
 }
 
 impl<'a, R> IntoStyle<R> for (&'a str, String)
 where
     R: DomRenderer,
 {
-    type State = (R::CssStyleDeclaration, String);
+    type State = (R::CssStyleDeclaration, &'a str, String);
 
     fn to_html(self, style: &mut String) {
         let (name, value) = self;
@@ -216,24 +245,33 @@ where
 
     fn hydrate<const FROM_SERVER: bool>(self, el: &R::Element) -> Self::State {
         let style = R::style(el);
-        (style, self.1)
+        (style, self.0, self.1)
     }
 
     fn build(self, el: &R::Element) -> Self::State {
-        let (name, value) = &self;
+        let (name, value) = self;
         let style = R::style(el);
-        R::set_css_property(&style, name, value);
-        (style, self.1)
+        R::set_css_property(&style, name, &value);
+        (style, name, value)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         let (name, value) = self;
-        let (style, prev) = state;
-        if value != *prev {
+        let (style, prev_name, prev) = state;
+        if name != *prev_name {
+            R::set_css_property(style, prev_name, "");
+            R::set_css_property(style, name, &value);
+        } else if value != *prev {
             R::set_css_property(style, name, &value);
         }
+        *prev_name = name;
         *prev = value;
     }
+
+    fn remove(state: Self::State, _el: &R::Element) {
+        let (style, name, _) = state;
+        R::set_css_property(&style, name, "");
+    }
 }
 
 #[cfg(feature = "nightly")]
@@ -260,6 +298,19 @@ where
     }
 
     fn rebuild(self, _state: &mut Self::State) {}
+
+    fn remove(_state: Self::State, el: &R::Element) {
+        remove_properties::<R>(el, V);
+    }
+}
+
+// a plain style string replaces the whole `style` attribute, but only its own
+// properties are taken away again, so that others set since are kept
+fn remove_properties<R: DomRenderer>(el: &R::Element, style: &str) {
+    let decl = R::style(el);
+    for (name, _) in style.split(';').filter_map(|prop| prop.split_once(':')) {
+        R::set_css_property(&decl, name.trim(), "");
+    }
 }
 
 /*
@@ -1,12 +1,101 @@
-use crate::renderer::Renderer;
-use std::{cell::RefCell, rc::Rc};
+use crate::{
+    renderer::{CastFrom, Renderer},
+    view::Position,
+};
+#[cfg(debug_assertions)]
+use std::borrow::Cow;
+use std::{
+    cell::{Cell, RefCell},
+    error, fmt,
+    rc::Rc,
+};
+
+/// What to do when the server-rendered HTML doesn't match the view that is
+/// being hydrated.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum MismatchBehavior {
+    /// Panic with a [`HydrationMismatch`] describing the difference.
+    #[default]
+    Panic,
+    /// Discard the mismatched node, render that part of the view on the
+    /// client instead, and continue hydrating.
+    ClientRender,
+}
+
+thread_local! {
+    static MISMATCH_BEHAVIOR: Cell<MismatchBehavior> = Default::default();
+}
+
+/// Sets what hydration should do when the server-rendered HTML doesn't match
+/// the view.
+///
+/// In debug builds, every node is checked against the view as it is
+/// hydrated. In release builds, nodes are only checked when using
+/// [`MismatchBehavior::ClientRender`]; otherwise only nodes of the wrong
+/// type are detected.
+pub fn set_mismatch_behavior(behavior: MismatchBehavior) {
+    MISMATCH_BEHAVIOR.with(|b| b.set(behavior));
+}
+
+pub fn mismatch_behavior() -> MismatchBehavior {
+    MISMATCH_BEHAVIOR.with(Cell::get)
+}
+
+/// A difference between the server-rendered HTML and the view that is being
+/// hydrated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HydrationMismatch {
+    /// The elements and components that enclose the mismatched node, from
+    /// the outside in. This is only tracked in debug builds.
+    pub path: Vec<String>,
+    /// Where the node was expected, relative to the previous one.
+    pub position: Position,
+    /// The node that the view expected to find.
+    pub expected: String,
+    /// The node that was actually found.
+    pub found: String,
+}
+
+impl fmt::Display for HydrationMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hydration mismatch")?;
+        if !self.path.is_empty() {
+            write!(f, " in {}", self.path.join(" > "))?;
+        }
+        write!(
+            f,
+            " at {:?}: expected {}, but found {}. This usually means that \
+             the server and the client rendered different views.",
+            self.position, self.expected, self.found
+        )
+    }
+}
+
+impl error::Error for HydrationMismatch {}
+
+// the cursor tried to move to a node that doesn't exist
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Missing {
+    Child,
+    Sibling,
+}
 
 #[derive(Debug)]
-pub struct Cursor<R: Renderer>(Rc<RefCell<R::Node>>);
+pub struct Cursor<R: Renderer> {
+    node: Rc<RefCell<R::Node>>,
+    missing: Rc<Cell<Option<Missing>>>,
+    #[cfg(debug_assertions)]
+    path: Rc<RefCell<Vec<Cow<'static, str>>>>,
+}
 
 impl<R: Renderer> Clone for Cursor<R> {
     fn clone(&self) -> Self {
-        Self(Rc::clone(&self.0))
+        Self {
+            node: Rc::clone(&self.node),
+            missing: Rc::clone(&self.missing),
+            #[cfg(debug_assertions)]
+            path: Rc::clone(&self.path),
+        }
     }
 }
 
@@ -16,36 +105,286 @@ where
     R::Node: Clone,
     R::Element: AsRef<R::Node>,
 {
-    pub fn new(root: R::Element) -> Self {
-        Self(Rc::new(RefCell::new(root.as_ref().clone())))
+    pub fn new(root: R::Node) -> Self {
+        Self {
+            node: Rc::new(RefCell::new(root)),
+            missing: Default::default(),
+            #[cfg(debug_assertions)]
+            path: Default::default(),
+        }
     }
 
     pub fn current(&self) -> R::Node {
-        self.0.borrow().clone()
+        self.node.borrow().clone()
     }
 
     pub fn child(&self) {
-        let mut inner = self.0.borrow_mut();
+        let mut inner = self.node.borrow_mut();
         if let Some(node) = R::first_child(&*inner) {
             *inner = node;
+            self.missing.set(None);
+        } else {
+            self.missing.set(Some(Missing::Child));
         }
     }
 
     pub fn sibling(&self) {
-        let mut inner = self.0.borrow_mut();
+        let mut inner = self.node.borrow_mut();
         if let Some(node) = R::next_sibling(&*inner) {
             *inner = node;
+            self.missing.set(None);
+        } else {
+            self.missing.set(Some(Missing::Sibling));
         }
     }
 
     pub fn parent(&self) {
-        let mut inner = self.0.borrow_mut();
+        let mut inner = self.node.borrow_mut();
         if let Some(node) = R::get_parent(&*inner) {
             *inner = node;
         }
+        self.missing.set(None);
     }
 
     pub fn set(&self, node: R::Node) {
-        *self.0.borrow_mut() = node;
+        *self.node.borrow_mut() = node;
+        self.missing.set(None);
+    }
+
+    /// Adds an element or component to the path reported by a
+    /// [`HydrationMismatch`], until the matching [`Cursor::exit`].
+    pub fn enter(&self, _name: &'static str) {
+        #[cfg(debug_assertions)]
+        self.path.borrow_mut().push(Cow::Borrowed(_name));
+    }
+
+    pub fn exit(&self) {
+        #[cfg(debug_assertions)]
+        self.path.borrow_mut().pop();
+    }
+
+    fn verifying() -> bool {
+        cfg!(debug_assertions)
+            || mismatch_behavior() == MismatchBehavior::ClientRender
+    }
+
+    /// Returns the current node as an element, checking that it is a `<tag>`
+    /// element. An empty `tag` matches any element.
+    pub fn element(
+        &self,
+        tag: &str,
+        position: Position,
+    ) -> Result<R::Element, HydrationMismatch> {
+        let expected = || {
+            if tag.is_empty() {
+                "an element".to_string()
+            } else {
+                format!("<{tag}>")
+            }
+        };
+        if Self::verifying() && self.missing.get().is_some() {
+            return Err(self.mismatch(position, expected()));
+        }
+        match R::Element::cast_from(self.current()) {
+            Some(el)
+                if !Self::verifying()
+                    || tag.is_empty()
+                    || R::tag_name(&el).eq_ignore_ascii_case(tag) =>
+            {
+                Ok(el)
+            }
+            _ => Err(self.mismatch(position, expected())),
+        }
+    }
+
+    /// Returns the current node as a text node, checking that it is one.
+    pub fn text(
+        &self,
+        position: Position,
+    ) -> Result<R::Text, HydrationMismatch> {
+        if Self::verifying() && self.missing.get().is_some() {
+            return Err(self.mismatch(position, "a text node".to_string()));
+        }
+        R::Text::cast_from(self.current())
+            .ok_or_else(|| self.mismatch(position, "a text node".to_string()))
+    }
+
+    /// Returns the current node as a placeholder, checking that it is one.
+    pub fn placeholder(
+        &self,
+        position: Position,
+    ) -> Result<R::Placeholder, HydrationMismatch> {
+        if Self::verifying() && self.missing.get().is_some() {
+            return Err(self.mismatch(position, "a placeholder".to_string()));
+        }
+        R::Placeholder::cast_from(self.current())
+            .ok_or_else(|| self.mismatch(position, "a placeholder".to_string()))
+    }
+
+    fn mismatch(
+        &self,
+        position: Position,
+        expected: String,
+    ) -> HydrationMismatch {
+        let found = if self.missing.get().is_some() {
+            "nothing".to_string()
+        } else {
+            let node = self.current();
+            if let Some(el) = R::Element::cast_from(node.clone()) {
+                format!("<{}>", R::tag_name(&el))
+            } else if R::Text::cast_from(node.clone()).is_some() {
+                "a text node".to_string()
+            } else if R::Placeholder::cast_from(node).is_some() {
+                "a placeholder".to_string()
+            } else {
+                "an unknown node".to_string()
+            }
+        };
+        HydrationMismatch {
+            #[cfg(debug_assertions)]
+            path: self.path.borrow().iter().map(|s| s.to_string()).collect(),
+            #[cfg(not(debug_assertions))]
+            path: Vec::new(),
+            position,
+            expected,
+            found,
+        }
+    }
+
+    /// Returns the parent of a list whose items have just been hydrated, and
+    /// the node that follows the list, if any.
+    ///
+    /// Lists don't have a marker of their own, so items that are added to
+    /// the list later are inserted before the node that follows it.
+    pub(crate) fn list_bounds(
+        &self,
+        position: Position,
+    ) -> (Option<R::Node>, Option<R::Node>) {
+        let current = self.current();
+        if position == Position::FirstChild {
+            // nothing has been hydrated yet, so the cursor is still on the
+            // parent
+            let next = R::first_child(&current);
+            (Some(current), next)
+        } else {
+            let next = R::next_sibling(&current);
+            (R::get_parent(&current), next)
+        }
+    }
+
+    /// Handles a [`HydrationMismatch`] according to the current
+    /// [`MismatchBehavior`].
+    ///
+    /// With [`MismatchBehavior::ClientRender`], `new` (which has been
+    /// rendered on the client) takes the place of the mismatched node, and
+    /// the cursor moves to it. Otherwise, this panics.
+    #[track_caller]
+    pub fn replace_mismatched(
+        &self,
+        mismatch: HydrationMismatch,
+        new: &R::Node,
+    ) {
+        if mismatch_behavior() != MismatchBehavior::ClientRender {
+            panic!("{mismatch}");
+        }
+
+        #[cfg(debug_assertions)]
+        {
+            crate::log(&format!(
+                "{mismatch} Rendering it on the client instead."
+            ));
+        }
+
+        let current = self.current();
+        let (parent, anchor) = match self.missing.get() {
+            Some(Missing::Child) => (Some(current), None),
+            Some(Missing::Sibling) => (R::get_parent(&current), None),
+            None => (R::get_parent(&current), Some(current)),
+        };
+        let parent = parent.unwrap_or_else(|| panic!("{mismatch}"));
+        R::insert_node(&parent, new, anchor.as_ref());
+        if let Some(anchor) = anchor {
+            R::remove_node(&parent, &anchor);
+        }
+        self.set(new.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{set_mismatch_behavior, MismatchBehavior};
+    use crate::{
+        html::element::{self, main, p, span, ElementChild},
+        renderer::{mock_dom::MockDom, Renderer},
+        view::{component::ComponentView, either::Either, RenderHtml},
+    };
+
+    // builds <div><main><p>Hello</p></main></div>, as if it had been
+    // rendered on the server
+    fn server_html() -> <MockDom as Renderer>::Element {
+        let root = MockDom::create_element(element::Div);
+        let main = MockDom::create_element(element::Main);
+        let p = MockDom::create_element(element::P);
+        let text = MockDom::create_text_node("Hello");
+        MockDom::insert_node(&root, main.as_ref(), None);
+        MockDom::insert_node(&main, p.as_ref(), None);
+        MockDom::insert_node(&p, text.as_ref(), None);
+        root
+    }
+
+    #[test]
+    fn matching_view_hydrates() {
+        let root = server_html();
+        let view = main::<MockDom>().child(p().child("Hello"));
+        let state = view.hydrate_from::<true>(&root);
+        assert_eq!(state.el.to_debug_html(), "<main><p>Hello</p></main>");
+    }
+
+    #[test]
+    #[should_panic(expected = "hydration mismatch in main at FirstChild: \
+                               expected <span>, but found <p>")]
+    fn mismatched_element_panics() {
+        let root = server_html();
+        let view = main::<MockDom>().child(span().child("Hello"));
+        view.hydrate_from::<true>(&root);
+    }
+
+    #[test]
+    fn mismatched_element_can_be_client_rendered() {
+        set_mismatch_behavior(MismatchBehavior::ClientRender);
+        let root = server_html();
+        let view = main::<MockDom>().child((span().child("Hi"), "there"));
+        view.hydrate_from::<true>(&root);
+        set_mismatch_behavior(MismatchBehavior::Panic);
+        assert_eq!(
+            root.to_debug_html(),
+            "<div><main><span>Hi</span>there</main></div>"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "hydration mismatch in Greeting > main at \
+                               FirstChild: expected <span>, but found <p>")]
+    fn mismatch_path_includes_components() {
+        let root = server_html();
+        let view = ComponentView::new(
+            "Greeting",
+            main::<MockDom>().child(span().child("Hello")),
+        );
+        view.hydrate_from::<true>(&root);
+    }
+
+    #[test]
+    fn missing_placeholders_can_be_client_rendered() {
+        set_mismatch_behavior(MismatchBehavior::ClientRender);
+        let root = server_html();
+        let view = main::<MockDom>()
+            .child((p().child(Either::<_, &str>::Left("Hello")), None::<&str>));
+        view.hydrate_from::<true>(&root);
+        set_mismatch_behavior(MismatchBehavior::Panic);
+        assert_eq!(
+            root.to_debug_html(),
+            "<div><main><p>Hello<!></p><!></main></div>"
+        );
     }
 }
@@ -108,7 +108,7 @@ where
 
     fn mount(
         &mut self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         marker: Option<&<R as Renderer>::Node>,
     ) {
         self.with_value_mut(|value| {
@@ -120,7 +120,7 @@ where
 
     fn insert_before_this(
         &self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool {
         self.with_value_mut(|value| {
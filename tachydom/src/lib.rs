@@ -6,12 +6,14 @@ pub mod prelude {
         async_views::FutureViewExt,
         html::{
             attribute::{
+                any_attr::AddAnyAttr,
                 aria::AriaAttributes,
                 custom::CustomAttribute,
                 global::{
-                    ClassAttribute, GlobalAttributes, OnAttribute,
-                    PropAttribute, StyleAttribute,
+                    AttrsAttribute, ClassAttribute, GlobalAttributes,
+                    OnAttribute, PropAttribute, StyleAttribute,
                 },
+                spread::SpreadAttribute,
             },
             element::{ElementChild, InnerHtmlAttribute},
             node_ref::NodeRefAttribute,
@@ -36,12 +38,17 @@ pub mod renderer;
 pub mod spawner;
 pub mod ssr;
 pub mod svg;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod view;
 
 #[cfg(feature = "islands")]
 pub use wasm_bindgen;
 #[cfg(feature = "islands")]
 pub use web_sys;
+// used by `custom_element!`
+#[doc(hidden)]
+pub use paste;
 
 #[cfg(all(feature = "leptos", not(feature = "reaccy")))]
 mod leptos;
@@ -51,8 +58,13 @@ mod tachy_reaccy;
 #[cfg(feature = "reaccy")]
 pub use tachy_reaccy::node_ref;
 
+/// Logs a message to the browser console, or to stderr when not running in
+/// the browser.
 pub fn log(text: &str) {
+    #[cfg(target_arch = "wasm32")]
     web_sys::console::log_1(&JsValue::from_str(text));
+    #[cfg(not(target_arch = "wasm32"))]
+    eprintln!("{text}");
 }
 
 pub(crate) trait UnwrapOrDebug {
@@ -3,21 +3,82 @@ use super::SpawningRenderer;
 use super::{CastFrom, DomRenderer, Renderer};
 use crate::{
     dom::{document, window},
+    html::event::Modifiers,
     ok_or_debug, or_debug,
     view::Mountable,
 };
-use rustc_hash::FxHashSet;
+use rustc_hash::FxHashMap;
 use std::{borrow::Cow, cell::RefCell};
 use wasm_bindgen::{intern, prelude::Closure, JsCast, JsValue};
 use web_sys::{
     Comment, CssStyleDeclaration, DocumentFragment, DomTokenList, Element,
-    HtmlElement, Node, Text,
+    HtmlElement, Node, ShadowRoot, ShadowRootInit, ShadowRootMode, Text,
 };
 
 pub struct Dom;
 
 thread_local! {
-    pub(crate) static GLOBAL_EVENTS: RefCell<FxHashSet<Cow<'static, str>>> = Default::default();
+    pub(crate) static GLOBAL_EVENTS: RefCell<FxHashMap<Cow<'static, str>, GlobalEvent>> = Default::default();
+}
+
+/// The handler installed on the window for a delegated event, and the number
+/// of elements that are currently listening for it.
+pub(crate) struct GlobalEvent {
+    handler: JsValue,
+    listeners: usize,
+}
+
+/// Creates the handler that is installed on the window for a delegated
+/// event, which calls the listener stored at `key` on each node the event
+/// passed through, from the target outward.
+fn delegated_handler(key: JsValue) -> JsValue {
+    let handler = move |ev: web_sys::Event| {
+        // the composed path crosses into open shadow roots, so listeners
+        // inside them run even though the event has been retargeted to the
+        // shadow host by the time it reaches the window
+        let path = ev.composed_path();
+        let path = if path.length() > 0 {
+            path.iter().collect::<Vec<_>>()
+        } else {
+            let mut path = Vec::new();
+            let mut node = ev
+                .target()
+                .and_then(|target| target.dyn_into::<Node>().ok());
+            while let Some(curr) = node {
+                node = curr.parent_node().or_else(|| {
+                    curr.dyn_ref::<ShadowRoot>().map(|root| root.host().into())
+                });
+                path.push(curr.into());
+            }
+            path
+        };
+
+        for node in path {
+            let node_is_disabled =
+                js_sys::Reflect::get(&node, &JsValue::from_str("disabled"))
+                    .map(|disabled| disabled.is_truthy())
+                    .unwrap_or(false);
+            if node_is_disabled {
+                continue;
+            }
+            let Ok(maybe_handler) = js_sys::Reflect::get(&node, &key) else {
+                continue;
+            };
+            if let Some(f) = maybe_handler.dyn_ref::<js_sys::Function>() {
+                let _ = f.call1(&node, &ev);
+
+                // `stopPropagation()` and `stopImmediatePropagation()`
+                // both stop the event from reaching any more delegated
+                // handlers
+                if ev.cancel_bubble() {
+                    return;
+                }
+            }
+        }
+    };
+
+    let handler = Box::new(handler) as Box<dyn FnMut(web_sys::Event)>;
+    Closure::wrap(handler).into_js_value()
 }
 
 impl Renderer for Dom {
@@ -51,7 +112,7 @@ impl Renderer for Dom {
     }
 
     fn insert_node(
-        parent: &Self::Element,
+        parent: &Self::Node,
         new_child: &Self::Node,
         anchor: Option<&Self::Node>,
     ) {
@@ -63,7 +124,7 @@ impl Renderer for Dom {
     }
 
     fn remove_node(
-        parent: &Self::Element,
+        parent: &Self::Node,
         child: &Self::Node,
     ) -> Option<Self::Node> {
         ok_or_debug!(parent.remove_child(child), parent, "removeNode")
@@ -89,7 +150,11 @@ impl Renderer for Dom {
         web_sys::console::log_1(node);
     }
 
-    fn clear_children(parent: &Self::Element) {
+    fn tag_name(el: &Self::Element) -> String {
+        el.tag_name().to_ascii_lowercase()
+    }
+
+    fn clear_children(parent: &Self::Node) {
         parent.set_text_content(Some(""));
     }
 }
@@ -98,6 +163,7 @@ impl DomRenderer for Dom {
     type Event = JsValue;
     type ClassList = DomTokenList;
     type CssStyleDeclaration = CssStyleDeclaration;
+    type Property = JsValue;
 
     fn set_property(el: &Self::Element, key: &str, value: &JsValue) {
         or_debug!(
@@ -143,6 +209,70 @@ impl DomRenderer for Dom {
         })
     }
 
+    fn add_event_listener_with_options(
+        el: &Self::Element,
+        name: &str,
+        options: Option<&web_sys::AddEventListenerOptions>,
+        modifiers: &Modifiers,
+        cb: Box<dyn FnMut(Self::Event)>,
+    ) -> Box<dyn FnOnce(&Self::Element)> {
+        let cb = wasm_bindgen::closure::Closure::wrap(cb);
+        let name = intern(name);
+        // a copy of the event's own options, with the modifiers added
+        let listener_options = web_sys::AddEventListenerOptions::new();
+        if let Some(options) = options {
+            js_sys::Object::assign(&listener_options, options);
+        }
+        for (key, enabled) in [
+            ("once", modifiers.once),
+            ("capture", modifiers.capture),
+            ("passive", modifiers.passive),
+        ] {
+            if enabled {
+                _ = js_sys::Reflect::set(
+                    &listener_options,
+                    &JsValue::from_str(key),
+                    &JsValue::TRUE,
+                );
+            }
+        }
+        or_debug!(
+            el.add_event_listener_with_callback_and_add_event_listener_options(
+                name,
+                cb.as_ref().unchecked_ref(),
+                &listener_options
+            ),
+            el,
+            "addEventListener"
+        );
+
+        // a listener can only be removed with the same `capture` flag it was
+        // added with
+        let capture = js_sys::Reflect::get(
+            &listener_options,
+            &JsValue::from_str("capture"),
+        )
+        .ok()
+        .and_then(|capture| capture.as_bool())
+        .unwrap_or(false);
+
+        // return the remover
+        Box::new({
+            let name = name.to_owned();
+            move |el| {
+                or_debug!(
+                    el.remove_event_listener_with_callback_and_bool(
+                        intern(&name),
+                        cb.as_ref().unchecked_ref(),
+                        capture
+                    ),
+                    el,
+                    "removeEventListener"
+                )
+            }
+        })
+    }
+
     fn add_event_listener_delegated(
         el: &Self::Element,
         name: Cow<'static, str>,
@@ -159,60 +289,10 @@ impl DomRenderer for Dom {
 
         GLOBAL_EVENTS.with(|global_events| {
             let mut events = global_events.borrow_mut();
-            if !events.contains(&name) {
-                // create global handler
-                let key = JsValue::from_str(key);
-                let handler = move |ev: web_sys::Event| {
-                    let target = ev.target();
-                    let node = ev.composed_path().get(0);
-                    let mut node = if node.is_undefined() || node.is_null() {
-                        JsValue::from(target)
-                    } else {
-                        node
-                    };
-
-                    // TODO reverse Shadow DOM retargetting
-                    // TODO simulate currentTarget
-
-                    while !node.is_null() {
-                        let node_is_disabled = js_sys::Reflect::get(
-                            &node,
-                            &JsValue::from_str("disabled"),
-                        )
-                        .unwrap()
-                        .is_truthy();
-                        if !node_is_disabled {
-                            let maybe_handler =
-                                js_sys::Reflect::get(&node, &key).unwrap();
-                            if !maybe_handler.is_undefined() {
-                                let f = maybe_handler
-                                    .unchecked_ref::<js_sys::Function>();
-                                let _ = f.call1(&node, &ev);
-
-                                if ev.cancel_bubble() {
-                                    return;
-                                }
-                            }
-                        }
-
-                        // navigate up tree
-                        if let Some(parent) =
-                            node.unchecked_ref::<web_sys::Node>().parent_node()
-                        {
-                            node = parent.into()
-                        } else if let Some(root) =
-                            node.dyn_ref::<web_sys::ShadowRoot>()
-                        {
-                            node = root.host().unchecked_into();
-                        } else {
-                            node = JsValue::null()
-                        }
-                    }
-                };
-
-                let handler =
-                    Box::new(handler) as Box<dyn FnMut(web_sys::Event)>;
-                let handler = Closure::wrap(handler).into_js_value();
+            if let Some(global) = events.get_mut(&name) {
+                global.listeners += 1;
+            } else {
+                let handler = delegated_handler(JsValue::from_str(key));
                 window()
                     .add_event_listener_with_callback(
                         &name,
@@ -221,7 +301,13 @@ impl DomRenderer for Dom {
                     .unwrap();
 
                 // register that we've created handler
-                events.insert(name);
+                events.insert(
+                    name.clone(),
+                    GlobalEvent {
+                        handler,
+                        listeners: 1,
+                    },
+                );
             }
         });
 
@@ -235,9 +321,47 @@ impl DomRenderer for Dom {
                 el,
                 "delete property"
             );
+
+            // the global handler is removed along with the last listener
+            GLOBAL_EVENTS.with(|global_events| {
+                let mut events = global_events.borrow_mut();
+                let Some(global) = events.get_mut(&name) else {
+                    return;
+                };
+                global.listeners -= 1;
+                if global.listeners == 0 {
+                    let global = events.remove(&name).unwrap();
+                    _ = window().remove_event_listener_with_callback(
+                        &name,
+                        global.handler.unchecked_ref(),
+                    );
+                }
+            });
         })
     }
 
+    fn is_event_target(ev: &Self::Event, el: &Self::Element) -> bool {
+        ev.unchecked_ref::<web_sys::Event>()
+            .target()
+            .map_or(false, |target| {
+                target.unchecked_ref::<JsValue>()
+                    == el.unchecked_ref::<JsValue>()
+            })
+    }
+
+    fn prevent_default(ev: &Self::Event) {
+        ev.unchecked_ref::<web_sys::Event>().prevent_default();
+    }
+
+    fn stop_propagation(ev: &Self::Event) {
+        ev.unchecked_ref::<web_sys::Event>().stop_propagation();
+    }
+
+    fn event_key(ev: &Self::Event) -> Option<String> {
+        ev.dyn_ref::<web_sys::KeyboardEvent>()
+            .map(web_sys::KeyboardEvent::key)
+    }
+
     fn class_list(el: &Self::Element) -> Self::ClassList {
         el.class_list()
     }
@@ -273,6 +397,19 @@ impl DomRenderer for Dom {
     fn set_inner_html(el: &Self::Element, html: &str) {
         el.set_inner_html(html);
     }
+
+    fn shadow_root(el: &Self::Element) -> Option<Self::Node> {
+        el.shadow_root().map(Into::into)
+    }
+
+    fn attach_shadow(el: &Self::Element) -> Self::Node {
+        if let Some(root) = Self::shadow_root(el) {
+            return root;
+        }
+        el.attach_shadow(&ShadowRootInit::new(ShadowRootMode::Open))
+            .expect("couldn't attach shadow root")
+            .into()
+    }
 }
 
 impl Mountable<Dom> for Node {
@@ -280,13 +417,13 @@ impl Mountable<Dom> for Node {
         todo!()
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+    fn mount(&mut self, parent: &Node, marker: Option<&Node>) {
         Dom::insert_node(parent, self, marker);
     }
 
     fn insert_before_this(
         &self,
-        parent: &<Dom as Renderer>::Element,
+        parent: &<Dom as Renderer>::Node,
         child: &mut dyn Mountable<Dom>,
     ) -> bool {
         child.mount(parent, Some(self));
@@ -299,13 +436,13 @@ impl Mountable<Dom> for Text {
         self.remove();
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+    fn mount(&mut self, parent: &Node, marker: Option<&Node>) {
         Dom::insert_node(parent, self, marker);
     }
 
     fn insert_before_this(
         &self,
-        parent: &<Dom as Renderer>::Element,
+        parent: &<Dom as Renderer>::Node,
         child: &mut dyn Mountable<Dom>,
     ) -> bool {
         child.mount(parent, Some(self.as_ref()));
@@ -318,13 +455,13 @@ impl Mountable<Dom> for Comment {
         self.remove();
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+    fn mount(&mut self, parent: &Node, marker: Option<&Node>) {
         Dom::insert_node(parent, self, marker);
     }
 
     fn insert_before_this(
         &self,
-        parent: &<Dom as Renderer>::Element,
+        parent: &<Dom as Renderer>::Node,
         child: &mut dyn Mountable<Dom>,
     ) -> bool {
         child.mount(parent, Some(self.as_ref()));
@@ -337,13 +474,13 @@ impl Mountable<Dom> for Element {
         self.remove();
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+    fn mount(&mut self, parent: &Node, marker: Option<&Node>) {
         Dom::insert_node(parent, self, marker);
     }
 
     fn insert_before_this(
         &self,
-        parent: &<Dom as Renderer>::Element,
+        parent: &<Dom as Renderer>::Node,
         child: &mut dyn Mountable<Dom>,
     ) -> bool {
         child.mount(parent, Some(self.as_ref()));
@@ -356,13 +493,13 @@ impl Mountable<Dom> for DocumentFragment {
         todo!()
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+    fn mount(&mut self, parent: &Node, marker: Option<&Node>) {
         Dom::insert_node(parent, self, marker);
     }
 
     fn insert_before_this(
         &self,
-        parent: &<Dom as Renderer>::Element,
+        parent: &<Dom as Renderer>::Node,
         child: &mut dyn Mountable<Dom>,
     ) -> bool {
         child.mount(parent, Some(self.as_ref()));
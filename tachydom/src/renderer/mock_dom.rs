@@ -4,13 +4,30 @@
 //!
 //! Do not use this for anything real.
 
-use super::{CastFrom, DomRenderer, Renderer};
+use super::{CastFrom, DomRenderer, Renderer, SpawningRenderer};
 use crate::{
-    html::element::{CreateElement, ElementType},
+    html::{
+        element::{CreateElement, ElementType},
+        event::Modifiers,
+    },
+    spawner::Spawner,
     view::Mountable,
 };
+use futures::{future::LocalBoxFuture, stream::FuturesUnordered, StreamExt};
 use slotmap::{new_key_type, SlotMap};
-use std::{borrow::Cow, cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    ops::Deref,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
 use wasm_bindgen::JsValue;
 pub struct MockDom;
 
@@ -24,6 +41,46 @@ pub struct Node(NodeId);
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Element(Node);
 
+/// A property value, stored as its string representation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PropertyValue(pub String);
+
+macro_rules! property_value {
+    ($($ty:ty),*) => {
+        $(impl From<$ty> for PropertyValue {
+            fn from(value: $ty) -> Self {
+                PropertyValue(value.to_string())
+            }
+        })*
+    };
+}
+
+property_value!(
+    String, &String, &str, usize, u8, u16, u32, u64, u128, isize, i8, i16, i32,
+    i64, i128, f32, f64, bool
+);
+
+impl From<JsValue> for PropertyValue {
+    fn from(value: JsValue) -> Self {
+        // a `JsValue` can only be inspected on wasm32
+        #[cfg(target_arch = "wasm32")]
+        {
+            PropertyValue(
+                value
+                    .as_string()
+                    .or_else(|| value.as_f64().map(|n| n.to_string()))
+                    .or_else(|| value.as_bool().map(|b| b.to_string()))
+                    .unwrap_or_default(),
+            )
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            _ = value;
+            PropertyValue::default()
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Text(Node);
 
@@ -42,6 +99,15 @@ impl AsRef<Node> for Element {
     }
 }
 
+// like `web_sys::Element`, so that an element can be passed as a parent
+impl Deref for Element {
+    type Target = Node;
+
+    fn deref(&self) -> &Node {
+        &self.0
+    }
+}
+
 impl AsRef<Node> for Text {
     fn as_ref(&self) -> &Node {
         &self.0
@@ -76,12 +142,111 @@ impl From<Placeholder> for Node {
     }
 }
 
+impl Node {
+    /// Returns the text of this node and all of its descendant text nodes.
+    pub fn text_content(&self) -> String {
+        let mut buf = String::new();
+        self.push_text_content(&mut buf);
+        buf
+    }
+
+    fn push_text_content(&self, buf: &mut String) {
+        let children = Document::with_node(self.0, |node| match &node.ty {
+            NodeType::Text(text) => {
+                buf.push_str(text);
+                Vec::new()
+            }
+            NodeType::Element { children, .. } => children.clone(),
+            NodeType::Placeholder => Vec::new(),
+        })
+        .unwrap_or_default();
+        for child in children {
+            child.push_text_content(buf);
+        }
+    }
+}
+
 impl Element {
     pub fn to_debug_html(&self) -> String {
         let mut buf = String::new();
         self.debug_html(&mut buf);
         buf
     }
+
+    /// Returns the element's tag name.
+    pub fn tag(&self) -> Cow<'static, str> {
+        Document::with_node(self.0 .0, |node| match &node.ty {
+            NodeType::Element { tag, .. } => tag.clone(),
+            _ => Cow::Borrowed(""),
+        })
+        .unwrap_or_default()
+    }
+
+    /// Returns the element's child nodes.
+    pub fn children(&self) -> Vec<Node> {
+        Document::with_node(self.0 .0, |node| match &node.ty {
+            NodeType::Element { children, .. } => children.clone(),
+            _ => Vec::new(),
+        })
+        .unwrap_or_default()
+    }
+
+    /// Returns the text of all the element's descendant text nodes.
+    pub fn text_content(&self) -> String {
+        self.0.text_content()
+    }
+
+    /// Returns the current value of the attribute `name`, if it is set.
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        Document::with_node(self.0 .0, |node| match &node.ty {
+            NodeType::Element { attrs, .. } => attrs.get(name).cloned(),
+            _ => None,
+        })
+        .flatten()
+    }
+
+    /// Returns the current value of the property `name`, if it has been set.
+    pub fn property(&self, name: &str) -> Option<String> {
+        Document::with_node(self.0 .0, |node| match &node.ty {
+            NodeType::Element { props, .. } => props.get(name).cloned(),
+            _ => None,
+        })
+        .flatten()
+    }
+
+    /// Sets the property `name` directly, as user input would.
+    ///
+    /// Native tests can't create a [`JsValue`], so this is the way to change
+    /// properties like `value` or `checked` from outside the view.
+    pub fn set_property(&self, name: &str, value: impl Into<String>) {
+        Document::with_node_mut(self.0 .0, |node| {
+            if let NodeType::Element { ref mut props, .. } = node.ty {
+                props.insert(name.to_string(), value.into());
+            }
+        });
+    }
+
+    // `class` and `style` are stored as ordinary attributes, so that they
+    // show up in the debug HTML the same way they would be serialized
+    fn update_attribute(
+        &self,
+        name: &str,
+        fun: impl FnOnce(&str) -> Option<String>,
+    ) {
+        Document::with_node_mut(self.0 .0, |node| {
+            if let NodeType::Element { ref mut attrs, .. } = node.ty {
+                let prev = attrs.get(name).map(String::as_str).unwrap_or("");
+                match fun(prev) {
+                    Some(value) => {
+                        attrs.insert(name.to_string(), value);
+                    }
+                    None => {
+                        attrs.remove(name);
+                    }
+                }
+            }
+        });
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -126,6 +291,8 @@ impl DebugHtml for NodeData {
                 tag,
                 attrs,
                 children,
+                shadow_root,
+                ..
             } => {
                 buf.push('<');
                 buf.push_str(tag);
@@ -138,6 +305,15 @@ impl DebugHtml for NodeData {
                 }
                 buf.push('>');
 
+                // written out as a declarative shadow root
+                if let Some(shadow_root) = shadow_root {
+                    buf.push_str("<template shadowrootmode=\"open\">");
+                    for child in shadow_root.children() {
+                        child.debug_html(buf);
+                    }
+                    buf.push_str("</template>");
+                }
+
                 for child in children {
                     child.debug_html(buf);
                 }
@@ -180,6 +356,7 @@ impl Document {
 
     pub fn reset(&self) {
         self.0.borrow_mut().clear();
+        LISTENERS.with(|listeners| listeners.take());
     }
 
     fn create_element(&self, tag: &str) -> Element {
@@ -188,7 +365,9 @@ impl Document {
             ty: NodeType::Element {
                 tag: tag.to_string().into(),
                 attrs: HashMap::new(),
+                props: HashMap::new(),
                 children: Vec::new(),
+                shadow_root: None,
             },
         })))
     }
@@ -208,14 +387,14 @@ impl Document {
     }
 }
 
-// TODO!
 impl DomRenderer for MockDom {
-    type Event = ();
-    type ClassList = ();
-    type CssStyleDeclaration = ();
+    type Event = JsValue;
+    type ClassList = Element;
+    type CssStyleDeclaration = Element;
+    type Property = PropertyValue;
 
-    fn set_property(el: &Self::Element, key: &str, value: &JsValue) {
-        todo!()
+    fn set_property(el: &Self::Element, key: &str, value: &PropertyValue) {
+        el.set_property(key, value.0.clone());
     }
 
     fn add_event_listener(
@@ -223,7 +402,58 @@ impl DomRenderer for MockDom {
         name: &str,
         cb: Box<dyn FnMut(Self::Event)>,
     ) -> Box<dyn FnOnce(&Self::Element)> {
-        todo!()
+        let key = (el.0 .0, name.to_string());
+        let id = LISTENERS.with(|listeners| {
+            let mut listeners = listeners.borrow_mut();
+            listeners.next_id += 1;
+            let id = listeners.next_id;
+            listeners
+                .direct
+                .entry(key.clone())
+                .or_default()
+                .push((id, Rc::new(RefCell::new(cb))));
+            id
+        });
+
+        // return the remover
+        Box::new(move |_| {
+            LISTENERS.with(|listeners| {
+                if let Some(direct) =
+                    listeners.borrow_mut().direct.get_mut(&key)
+                {
+                    direct.retain(|(listener_id, _)| *listener_id != id);
+                }
+            })
+        })
+    }
+
+    fn add_event_listener_with_options(
+        el: &Self::Element,
+        name: &str,
+        _options: Option<&web_sys::AddEventListenerOptions>,
+        modifiers: &Modifiers,
+        cb: Box<dyn FnMut(Self::Event)>,
+    ) -> Box<dyn FnOnce(&Self::Element)> {
+        // there is no capture phase, and nothing happens by default that a
+        // passive listener could delay, so only `once` changes anything;
+        // such a listener stays in place, but does nothing after it's called
+        //
+        // the event's own options are JavaScript objects, which can't be
+        // read outside the browser
+        if modifiers.once {
+            let mut cb = Some(cb);
+            Self::add_event_listener(
+                el,
+                name,
+                Box::new(move |ev| {
+                    if let Some(mut cb) = cb.take() {
+                        cb(ev);
+                    }
+                }),
+            )
+        } else {
+            Self::add_event_listener(el, name, cb)
+        }
     }
 
     fn add_event_listener_delegated(
@@ -232,23 +462,80 @@ impl DomRenderer for MockDom {
         delegation_key: Cow<'static, str>,
         cb: Box<dyn FnMut(Self::Event)>,
     ) -> Box<dyn FnOnce(&Self::Element)> {
-        todo!()
+        let key = (el.0 .0, delegation_key.clone());
+        LISTENERS.with(|listeners| {
+            let mut listeners = listeners.borrow_mut();
+            listeners
+                .delegated
+                .insert(key.clone(), Rc::new(RefCell::new(cb)));
+            listeners
+                .global
+                .entry(name.clone())
+                .or_insert((delegation_key, 0))
+                .1 += 1;
+        });
+
+        // return the remover
+        Box::new(move |_| {
+            LISTENERS.with(|listeners| {
+                let mut listeners = listeners.borrow_mut();
+                listeners.delegated.remove(&key);
+                // like the global handler, the event is no longer delegated
+                // once its last listener has been removed
+                if let Some((_, count)) = listeners.global.get_mut(&name) {
+                    *count -= 1;
+                    if *count == 0 {
+                        listeners.global.remove(&name);
+                    }
+                }
+            })
+        })
+    }
+
+    fn is_event_target(_ev: &Self::Event, el: &Self::Element) -> bool {
+        // events carry no data of their own, so the target is that of the
+        // event that is currently being dispatched
+        EVENT.with(|event| event.borrow().target == Some(el.0 .0))
+    }
+
+    fn prevent_default(_ev: &Self::Event) {
+        EVENT.with(|event| event.borrow_mut().default_prevented = true);
+    }
+
+    fn stop_propagation(_ev: &Self::Event) {
+        EVENT.with(|event| event.borrow_mut().propagation_stopped = true);
+    }
+
+    fn event_key(_ev: &Self::Event) -> Option<String> {
+        EVENT.with(|event| event.borrow().key.clone())
     }
 
     fn class_list(el: &Self::Element) -> Self::ClassList {
-        todo!()
+        el.clone()
     }
 
     fn add_class(class_list: &Self::ClassList, name: &str) {
-        todo!()
+        class_list.update_attribute("class", |prev| {
+            let mut classes = prev.split_whitespace().collect::<Vec<_>>();
+            if !classes.contains(&name) {
+                classes.push(name);
+            }
+            Some(classes.join(" "))
+        });
     }
 
     fn remove_class(class_list: &Self::ClassList, name: &str) {
-        todo!()
+        class_list.update_attribute("class", |prev| {
+            let classes = prev
+                .split_whitespace()
+                .filter(|class| *class != name)
+                .collect::<Vec<_>>();
+            Some(classes.join(" "))
+        });
     }
 
     fn style(el: &Self::Element) -> Self::CssStyleDeclaration {
-        todo!()
+        el.clone()
     }
 
     fn set_css_property(
@@ -256,11 +543,199 @@ impl DomRenderer for MockDom {
         name: &str,
         value: &str,
     ) {
-        todo!()
+        style.update_attribute("style", |prev| {
+            let mut props = prev
+                .split(';')
+                .filter_map(|prop| prop.split_once(':'))
+                .map(|(k, v)| (k.trim(), v.trim()))
+                .filter(|(k, _)| *k != name)
+                .collect::<Vec<_>>();
+            // like the DOM, setting a property to an empty value removes it
+            if !value.is_empty() {
+                props.push((name, value));
+            }
+            (!props.is_empty()).then(|| {
+                props
+                    .into_iter()
+                    .map(|(k, v)| format!("{k}: {v};"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+        });
     }
 
     fn set_inner_html(el: &Self::Element, html: &str) {
-        todo!()
+        // the HTML isn't parsed; it is stored as a single text node, which is
+        // written back out as-is by the debug HTML
+        MockDom::clear_children(el);
+        let text = MockDom::create_text_node(html);
+        MockDom::insert_node(el, text.as_ref(), None);
+    }
+
+    fn shadow_root(el: &Self::Element) -> Option<Self::Node> {
+        Document::with_node(el.0 .0, |node| match &node.ty {
+            NodeType::Element { shadow_root, .. } => {
+                shadow_root.clone().map(|root| root.0)
+            }
+            _ => None,
+        })
+        .flatten()
+    }
+
+    fn attach_shadow(el: &Self::Element) -> Self::Node {
+        if let Some(root) = Self::shadow_root(el) {
+            return root;
+        }
+        // like a real shadow root, this has no parent, and is not one of the
+        // host's children
+        let root = document().create_element("#shadow-root");
+        Document::with_node_mut(el.0 .0, |node| {
+            if let NodeType::Element { shadow_root, .. } = &mut node.ty {
+                *shadow_root = Some(root.clone());
+            }
+        });
+        root.0
+    }
+}
+
+type Listener = Rc<RefCell<Box<dyn FnMut(JsValue)>>>;
+
+#[derive(Default)]
+struct Listeners {
+    next_id: usize,
+    direct: HashMap<(NodeId, String), Vec<(usize, Listener)>>,
+    delegated: HashMap<(NodeId, Cow<'static, str>), Listener>,
+    // event name => (delegation key, number of listeners), for every event
+    // that is currently delegated
+    global: HashMap<Cow<'static, str>, (Cow<'static, str>, usize)>,
+}
+
+// the event that is currently being dispatched
+#[derive(Debug, Default)]
+struct CurrentEvent {
+    target: Option<NodeId>,
+    key: Option<String>,
+    default_prevented: bool,
+    propagation_stopped: bool,
+}
+
+thread_local! {
+    static LISTENERS: RefCell<Listeners> = Default::default();
+    static EVENT: RefCell<CurrentEvent> = Default::default();
+}
+
+impl Document {
+    /// Dispatches an event named `name` at `target`.
+    ///
+    /// Listeners added with [`DomRenderer::add_event_listener`] run first,
+    /// on the target and then (if `bubbles` is `true`) on each of its
+    /// ancestors. Delegated listeners run afterward, in the same order,
+    /// skipping any element with a `disabled` attribute, just as the global
+    /// handler installed by [`Dom`](super::dom::Dom) does. A listener that
+    /// stops propagation keeps the event from reaching any later element.
+    ///
+    /// Returns `false` if a listener prevented the event's default action.
+    pub fn dispatch_event(
+        &self,
+        target: impl AsRef<Node>,
+        name: &str,
+        bubbles: bool,
+    ) -> bool {
+        self.dispatch(target.as_ref(), name, bubbles, None)
+    }
+
+    /// Dispatches a keyboard event named `name`, like `keydown`, for the
+    /// given [`key`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key)
+    /// at `target`. Keyboard events bubble.
+    ///
+    /// Returns `false` if a listener prevented the event's default action.
+    pub fn dispatch_key_event(
+        &self,
+        target: impl AsRef<Node>,
+        name: &str,
+        key: &str,
+    ) -> bool {
+        self.dispatch(target.as_ref(), name, true, Some(key.to_string()))
+    }
+
+    fn dispatch(
+        &self,
+        target: &Node,
+        name: &str,
+        bubbles: bool,
+        key: Option<String>,
+    ) -> bool {
+        let mut path = vec![target.clone()];
+        if bubbles {
+            while let Some(parent) = MockDom::get_parent(path.last().unwrap()) {
+                path.push(parent);
+            }
+        }
+        let prev = EVENT.with(|event| {
+            event.replace(CurrentEvent {
+                target: Some(target.0),
+                key,
+                ..Default::default()
+            })
+        });
+        let stopped = || EVENT.with(|event| event.borrow().propagation_stopped);
+
+        for node in &path {
+            // listeners are collected before they're called, so that they're
+            // free to add or remove other listeners
+            let direct = LISTENERS.with(|listeners| {
+                listeners
+                    .borrow()
+                    .direct
+                    .get(&(node.0, name.to_string()))
+                    .into_iter()
+                    .flatten()
+                    .map(|(_, listener)| Rc::clone(listener))
+                    .collect::<Vec<_>>()
+            });
+            for listener in direct {
+                (listener.borrow_mut())(JsValue::UNDEFINED);
+            }
+            if stopped() {
+                break;
+            }
+        }
+
+        // the global handler is on the window, which the event only reaches
+        // if nothing stopped it on the way
+        if !stopped() {
+            let delegated = LISTENERS.with(|listeners| {
+                let listeners = listeners.borrow();
+                let Some((key, _)) = listeners.global.get(name) else {
+                    return Vec::new();
+                };
+                path.iter()
+                    .filter(|node| {
+                        Element::cast_from((*node).clone()).map_or(true, |el| {
+                            el.attribute("disabled").is_none()
+                        })
+                    })
+                    .filter_map(|node| {
+                        listeners.delegated.get(&(node.0, key.clone())).cloned()
+                    })
+                    .collect::<Vec<_>>()
+            });
+            for listener in delegated {
+                (listener.borrow_mut())(JsValue::UNDEFINED);
+                if stopped() {
+                    break;
+                }
+            }
+        }
+
+        let event = EVENT.with(|event| event.replace(prev));
+        !event.default_prevented
+    }
+}
+
+fn unmount(node: &Node) {
+    if let Some(parent) = MockDom::get_parent(node) {
+        MockDom::remove_node(&parent, node);
     }
 }
 
@@ -271,7 +746,96 @@ impl Default for Document {
 }
 
 thread_local! {
-    static DOCUMENT: Document = Document::new();
+    static DOCUMENT: Document = {
+        // effects and resources run alongside the tasks spawned while
+        // rendering, so that `poll_tasks` can wait for them too
+        #[cfg(feature = "reaccy")]
+        ::tachy_reaccy::spawn::set_local_executor(MockSpawn::spawn_local);
+        Document::new()
+    };
+    static TASKS: RefCell<FuturesUnordered<LocalBoxFuture<'static, ()>>> =
+        Default::default();
+    // tasks spawned while `TASKS` is being polled
+    static SPAWNED: RefCell<Vec<LocalBoxFuture<'static, ()>>> =
+        Default::default();
+}
+
+/// Spawns tasks onto a queue that is run by [`poll_tasks`], so that tests can
+/// tell when all of the tasks spawned while rendering with [`MockDom`] have
+/// settled.
+///
+/// With the `reaccy` feature, the tasks that effects and resources spawn on
+/// a thread run here too, once [`MockDom`] has been used on that thread.
+#[derive(Debug, Copy, Clone)]
+pub struct MockSpawn;
+
+impl Spawner for MockSpawn {
+    fn spawn<Fut>(fut: Fut)
+    where
+        Fut: Future + Send + Sync + 'static,
+    {
+        Self::spawn_local(fut);
+    }
+
+    fn spawn_local<Fut>(fut: Fut)
+    where
+        Fut: Future + 'static,
+    {
+        let fut = Box::pin(async move {
+            fut.await;
+        });
+        SPAWNED.with(|spawned| spawned.borrow_mut().push(fut));
+    }
+}
+
+impl SpawningRenderer for MockDom {
+    type Spawn = MockSpawn;
+}
+
+/// Polls the tasks spawned with [`MockSpawn`] until none of them can make
+/// any more progress, because each has either finished or is waiting for
+/// something else to happen, like a signal to change.
+///
+/// ## Panics
+/// Panics if it is called from inside one of those tasks.
+pub(crate) fn poll_tasks(_cx: &mut Context<'_>) -> Poll<()> {
+    TASKS.with(|tasks| {
+        let mut tasks = tasks
+            .try_borrow_mut()
+            .expect("spawned tasks cannot wait for each other to finish");
+        let woken = Arc::new(WakeFlag::default());
+        let waker = Waker::from(Arc::clone(&woken));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            tasks.extend(SPAWNED.with(|spawned| spawned.take()));
+            match tasks.poll_next_unpin(&mut cx) {
+                Poll::Ready(Some(())) => {}
+                // all tasks have finished, or are waiting for something that
+                // hasn't happened, unless one of them spawned another
+                Poll::Ready(None) | Poll::Pending => {
+                    let spawned =
+                        SPAWNED.with(|spawned| !spawned.borrow().is_empty());
+                    if !spawned && !woken.0.swap(false, Ordering::Relaxed) {
+                        return Poll::Ready(());
+                    }
+                }
+            }
+        }
+    })
+}
+
+// records whether any of the tasks was woken while they were being polled
+#[derive(Default)]
+struct WakeFlag(AtomicBool);
+
+impl Wake for WakeFlag {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.store(true, Ordering::Relaxed);
+    }
 }
 
 pub fn document() -> Document {
@@ -284,23 +848,25 @@ pub enum NodeType {
     Element {
         tag: Cow<'static, str>,
         attrs: HashMap<String, String>,
+        props: HashMap<String, String>,
         children: Vec<Node>,
+        shadow_root: Option<Element>,
     },
     Placeholder,
 }
 
 impl Mountable<MockDom> for Node {
     fn unmount(&mut self) {
-        todo!()
+        unmount(self);
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+    fn mount(&mut self, parent: &Node, marker: Option<&Node>) {
         MockDom::insert_node(parent, self, marker);
     }
 
     fn insert_before_this(
         &self,
-        parent: &<MockDom as Renderer>::Element,
+        parent: &<MockDom as Renderer>::Node,
         child: &mut dyn Mountable<MockDom>,
     ) -> bool {
         child.mount(parent, Some(self));
@@ -310,16 +876,16 @@ impl Mountable<MockDom> for Node {
 
 impl Mountable<MockDom> for Text {
     fn unmount(&mut self) {
-        todo!()
+        unmount(self.as_ref());
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+    fn mount(&mut self, parent: &Node, marker: Option<&Node>) {
         MockDom::insert_node(parent, self.as_ref(), marker);
     }
 
     fn insert_before_this(
         &self,
-        parent: &<MockDom as Renderer>::Element,
+        parent: &<MockDom as Renderer>::Node,
         child: &mut dyn Mountable<MockDom>,
     ) -> bool {
         child.mount(parent, Some(self.as_ref()));
@@ -329,16 +895,16 @@ impl Mountable<MockDom> for Text {
 
 impl Mountable<MockDom> for Element {
     fn unmount(&mut self) {
-        todo!()
+        unmount(self.as_ref());
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+    fn mount(&mut self, parent: &Node, marker: Option<&Node>) {
         MockDom::insert_node(parent, self.as_ref(), marker);
     }
 
     fn insert_before_this(
         &self,
-        parent: &<MockDom as Renderer>::Element,
+        parent: &<MockDom as Renderer>::Node,
         child: &mut dyn Mountable<MockDom>,
     ) -> bool {
         child.mount(parent, Some(self.as_ref()));
@@ -348,16 +914,16 @@ impl Mountable<MockDom> for Element {
 
 impl Mountable<MockDom> for Placeholder {
     fn unmount(&mut self) {
-        todo!()
+        unmount(self.as_ref());
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+    fn mount(&mut self, parent: &Node, marker: Option<&Node>) {
         MockDom::insert_node(parent, self.as_ref(), marker);
     }
 
     fn insert_before_this(
         &self,
-        parent: &<MockDom as Renderer>::Element,
+        parent: &<MockDom as Renderer>::Node,
         child: &mut dyn Mountable<MockDom>,
     ) -> bool {
         child.mount(parent, Some(self.as_ref()));
@@ -367,7 +933,7 @@ impl Mountable<MockDom> for Placeholder {
 
 impl<E: ElementType> CreateElement<MockDom> for E {
     fn create_element(&self) -> <MockDom as Renderer>::Element {
-        document().create_element(E::TAG)
+        document().create_element(self.tag())
     }
 }
 
@@ -410,18 +976,17 @@ impl Renderer for MockDom {
     }
 
     fn insert_node(
-        parent: &Self::Element,
+        parent: &Self::Node,
         new_child: &Self::Node,
         anchor: Option<&Self::Node>,
     ) {
-        debug_assert!(&parent.0 != new_child);
+        debug_assert!(parent != new_child);
         // remove if already mounted
         if let Some(parent) = MockDom::get_parent(new_child) {
-            let parent = Element(parent);
             MockDom::remove_node(&parent, new_child);
         }
         // mount on new parent
-        Document::with_node_mut(parent.0 .0, |parent| {
+        Document::with_node_mut(parent.0, |parent| {
             if let NodeType::Element {
                 ref mut children, ..
             } = parent.ty
@@ -442,15 +1007,15 @@ impl Renderer for MockDom {
         });
         // set parent on child node
         Document::with_node_mut(new_child.0, |node| {
-            node.parent = Some(parent.0 .0)
+            node.parent = Some(parent.0)
         });
     }
 
     fn remove_node(
-        parent: &Self::Element,
+        parent: &Self::Node,
         child: &Self::Node,
     ) -> Option<Self::Node> {
-        let child = Document::with_node_mut(parent.0 .0, |parent| {
+        let child = Document::with_node_mut(parent.0, |parent| {
             if let NodeType::Element {
                 ref mut children, ..
             } = parent.ty
@@ -472,11 +1037,8 @@ impl Renderer for MockDom {
     }
 
     fn remove(node: &Self::Node) {
-        let parent = Element(Node(
-            Self::get_parent(node)
-                .expect("tried to remove a parentless node")
-                .0,
-        ));
+        let parent =
+            Self::get_parent(node).expect("tried to remove a parentless node");
         Self::remove_node(&parent, node);
     }
 
@@ -521,9 +1083,13 @@ impl Renderer for MockDom {
         println!("{node:?}");
     }
 
-    fn clear_children(parent: &Self::Element) {
+    fn tag_name(el: &Self::Element) -> String {
+        el.tag().to_ascii_lowercase()
+    }
+
+    fn clear_children(parent: &Self::Node) {
         let prev_children =
-            Document::with_node_mut(parent.0 .0, |node| match node.ty {
+            Document::with_node_mut(parent.0, |node| match node.ty {
                 NodeType::Element {
                     ref mut children, ..
                 } => std::mem::take(children),
@@ -567,11 +1133,13 @@ impl CastFrom<Node> for Placeholder {
 
 #[cfg(test)]
 mod tests {
-    use super::MockDom;
+    use super::{document, Element, MockDom, LISTENERS};
     use crate::{
         html::element,
-        renderer::{mock_dom::node_eq, Renderer},
+        renderer::{mock_dom::node_eq, DomRenderer, Renderer},
+        view::Mountable,
     };
+    use std::{cell::Cell, rc::Rc};
 
     #[test]
     fn html_debugging_works() {
@@ -688,4 +1256,164 @@ mod tests {
             Some(text.as_ref())
         );
     }
+
+    #[test]
+    fn class_list_updates_class_attribute() {
+        let p = MockDom::create_element(element::P);
+        let class_list = MockDom::class_list(&p);
+        MockDom::add_class(&class_list, "foo");
+        MockDom::add_class(&class_list, "bar");
+        MockDom::add_class(&class_list, "foo");
+        assert_eq!(p.to_debug_html(), "<p class=\"foo bar\"></p>");
+        MockDom::remove_class(&class_list, "foo");
+        assert_eq!(p.to_debug_html(), "<p class=\"bar\"></p>");
+    }
+
+    #[test]
+    fn style_updates_style_attribute() {
+        let p = MockDom::create_element(element::P);
+        let style = MockDom::style(&p);
+        MockDom::set_css_property(&style, "color", "red");
+        MockDom::set_css_property(&style, "width", "10px");
+        MockDom::set_css_property(&style, "color", "blue");
+        assert_eq!(
+            p.attribute("style").as_deref(),
+            Some("width: 10px; color: blue;")
+        );
+        MockDom::set_css_property(&style, "width", "");
+        MockDom::set_css_property(&style, "color", "");
+        assert_eq!(p.to_debug_html(), "<p></p>");
+    }
+
+    #[test]
+    fn set_inner_html_replaces_children() {
+        let main = MockDom::create_element(element::Main);
+        let p = MockDom::create_element(element::P);
+        MockDom::insert_node(&main, p.as_ref(), None);
+        MockDom::set_inner_html(&main, "<b>bold</b>");
+        assert_eq!(main.to_debug_html(), "<main><b>bold</b></main>");
+        assert!(MockDom::get_parent(p.as_ref()).is_none());
+    }
+
+    #[test]
+    fn unmount_removes_from_parent() {
+        let main = MockDom::create_element(element::Main);
+        let mut p = MockDom::create_element(element::P);
+        let mut text = MockDom::create_text_node("foo");
+        MockDom::insert_node(&main, p.as_ref(), None);
+        MockDom::insert_node(&main, text.as_ref(), None);
+        p.unmount();
+        text.unmount();
+        assert_eq!(main.to_debug_html(), "<main></main>");
+        // unmounting a node without a parent does nothing
+        p.unmount();
+    }
+
+    #[test]
+    fn event_listeners_can_be_added_and_removed() {
+        let main = MockDom::create_element(element::Main);
+        let button = MockDom::create_element(element::Button);
+        MockDom::insert_node(&main, button.as_ref(), None);
+
+        let clicks = Rc::new(Cell::new(0));
+        let remove = MockDom::add_event_listener(
+            &main,
+            "click",
+            Box::new({
+                let clicks = Rc::clone(&clicks);
+                move |_| clicks.set(clicks.get() + 1)
+            }),
+        );
+        document().dispatch_event(&button, "click", true);
+        assert_eq!(clicks.get(), 1);
+        // events that don't bubble only reach the target
+        document().dispatch_event(&button, "click", false);
+        assert_eq!(clicks.get(), 1);
+
+        remove(&main);
+        document().dispatch_event(&button, "click", true);
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn delegated_listeners_skip_disabled_elements() {
+        let main = MockDom::create_element(element::Main);
+        let button = MockDom::create_element(element::Button);
+        MockDom::insert_node(&main, button.as_ref(), None);
+
+        let clicks = Rc::new(Cell::new(0));
+        let remove = MockDom::add_event_listener_delegated(
+            &button,
+            "click".into(),
+            "$$$click".into(),
+            Box::new({
+                let clicks = Rc::clone(&clicks);
+                move |_| clicks.set(clicks.get() + 1)
+            }),
+        );
+        document().dispatch_event(&button, "click", true);
+        assert_eq!(clicks.get(), 1);
+
+        MockDom::set_attribute(&button, "disabled", "");
+        document().dispatch_event(&button, "click", true);
+        assert_eq!(clicks.get(), 1);
+
+        MockDom::remove_attribute(&button, "disabled");
+        remove(&button);
+        document().dispatch_event(&button, "click", true);
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn delegated_event_is_removed_with_last_listener() {
+        let main = MockDom::create_element(element::Main);
+        let first = MockDom::create_element(element::Button);
+        let second = MockDom::create_element(element::Button);
+        MockDom::insert_node(&main, first.as_ref(), None);
+        MockDom::insert_node(&main, second.as_ref(), None);
+
+        let clicks = Rc::new(Cell::new(0));
+        let add = |el: &Element| {
+            MockDom::add_event_listener_delegated(
+                el,
+                "dblclick".into(),
+                "$$$dblclick".into(),
+                Box::new({
+                    let clicks = Rc::clone(&clicks);
+                    move |_| clicks.set(clicks.get() + 1)
+                }),
+            )
+        };
+        let is_delegated = || {
+            LISTENERS.with(|listeners| {
+                listeners.borrow().global.contains_key("dblclick")
+            })
+        };
+        let remove_first = add(&first);
+        let remove_second = add(&second);
+
+        remove_first(&first);
+        assert!(is_delegated());
+        document().dispatch_event(&second, "dblclick", true);
+        assert_eq!(clicks.get(), 1);
+
+        remove_second(&second);
+        assert!(!is_delegated());
+        document().dispatch_event(&second, "dblclick", true);
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn properties_are_stored_on_the_element() {
+        use crate::html::{attribute::Attribute, property::property};
+
+        let input = MockDom::create_element(element::Input);
+        let mut state = property::<_, _, MockDom>("value", "foo").build(&input);
+        assert_eq!(input.property("value").as_deref(), Some("foo"));
+
+        property::<_, _, MockDom>("value", 42).rebuild(&mut state);
+        assert_eq!(input.property("value").as_deref(), Some("42"));
+        // properties aren't attributes
+        assert_eq!(input.to_debug_html(), "<input></input>");
+    }
 }
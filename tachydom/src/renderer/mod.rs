@@ -1,6 +1,9 @@
-use crate::{html::element::CreateElement, spawner::Spawner, view::Mountable};
+use crate::{
+    html::{element::CreateElement, event::Modifiers},
+    spawner::Spawner,
+    view::Mountable,
+};
 use std::borrow::Cow;
-use wasm_bindgen::JsValue;
 
 pub mod dom;
 #[cfg(feature = "testing")]
@@ -47,7 +50,7 @@ pub trait Renderer: Sized {
     /// Appends the new child to the parent, before the anchor node. If `anchor` is `None`,
     /// append to the end of the parent's children.
     fn insert_node(
-        parent: &Self::Element,
+        parent: &Self::Node,
         new_child: &Self::Node,
         marker: Option<&Self::Node>,
     );
@@ -55,26 +58,23 @@ pub trait Renderer: Sized {
     /// Mounts the new child before the marker as its sibling.
     ///
     /// ## Panics
-    /// The default implementation panics if `before` does not have a parent [`R::Element`].
+    /// The default implementation panics if `before` does not have a parent.
     fn mount_before<M>(new_child: &mut M, before: &Self::Node)
     where
         M: Mountable<Self>,
     {
-        let parent = Self::Element::cast_from(
-            Self::get_parent(before).expect("node should have parent"),
-        )
-        .expect("placeholder parent should be Element");
+        let parent = Self::get_parent(before).expect("node should have parent");
         new_child.mount(&parent, Some(before));
     }
 
     /// Removes the child node from the parents, and returns the removed node.
     fn remove_node(
-        parent: &Self::Element,
+        parent: &Self::Node,
         child: &Self::Node,
     ) -> Option<Self::Node>;
 
-    /// Removes all children from the parent element.
-    fn clear_children(parent: &Self::Element);
+    /// Removes all children from the parent node.
+    fn clear_children(parent: &Self::Node);
 
     /// Removes the node.
     fn remove(node: &Self::Node);
@@ -89,6 +89,9 @@ pub trait Renderer: Sized {
     fn next_sibling(node: &Self::Node) -> Option<Self::Node>;
 
     fn log_node(node: &Self::Node);
+
+    /// Returns the lowercase tag name of the given element.
+    fn tag_name(el: &Self::Element) -> String;
 }
 
 /// Additional rendering behavior that applies only to DOM nodes.
@@ -99,9 +102,11 @@ pub trait DomRenderer: Renderer {
     type ClassList;
     /// The CSS styles for an element.
     type CssStyleDeclaration;
+    /// A value that can be set as a property on an element.
+    type Property: PartialEq;
 
     /// Sets a JavaScript object property on a DOM element.
-    fn set_property(el: &Self::Element, key: &str, value: &JsValue);
+    fn set_property(el: &Self::Element, key: &str, value: &Self::Property);
 
     /// Adds an event listener to an element.
     ///
@@ -112,8 +117,26 @@ pub trait DomRenderer: Renderer {
         cb: Box<dyn FnMut(Self::Event)>,
     ) -> Box<dyn FnOnce(&Self::Element)>;
 
+    /// Adds an event listener to an element with the given options, such as
+    /// `passive`, `once` or `capture`, combined with the `once`, `capture`
+    /// and `passive` options of the given modifiers.
+    ///
+    /// Returns a function to remove the listener.
+    fn add_event_listener_with_options(
+        el: &Self::Element,
+        name: &str,
+        options: Option<&web_sys::AddEventListenerOptions>,
+        modifiers: &Modifiers,
+        cb: Box<dyn FnMut(Self::Event)>,
+    ) -> Box<dyn FnOnce(&Self::Element)>;
+
     /// Adds an event listener to an element, delegated to the window if possible.
     ///
+    /// The delegated handler runs the listeners on each element the event
+    /// passes through, including elements inside shadow roots, until one of
+    /// them stops propagation. Removing the last listener for an event
+    /// removes the handler from the window.
+    ///
     /// Returns a function to remove the listener.
     fn add_event_listener_delegated(
         el: &Self::Element,
@@ -122,6 +145,22 @@ pub trait DomRenderer: Renderer {
         cb: Box<dyn FnMut(Self::Event)>,
     ) -> Box<dyn FnOnce(&Self::Element)>;
 
+    /// Whether the event was dispatched on `el` itself, rather than on one
+    /// of its descendants.
+    ///
+    /// The element a listener was added to is passed in explicitly, because
+    /// a delegated event's `currentTarget` is always the window.
+    fn is_event_target(ev: &Self::Event, el: &Self::Element) -> bool;
+
+    /// Prevents the default action of the event.
+    fn prevent_default(ev: &Self::Event);
+
+    /// Stops the event from propagating to any other elements.
+    fn stop_propagation(ev: &Self::Event);
+
+    /// The key that was pressed, if the event is a keyboard event.
+    fn event_key(ev: &Self::Event) -> Option<String>;
+
     /// The list of CSS classes for an element.
     fn class_list(el: &Self::Element) -> Self::ClassList;
 
@@ -143,6 +182,17 @@ pub trait DomRenderer: Renderer {
 
     /// Sets the `innerHTML` of a DOM element, without escaping any values.
     fn set_inner_html(el: &Self::Element, html: &str);
+
+    /// Returns the open shadow root attached to the element, if any, such as
+    /// one created from a declarative `<template shadowrootmode="open">`.
+    ///
+    /// A shadow root isn't an element, but like any other node, other nodes
+    /// can be inserted into it as their parent.
+    fn shadow_root(el: &Self::Element) -> Option<Self::Node>;
+
+    /// Attaches an open shadow root to the element, or returns the one that
+    /// is already attached.
+    fn attach_shadow(el: &Self::Element) -> Self::Node;
 }
 
 /// A renderer that is able to spawn async tasks during rendering.
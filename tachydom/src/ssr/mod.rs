@@ -1,6 +1,6 @@
 use crate::{
     renderer::Renderer,
-    view::{Position, PositionState, RenderHtml},
+    view::{Position, RenderHtml},
 };
 use futures::Stream;
 use std::{
@@ -9,6 +9,7 @@ use std::{
     future::Future,
     mem,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
@@ -19,6 +20,7 @@ pub struct StreamBuilder {
     pending: Option<ChunkFuture>,
     pending_ooo: VecDeque<ChunkFuture>,
     id: Option<Vec<u16>>,
+    nonce: Option<Arc<str>>,
 }
 
 type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send + Sync>>;
@@ -32,6 +34,18 @@ impl StreamBuilder {
         }
     }
 
+    /// Sets the [CSP nonce](https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/nonce)
+    /// that is added to the inline scripts used for out-of-order streaming.
+    pub fn with_nonce(mut self, nonce: Option<Arc<str>>) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// The nonce added to inline scripts, if any.
+    pub fn nonce(&self) -> Option<&str> {
+        self.nonce.as_deref()
+    }
+
     pub fn push_sync(&mut self, string: &str) {
         self.sync_buf.push_str(string);
     }
@@ -143,6 +157,7 @@ impl StreamBuilder {
         Rndr::Element: Clone,
     {
         let id = self.clone_id();
+        let nonce = self.nonce.clone();
         // copy so it's not updated by additional iterations
         // i.e., restart in the same position we were at when we suspended
         let mut position = *position;
@@ -152,7 +167,7 @@ impl StreamBuilder {
             chunks: Box::pin(async move {
                 let view = view.await;
 
-                let mut subbuilder = StreamBuilder::new(id);
+                let mut subbuilder = StreamBuilder::new(id).with_nonce(nonce);
                 let mut id = String::new();
                 if let Some(ids) = &subbuilder.id {
                     for piece in ids {
@@ -176,8 +191,12 @@ impl StreamBuilder {
 
                 subbuilder.sync_buf.push_str("<!></template>");
 
-                // TODO nonce
                 subbuilder.sync_buf.push_str("<script");
+                if let Some(nonce) = &subbuilder.nonce {
+                    subbuilder.sync_buf.push_str(" nonce=\"");
+                    subbuilder.sync_buf.push_str(nonce);
+                    subbuilder.sync_buf.push('"');
+                }
                 subbuilder.sync_buf.push_str(r#">(function() { let id = ""#);
                 subbuilder.sync_buf.push_str(&id);
                 subbuilder.sync_buf.push_str(
@@ -557,4 +576,32 @@ mod tests {
 
         assert!(stream.next().await.is_none());
     }
+
+    #[tokio::test]
+    async fn out_of_order_scripts_have_nonce() {
+        let el: HtmlElement<_, _, _, Dom> = main().child(
+            async {
+                sleep(Duration::from_millis(250)).await;
+                p().child(
+                    async {
+                        sleep(Duration::from_millis(250)).await;
+                        "Inner Suspense"
+                    }
+                    .suspend()
+                    .with_fallback("Loading Inner..."),
+                )
+            }
+            .suspend()
+            .with_fallback("Loading..."),
+        );
+        let mut stream =
+            el.to_html_stream_out_of_order_with_nonce(Some("abc123".into()));
+
+        assert!(!stream.next().await.unwrap().contains("<script"));
+        let loading_inner = stream.next().await.unwrap();
+        assert!(loading_inner.contains("<script nonce=\"abc123\">"));
+        let inner = stream.next().await.unwrap();
+        assert!(inner.contains("<script nonce=\"abc123\">"));
+        assert!(stream.next().await.is_none());
+    }
 }
@@ -71,6 +71,14 @@ where
         )
         .into();
     }
+
+    fn remove(state: Self::State, el: &R::Element) {
+        // stop the effect before removing what it last added
+        let value = state.0.and_then(|effect| effect.take_value());
+        if let Some(value) = value {
+            C::remove(value, el);
+        }
+    }
 }
 
 impl<F, R> IntoClass<R> for (&'static str, F)
@@ -80,7 +88,7 @@ where
     R::ClassList: Clone + 'static,
     R::Element: Clone,
 {
-    type State = RenderEffectState<(R::ClassList, bool)>;
+    type State = RenderEffectState<(R::ClassList, &'static str, bool)>;
 
     fn to_html(self, class: &mut String) {
         let (name, mut f) = self;
@@ -94,9 +102,9 @@ where
         // TODO FROM_SERVER vs template
         let (name, mut f) = self;
         let class_list = R::class_list(el);
-        RenderEffect::new(move |prev: Option<(R::ClassList, bool)>| {
+        RenderEffect::new(move |prev: Option<(R::ClassList, &str, bool)>| {
             let include = f();
-            if let Some((class_list, prev)) = prev {
+            if let Some((class_list, _, prev)) = prev {
                 if include {
                     if !prev {
                         R::add_class(&class_list, name);
@@ -105,7 +113,7 @@ where
                     R::remove_class(&class_list, name);
                 }
             }
-            (class_list.clone(), include)
+            (class_list.clone(), name, include)
         })
         .into()
     }
@@ -113,10 +121,10 @@ where
     fn build(self, el: &R::Element) -> Self::State {
         let (name, mut f) = self;
         let class_list = R::class_list(el);
-        RenderEffect::new(move |prev: Option<(R::ClassList, bool)>| {
+        RenderEffect::new(move |prev: Option<(R::ClassList, &str, bool)>| {
             let include = f();
             match prev {
-                Some((class_list, prev)) => {
+                Some((class_list, _, prev)) => {
                     if include {
                         if !prev {
                             R::add_class(&class_list, name);
@@ -131,7 +139,7 @@ where
                     }
                 }
             }
-            (class_list.clone(), include)
+            (class_list.clone(), name, include)
         })
         .into()
     }
@@ -163,4 +171,11 @@ where
         )
         .into(); */
     }
+
+    fn remove(state: Self::State, _el: &R::Element) {
+        let value = state.0.and_then(|effect| effect.take_value());
+        if let Some((class_list, name, true)) = value {
+            R::remove_class(&class_list, name);
+        }
+    }
 }
@@ -1,6 +1,9 @@
 use crate::{
     async_views::Suspend,
-    html::{attribute::AttributeValue, property::IntoProperty},
+    html::{
+        attribute::{Attribute, AttributeValue},
+        property::IntoProperty,
+    },
     hydration::Cursor,
     renderer::{DomRenderer, Renderer},
     ssr::StreamBuilder,
@@ -14,6 +17,7 @@ use tachy_reaccy::{async_signal::ScopedFuture, render_effect::RenderEffect};
 
 mod class;
 pub mod node_ref;
+mod spread;
 mod style;
 
 impl<F, V> ToTemplate for F
@@ -99,7 +103,7 @@ where
         }
     }
 
-    fn mount(&mut self, parent: &R::Element, marker: Option<&R::Node>) {
+    fn mount(&mut self, parent: &R::Node, marker: Option<&R::Node>) {
         if let Some(ref mut inner) = self.0 {
             inner.mount(parent, marker);
         }
@@ -107,7 +111,7 @@ where
 
     fn insert_before_this(
         &self,
-        parent: &R::Element,
+        parent: &R::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool {
         if let Some(inner) = &self.0 {
@@ -200,7 +204,7 @@ where
 
     fn mount(
         &mut self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         marker: Option<&<R as Renderer>::Node>,
     ) {
         self.with_value_mut(|state| {
@@ -210,7 +214,7 @@ where
 
     fn insert_before_this(
         &self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool {
         self.with_value_mut(|value| value.insert_before_this(parent, child))
@@ -332,6 +336,89 @@ where
     } */
 }
 
+// Reactive attributes
+// A closure that returns a whole attribute, or set of attributes, as in the
+// `{move || ...}` block attribute in the `view` macro
+impl<F, A, R> Attribute<R> for F
+where
+    F: FnMut() -> A + 'static,
+    A: Attribute<R>,
+    A::State: 'static,
+    R: Renderer,
+    R::Element: Clone + 'static,
+{
+    const MIN_LENGTH: usize = 0;
+
+    type State = RenderEffectState<A::State>;
+
+    fn to_html(
+        mut self,
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        inner_html: &mut String,
+    ) {
+        let attr = self();
+        attr.to_html(buf, class, style, inner_html);
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        mut self,
+        el: &R::Element,
+    ) -> Self::State {
+        let el = el.clone();
+        RenderEffect::new(move |prev| {
+            let attr = self();
+            if let Some(mut state) = prev {
+                attr.rebuild(&mut state);
+                state
+            } else {
+                attr.hydrate::<FROM_SERVER>(&el)
+            }
+        })
+        .into()
+    }
+
+    fn build(mut self, el: &R::Element) -> Self::State {
+        let el = el.clone();
+        RenderEffect::new(move |prev| {
+            let attr = self();
+            if let Some(mut state) = prev {
+                attr.rebuild(&mut state);
+                state
+            } else {
+                attr.build(&el)
+            }
+        })
+        .into()
+    }
+
+    fn rebuild(mut self, state: &mut Self::State) {
+        let prev_effect = mem::take(&mut state.0);
+        let prev_value = prev_effect.as_ref().and_then(|e| e.take_value());
+        drop(prev_effect);
+        *state = RenderEffect::new_with_value(
+            move |prev| {
+                let attr = self();
+                let mut state = prev
+                    .expect("the previous attributes should have been built");
+                attr.rebuild(&mut state);
+                state
+            },
+            prev_value,
+        )
+        .into();
+    }
+
+    fn remove(state: Self::State, el: &R::Element) {
+        // stop the effect before removing what it last set
+        let value = state.0.and_then(|effect| effect.take_value());
+        if let Some(value) = value {
+            A::remove(value, el);
+        }
+    }
+}
+
 // Dynamic properties
 // These do update during hydration because properties don't exist in the DOM
 impl<F, V, R> IntoProperty<R> for F
@@ -0,0 +1,126 @@
+use super::RenderEffectState;
+use crate::{
+    html::attribute::{spread::IntoSpread, Attribute},
+    renderer::Renderer,
+    view::{Position, ToTemplate},
+};
+use std::marker::PhantomData;
+use tachy_reaccy::render_effect::RenderEffect;
+
+/// A set of attributes that is updated whenever the signals read by `F`
+/// change.
+pub struct ReactiveSpread<F, R> {
+    f: F,
+    rndr: PhantomData<R>,
+}
+
+impl<F, S, R> IntoSpread<R> for F
+where
+    F: FnMut() -> S + 'static,
+    S: IntoSpread<R> + 'static,
+    <S::Output as Attribute<R>>::State: 'static,
+    R: Renderer,
+    R::Element: Clone + 'static,
+{
+    type Output = ReactiveSpread<F, R>;
+
+    fn into_spread(self) -> Self::Output {
+        ReactiveSpread {
+            f: self,
+            rndr: PhantomData,
+        }
+    }
+}
+
+impl<F, S, R> Attribute<R> for ReactiveSpread<F, R>
+where
+    F: FnMut() -> S + 'static,
+    S: IntoSpread<R> + 'static,
+    <S::Output as Attribute<R>>::State: 'static,
+    R: Renderer,
+    R::Element: Clone + 'static,
+{
+    const MIN_LENGTH: usize = 0;
+
+    type State = RenderEffectState<<S::Output as Attribute<R>>::State>;
+
+    fn to_html(
+        mut self,
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        inner_html: &mut String,
+    ) {
+        let value = (self.f)().into_spread();
+        value.to_html(buf, class, style, inner_html);
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        mut self,
+        el: &R::Element,
+    ) -> Self::State {
+        let el = el.clone();
+        RenderEffect::new(move |prev| {
+            let value = (self.f)().into_spread();
+            if let Some(mut state) = prev {
+                value.rebuild(&mut state);
+                state
+            } else {
+                value.hydrate::<FROM_SERVER>(&el)
+            }
+        })
+        .into()
+    }
+
+    fn build(mut self, el: &R::Element) -> Self::State {
+        let el = el.clone();
+        RenderEffect::new(move |prev| {
+            let value = (self.f)().into_spread();
+            if let Some(mut state) = prev {
+                value.rebuild(&mut state);
+                state
+            } else {
+                value.build(&el)
+            }
+        })
+        .into()
+    }
+
+    fn rebuild(mut self, state: &mut Self::State) {
+        let prev_effect = std::mem::take(&mut state.0);
+        let prev_value = prev_effect.as_ref().and_then(|e| e.take_value());
+        drop(prev_effect);
+        *state = RenderEffect::new_with_value(
+            move |prev| {
+                let value = (self.f)().into_spread();
+                let mut state = prev.expect(
+                    "the previous spread attributes should have been built",
+                );
+                value.rebuild(&mut state);
+                state
+            },
+            prev_value,
+        )
+        .into();
+    }
+
+    fn remove(state: Self::State, el: &R::Element) {
+        // stop the effect before removing what it last set
+        let value = state.0.and_then(|effect| effect.take_value());
+        if let Some(value) = value {
+            S::Output::remove(value, el);
+        }
+    }
+}
+
+impl<F, R> ToTemplate for ReactiveSpread<F, R> {
+    // the attributes are only known at runtime
+    fn to_template(
+        _buf: &mut String,
+        _class: &mut String,
+        _style: &mut String,
+        _inner_html: &mut String,
+        _position: &mut Position,
+    ) {
+    }
+}
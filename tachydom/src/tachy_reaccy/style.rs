@@ -10,7 +10,11 @@ where
     R: DomRenderer,
     R::CssStyleDeclaration: Clone + 'static,
 {
-    type State = RenderEffectState<(R::CssStyleDeclaration, Cow<'static, str>)>;
+    type State = RenderEffectState<(
+        R::CssStyleDeclaration,
+        &'static str,
+        Cow<'static, str>,
+    )>;
 
     fn to_html(self, style: &mut String) {
         let (name, mut f) = self;
@@ -28,8 +32,9 @@ where
         RenderEffect::new(move |prev| {
             let value = f().into();
             if let Some(mut state) = prev {
-                let (style, prev): &mut (
+                let (style, _, prev): &mut (
                     R::CssStyleDeclaration,
+                    &str,
                     Cow<'static, str>,
                 ) = &mut state;
                 if &value != prev {
@@ -43,7 +48,7 @@ where
                 if !FROM_SERVER {
                     R::set_css_property(&style, name, &value);
                 }
-                (style.clone(), value)
+                (style.clone(), name, value)
             }
         })
         .into()
@@ -55,8 +60,9 @@ where
         RenderEffect::new(move |prev| {
             let value = f().into();
             if let Some(mut state) = prev {
-                let (style, prev): &mut (
+                let (style, _, prev): &mut (
                     R::CssStyleDeclaration,
+                    &str,
                     Cow<'static, str>,
                 ) = &mut state;
                 if &value != prev {
@@ -67,7 +73,7 @@ where
             } else {
                 // always set the style initially without checking
                 R::set_css_property(&style, name, &value);
-                (style.clone(), value)
+                (style.clone(), name, value)
             }
         })
         .into()
@@ -97,6 +103,13 @@ where
         )
         .into(); */
     }
+
+    fn remove(state: Self::State, _el: &R::Element) {
+        let value = state.0.and_then(|effect| effect.take_value());
+        if let Some((style, name, _)) = value {
+            R::set_css_property(&style, name, "");
+        }
+    }
 }
 
 impl<F, C, R> IntoStyle<R> for F
@@ -137,4 +150,10 @@ where
     }
 
     fn rebuild(self, state: &mut Self::State) {}
+
+    fn remove(state: Self::State, el: &R::Element) {
+        if let Some(value) = state.take_value() {
+            C::remove(value, el);
+        }
+    }
 }
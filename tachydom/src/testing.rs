@@ -0,0 +1,409 @@
+//! Helpers for testing views natively, with the [`MockDom`] renderer.
+//!
+//! Build a view with `MockDom`, find elements in it with [`get`], [`query`]
+//! or [`query_all`], interact with them with [`click`], [`input`] or
+//! [`submit`], and then wait for any tasks that were spawned, including
+//! effects, with [`await_idle`].
+//!
+//! Events are dispatched through the same listeners that [`on`] adds, so
+//! both direct and delegated listeners will run. Native tests can't create
+//! real event objects, so handlers receive an `undefined` event: rather than
+//! reading the event, a handler that needs the current value of an input
+//! should read it from the element.
+//!
+//! [`MockDom`]: crate::renderer::mock_dom::MockDom
+//! [`on`]: crate::html::event::on
+
+use crate::renderer::{
+    mock_dom::{document, poll_tasks, Element, MockDom},
+    CastFrom, Renderer,
+};
+use std::future;
+
+/// A way of finding elements in a [`MockDom`] tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum By {
+    /// Elements with the given tag name.
+    Tag(String),
+    /// Elements that have the attribute set, optionally to a specific value.
+    Attribute(String, Option<String>),
+    /// The innermost elements whose text, with whitespace collapsed, is
+    /// exactly the given text.
+    Text(String),
+    /// Elements with the given ARIA role, either set with the `role`
+    /// attribute or implied by the element itself, like `button` for
+    /// `<button>` or `heading` for `<h1>`.
+    Role(String),
+}
+
+impl By {
+    pub fn tag(tag: impl Into<String>) -> Self {
+        Self::Tag(tag.into())
+    }
+
+    pub fn attribute(name: impl Into<String>) -> Self {
+        Self::Attribute(name.into(), None)
+    }
+
+    pub fn attribute_value(
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        Self::Attribute(name.into(), Some(value.into()))
+    }
+
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    pub fn role(role: impl Into<String>) -> Self {
+        Self::Role(role.into())
+    }
+
+    fn matches(&self, el: &Element) -> bool {
+        match self {
+            By::Tag(tag) => el.tag().eq_ignore_ascii_case(tag),
+            By::Attribute(name, None) => el.attribute(name).is_some(),
+            By::Attribute(name, Some(value)) => {
+                el.attribute(name).as_ref() == Some(value)
+            }
+            By::Text(text) => {
+                normalize(&el.text_content()) == normalize(text)
+                    && !child_elements(el).any(|child| self.matches(&child))
+            }
+            By::Role(role) => role_of(el).as_deref() == Some(role.as_str()),
+        }
+    }
+}
+
+/// Returns every element in `root` (including `root` itself) that matches,
+/// in document order.
+pub fn query_all(root: &Element, by: &By) -> Vec<Element> {
+    let mut found = Vec::new();
+    collect(root, by, &mut found);
+    found
+}
+
+/// Returns the first element in `root` (including `root` itself) that
+/// matches.
+pub fn query(root: &Element, by: &By) -> Option<Element> {
+    query_all(root, by).into_iter().next()
+}
+
+/// Returns the only element in `root` (including `root` itself) that
+/// matches.
+///
+/// ## Panics
+/// Panics, printing the tree, if there is not exactly one matching element.
+#[track_caller]
+pub fn get(root: &Element, by: &By) -> Element {
+    let mut found = query_all(root, by);
+    if found.len() != 1 {
+        panic!(
+            "expected exactly one element matching {by:?}, but found {} in\n{}",
+            found.len(),
+            root.to_debug_html()
+        );
+    }
+    found.remove(0)
+}
+
+fn collect(el: &Element, by: &By, found: &mut Vec<Element>) {
+    if by.matches(el) {
+        found.push(el.clone());
+    }
+    for child in child_elements(el) {
+        collect(&child, by, found);
+    }
+}
+
+fn child_elements(el: &Element) -> impl Iterator<Item = Element> {
+    el.children().into_iter().filter_map(Element::cast_from)
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn role_of(el: &Element) -> Option<String> {
+    if let Some(role) = el.attribute("role") {
+        return role.split_whitespace().next().map(str::to_string);
+    }
+    let role = match el.tag().to_ascii_lowercase().as_str() {
+        "a" | "area" if el.attribute("href").is_some() => "link",
+        "article" => "article",
+        "aside" => "complementary",
+        "button" => "button",
+        "datalist" => "listbox",
+        "details" | "fieldset" => "group",
+        "dialog" => "dialog",
+        "footer" => "contentinfo",
+        "form" => "form",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "header" => "banner",
+        "hr" => "separator",
+        "img" => "img",
+        "input" => match el.attribute("type").as_deref() {
+            Some("checkbox") => "checkbox",
+            Some("radio") => "radio",
+            Some("button" | "image" | "reset" | "submit") => "button",
+            Some("range") => "slider",
+            Some("number") => "spinbutton",
+            Some("search") => "searchbox",
+            Some("hidden") => return None,
+            _ => "textbox",
+        },
+        "li" => "listitem",
+        "main" => "main",
+        "menu" | "ol" | "ul" => "list",
+        "nav" => "navigation",
+        "option" => "option",
+        "progress" => "progressbar",
+        "section" => "region",
+        "select" if el.attribute("multiple").is_some() => "listbox",
+        "select" => "combobox",
+        "table" => "table",
+        "tbody" | "tfoot" | "thead" => "rowgroup",
+        "td" => "cell",
+        "textarea" => "textbox",
+        "th" => "columnheader",
+        "tr" => "row",
+        _ => return None,
+    };
+    Some(role.to_string())
+}
+
+/// Dispatches an event named `name` at `el`.
+pub fn dispatch(el: &Element, name: &str, bubbles: bool) {
+    document().dispatch_event(el, name, bubbles);
+}
+
+/// Clicks `el`.
+///
+/// As in a browser, clicking a disabled element does nothing, and clicking a
+/// submit button also submits its form.
+pub fn click(el: &Element) {
+    if el.attribute("disabled").is_some() {
+        return;
+    }
+    dispatch(el, "click", true);
+
+    let tag = el.tag().to_ascii_lowercase();
+    let ty = el.attribute("type");
+    let submits = match tag.as_str() {
+        "button" => matches!(ty.as_deref(), None | Some("submit")),
+        "input" => matches!(ty.as_deref(), Some("submit" | "image")),
+        _ => false,
+    };
+    if submits {
+        if let Some(form) = closest(el, "form") {
+            submit(&form);
+        }
+    }
+}
+
+/// Sets the `value` of `el`, as if the user had typed it, and dispatches an
+/// `input` event.
+pub fn input(el: &Element, value: &str) {
+    el.set_property("value", value);
+    dispatch(el, "input", true);
+}
+
+/// Dispatches a `submit` event at the form `el`.
+pub fn submit(form: &Element) {
+    dispatch(form, "submit", true);
+}
+
+fn closest(el: &Element, tag: &str) -> Option<Element> {
+    let mut node = MockDom::get_parent(el.as_ref());
+    while let Some(current) = node {
+        if let Some(el) = Element::cast_from(current.clone()) {
+            if el.tag().eq_ignore_ascii_case(tag) {
+                return Some(el);
+            }
+        }
+        node = MockDom::get_parent(&current);
+    }
+    None
+}
+
+/// Runs the tasks that have been spawned while rendering with [`MockDom`]
+/// until each of them has either finished or is waiting for something else
+/// to happen, like a signal to change.
+///
+/// With the `reaccy` feature, this includes the effects and resources
+/// created on this thread after [`MockDom`] was first used on it, so after
+/// an event like [`input`], it waits for the effects that it triggered.
+///
+/// ## Panics
+/// Panics if it is called from inside one of those tasks.
+pub async fn await_idle() {
+    future::poll_fn(poll_tasks).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{await_idle, click, get, input, query, query_all, By};
+    use crate::{
+        html::{
+            attribute::global::{GlobalAttributes, OnAttribute},
+            element::{
+                button, form, h1, input as input_el, main, p, ElementChild,
+            },
+            event,
+        },
+        renderer::{
+            mock_dom::{MockDom, MockSpawn},
+            Renderer,
+        },
+        spawner::Spawner,
+        view::Render,
+    };
+    use std::{
+        cell::Cell,
+        future::Future,
+        pin::Pin,
+        rc::Rc,
+        task::{Context, Poll},
+    };
+
+    #[test]
+    fn queries_find_elements() {
+        let view = main::<MockDom>().child((
+            h1().child("Title"),
+            p().id("first").child("Hello, world!"),
+            p().role("status").child(" Loading  now "),
+            button().child("Go"),
+        ));
+        let root = view.build().el;
+
+        assert_eq!(query_all(&root, &By::tag("p")).len(), 2);
+        assert_eq!(
+            get(&root, &By::attribute_value("id", "first")).text_content(),
+            "Hello, world!"
+        );
+        assert_eq!(get(&root, &By::text("Loading now")).tag(), "p");
+        assert_eq!(get(&root, &By::role("heading")).text_content(), "Title");
+        assert_eq!(get(&root, &By::role("status")).tag(), "p");
+        assert_eq!(get(&root, &By::role("button")).text_content(), "Go");
+        assert!(query(&root, &By::role("link")).is_none());
+    }
+
+    #[test]
+    fn click_runs_listeners() {
+        let clicks = Rc::new(Cell::new(0));
+        let view = main::<MockDom>().child(button().on(event::click, {
+            let clicks = Rc::clone(&clicks);
+            move |_| clicks.set(clicks.get() + 1)
+        }));
+        let root = view.build().el;
+        let button = get(&root, &By::role("button"));
+
+        click(&button);
+        click(&button);
+        assert_eq!(clicks.get(), 2);
+
+        MockDom::set_attribute(&button, "disabled", "");
+        click(&button);
+        assert_eq!(clicks.get(), 2);
+    }
+
+    #[cfg(feature = "delegation")]
+    #[test]
+    fn click_runs_delegated_listeners() {
+        let clicks = Rc::new(Cell::new(0));
+        let view = main::<MockDom>()
+            .on(event::click, {
+                let clicks = Rc::clone(&clicks);
+                move |_| clicks.set(clicks.get() + 1)
+            })
+            .child(button().child("Go"));
+        let root = view.build().el;
+
+        click(&get(&root, &By::role("button")));
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn submit_button_submits_form() {
+        let submitted = Rc::new(Cell::new(false));
+        let view = form::<MockDom>()
+            .on(event::submit, {
+                let submitted = Rc::clone(&submitted);
+                move |_| submitted.set(true)
+            })
+            .child((input_el(), button().child("Send")));
+        let root = view.build().el;
+
+        let text = get(&root, &By::role("textbox"));
+        input(&text, "hello");
+        assert_eq!(text.property("value").as_deref(), Some("hello"));
+
+        click(&get(&root, &By::text("Send")));
+        assert!(submitted.get());
+    }
+
+    #[test]
+    fn await_idle_waits_for_spawned_tasks() {
+        let root = MockDom::create_element(crate::html::element::Main);
+        MockSpawn::spawn_local({
+            let root = root.clone();
+            async move {
+                YieldNow(false).await;
+                let text = MockDom::create_text_node("done");
+                MockDom::insert_node(&root, text.as_ref(), None);
+            }
+        });
+        assert_eq!(root.to_debug_html(), "<main></main>");
+
+        futures::executor::block_on(await_idle());
+        assert_eq!(root.to_debug_html(), "<main>done</main>");
+    }
+
+    #[cfg(feature = "reaccy")]
+    #[test]
+    fn await_idle_runs_effects() {
+        use ::tachy_reaccy::prelude::*;
+
+        let edits = RwSignal::new(0);
+        let view = main::<MockDom>().child((
+            input_el().on(event::input, move |_| edits.update(|n| *n += 1)),
+            p().child(move || edits.get().to_string()),
+        ));
+        // the view's render effects are dropped along with its state
+        let state = view.build();
+        let root = state.el.clone();
+        let _effect = Effect::new({
+            let root = root.clone();
+            move |_| {
+                let edits = edits.get().to_string();
+                MockDom::set_attribute(&root, "data-edits", &edits);
+            }
+        });
+        futures::executor::block_on(await_idle());
+        assert_eq!(root.attribute("data-edits").as_deref(), Some("0"));
+
+        let text = get(&root, &By::role("textbox"));
+        input(&text, "a");
+        input(&text, "ab");
+        futures::executor::block_on(await_idle());
+        assert_eq!(root.attribute("data-edits").as_deref(), Some("2"));
+        assert_eq!(get(&root, &By::tag("p")).text_content(), "2");
+    }
+
+    struct YieldNow(bool);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+}
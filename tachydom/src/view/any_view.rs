@@ -1,8 +1,5 @@
 use super::{Mountable, Position, PositionState, Render, RenderHtml};
-use crate::{
-    hydration::Cursor,
-    renderer::{CastFrom, Renderer},
-};
+use crate::{hydration::Cursor, renderer::Renderer};
 use std::{
     any::{Any, TypeId},
     marker::PhantomData,
@@ -32,9 +29,9 @@ where
     type_id: TypeId,
     state: Box<dyn Any>,
     unmount: fn(&mut dyn Any),
-    mount: fn(&mut dyn Any, parent: &R::Element, marker: Option<&R::Node>),
+    mount: fn(&mut dyn Any, parent: &R::Node, marker: Option<&R::Node>),
     insert_before_this:
-        fn(&dyn Any, parent: &R::Element, child: &mut dyn Mountable<R>) -> bool,
+        fn(&dyn Any, parent: &R::Node, child: &mut dyn Mountable<R>) -> bool,
     rndr: PhantomData<R>,
 }
 
@@ -47,7 +44,7 @@ where
 
 fn mount_any<R, T>(
     state: &mut dyn Any,
-    parent: &R::Element,
+    parent: &R::Node,
     marker: Option<&R::Node>,
 ) where
     T: Render<R>,
@@ -74,7 +71,7 @@ where
 
 fn insert_before_this<R, T>(
     state: &dyn Any,
-    parent: &R::Element,
+    parent: &R::Node,
     child: &mut dyn Mountable<R>,
 ) -> bool
 where
@@ -173,7 +170,7 @@ where
                     .expect("AnyView::rebuild couldn't downcast state");
                 value.rebuild(state);
             } else {
-                let mut new = value.into_any().build();
+                let new = value.into_any().build();
 
                 // TODO mount new state
                 /*R::mount_before(&mut new, state.placeholder.as_ref());*/
@@ -241,13 +238,13 @@ where
         (self.unmount)(&mut *self.state)
     }
 
-    fn mount(&mut self, parent: &R::Element, marker: Option<&R::Node>) {
+    fn mount(&mut self, parent: &R::Node, marker: Option<&R::Node>) {
         (self.mount)(&mut *self.state, parent, marker)
     }
 
     fn insert_before_this(
         &self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool {
         (self.insert_before_this)(self, parent, child)
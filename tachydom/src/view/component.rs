@@ -0,0 +1,121 @@
+use super::{Position, PositionState, Render, RenderHtml, ToTemplate};
+use crate::{
+    html::attribute::{any_attr::AddAnyAttr, Attribute},
+    hydration::Cursor,
+    renderer::Renderer,
+    ssr::StreamBuilder,
+};
+
+/// The view returned by a component, along with the component's name.
+///
+/// This renders exactly like the view itself. While it is being hydrated,
+/// the name is added to the path reported by a
+/// [`HydrationMismatch`](crate::hydration::HydrationMismatch), so that a
+/// mismatch can be traced back to the component that rendered it.
+pub struct ComponentView<T> {
+    name: &'static str,
+    view: T,
+}
+
+impl<T> ComponentView<T> {
+    pub fn new(name: &'static str, view: T) -> Self {
+        Self { name, view }
+    }
+}
+
+impl<T, R> Render<R> for ComponentView<T>
+where
+    T: Render<R>,
+    R: Renderer,
+{
+    type State = T::State;
+
+    fn build(self) -> Self::State {
+        self.view.build()
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.view.rebuild(state)
+    }
+}
+
+impl<T, R> RenderHtml<R> for ComponentView<T>
+where
+    T: RenderHtml<R>,
+    R: Renderer,
+    R::Node: Clone,
+    R::Element: Clone,
+{
+    const MIN_LENGTH: usize = T::MIN_LENGTH;
+
+    fn min_length(&self) -> usize {
+        self.view.min_length()
+    }
+
+    fn to_html_with_buf(self, buf: &mut String, position: &mut Position) {
+        self.view.to_html_with_buf(buf, position)
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+    ) where
+        Self: Sized,
+    {
+        self.view
+            .to_html_async_with_buf::<OUT_OF_ORDER>(buf, position)
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor<R>,
+        position: &PositionState,
+    ) -> Self::State {
+        cursor.enter(self.name);
+        let state = self.view.hydrate::<FROM_SERVER>(cursor, position);
+        cursor.exit();
+        state
+    }
+}
+
+impl<T> ToTemplate for ComponentView<T>
+where
+    T: ToTemplate,
+{
+    const TEMPLATE: &'static str = T::TEMPLATE;
+    const CLASS: &'static str = T::CLASS;
+    const STYLE: &'static str = T::STYLE;
+    const LEN: usize = T::LEN;
+
+    fn to_template(
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        inner_html: &mut String,
+        position: &mut Position,
+    ) {
+        T::to_template(buf, class, style, inner_html, position)
+    }
+}
+
+impl<T, Rndr> AddAnyAttr<Rndr> for ComponentView<T>
+where
+    T: AddAnyAttr<Rndr>,
+    Rndr: Renderer,
+    Rndr::Node: Clone,
+    Rndr::Element: Clone,
+{
+    type Output<SomeNewAttr: Attribute<Rndr>> =
+        ComponentView<T::Output<SomeNewAttr>>;
+
+    fn add_any_attr<NewAttr: Attribute<Rndr>>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr> {
+        ComponentView {
+            name: self.name,
+            view: self.view.add_any_attr(attr),
+        }
+    }
+}
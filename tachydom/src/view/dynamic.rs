@@ -92,7 +92,7 @@ where
 
     fn mount(
         &mut self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         marker: Option<&<R as Renderer>::Node>,
     ) {
         self.with_value_mut(|value| {
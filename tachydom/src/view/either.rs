@@ -1,7 +1,7 @@
 use super::{Mountable, Position, PositionState, Render, RenderHtml};
 use crate::{
     hydration::Cursor,
-    renderer::{CastFrom, Renderer},
+    renderer::Renderer,
     ssr::StreamBuilder,
 };
 
@@ -80,7 +80,7 @@ where
 
     fn mount(
         &mut self,
-        parent: &<Rndr as Renderer>::Element,
+        parent: &<Rndr as Renderer>::Node,
         marker: Option<&<Rndr as Renderer>::Node>,
     ) {
         self.marker.mount(parent, marker);
@@ -96,7 +96,7 @@ where
 
     fn insert_before_this(
         &self,
-        parent: &<Rndr as Renderer>::Element,
+        parent: &<Rndr as Renderer>::Node,
         child: &mut dyn Mountable<Rndr>,
     ) -> bool {
         match &self.state {
@@ -158,8 +158,14 @@ where
             }
         };
         cursor.sibling();
-        let marker = cursor.current().to_owned();
-        let marker = Rndr::Placeholder::cast_from(marker).unwrap();
+        let marker = match cursor.placeholder(Position::NextChild) {
+            Ok(marker) => marker,
+            Err(mismatch) => {
+                let marker = Rndr::create_placeholder();
+                cursor.replace_mismatched(mismatch, marker.as_ref());
+                marker
+            }
+        };
         position.set(Position::NextChild);
         EitherState { state, marker }
     }
@@ -208,7 +214,7 @@ macro_rules! tuples {
 
                 fn mount(
                     &mut self,
-                    parent: &<Rndr as Renderer>::Element,
+                    parent: &<Rndr as Renderer>::Node,
                     marker: Option<&<Rndr as Renderer>::Node>,
                 ) {
                     self.marker.mount(parent, marker);
@@ -219,7 +225,7 @@ macro_rules! tuples {
 
                 fn insert_before_this(
                     &self,
-                    parent: &<Rndr as Renderer>::Element,
+                    parent: &<Rndr as Renderer>::Node,
                     child: &mut dyn Mountable<Rndr>,
                 ) -> bool {
                     match &self.state {
@@ -306,8 +312,14 @@ macro_rules! tuples {
                         $([<EitherOf $num>]::$ty(this) => [<EitherOf $num>]::$ty(this.hydrate::<FROM_SERVER>(cursor, position)),)*
                     };
                     cursor.sibling();
-                    let marker = cursor.current().to_owned();
-                    let marker = Rndr::Placeholder::cast_from(marker).unwrap();
+                    let marker = match cursor.placeholder(Position::NextChild) {
+                        Ok(marker) => marker,
+                        Err(mismatch) => {
+                            let marker = Rndr::create_placeholder();
+                            cursor.replace_mismatched(mismatch, marker.as_ref());
+                            marker
+                        }
+                    };
                     position.set(Position::NextChild);
                     Self::State { marker, state }
                 }
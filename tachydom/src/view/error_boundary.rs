@@ -161,7 +161,7 @@ where
 
     fn mount(
         &mut self,
-        parent: &<Rndr as Renderer>::Element,
+        parent: &<Rndr as Renderer>::Node,
         marker: Option<&<Rndr as Renderer>::Node>,
     ) {
         self.marker.mount(parent, marker);
@@ -177,7 +177,7 @@ where
 
     fn insert_before_this(
         &self,
-        parent: &<Rndr as Renderer>::Element,
+        parent: &<Rndr as Renderer>::Node,
         child: &mut dyn Mountable<Rndr>,
     ) -> bool {
         match &self.state {
@@ -1,10 +1,11 @@
 use super::{Mountable, Position, PositionState, Render, RenderHtml};
 use crate::{
     hydration::Cursor,
-    renderer::{CastFrom, Renderer},
+    renderer::Renderer,
     ssr::StreamBuilder,
 };
 use itertools::Itertools;
+use std::marker::PhantomData;
 
 impl<T, R> Render<R> for Option<T>
 where
@@ -85,13 +86,20 @@ where
         let state = self.map(|s| s.hydrate::<FROM_SERVER>(cursor, position));
 
         // pull the placeholder
-        if position.get() == Position::FirstChild {
+        let curr_position = position.get();
+        if curr_position == Position::FirstChild {
             cursor.child();
         } else {
             cursor.sibling();
         }
-        let placeholder = cursor.current().to_owned();
-        let placeholder = R::Placeholder::cast_from(placeholder).unwrap();
+        let placeholder = match cursor.placeholder(curr_position) {
+            Ok(placeholder) => placeholder,
+            Err(mismatch) => {
+                let placeholder = R::create_placeholder();
+                cursor.replace_mismatched(mismatch, placeholder.as_ref());
+                placeholder
+            }
+        };
         position.set(Position::NextChild);
 
         OptionState { placeholder, state }
@@ -122,7 +130,7 @@ where
         R::remove(self.placeholder.as_ref());
     }
 
-    fn mount(&mut self, parent: &R::Element, marker: Option<&R::Node>) {
+    fn mount(&mut self, parent: &R::Node, marker: Option<&R::Node>) {
         if let Some(ref mut state) = self.state {
             state.mount(parent, marker);
         }
@@ -131,7 +139,7 @@ where
 
     fn insert_before_this(
         &self,
-        parent: &R::Element,
+        parent: &R::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool {
         if self
@@ -219,7 +227,7 @@ where
     R: Renderer,
 {
     states: Vec<T::State>,
-    parent: Option<R::Element>,
+    parent: Option<R::Node>,
     marker: Option<R::Node>,
 }
 
@@ -240,7 +248,7 @@ where
 
     fn mount(
         &mut self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         marker: Option<&<R as Renderer>::Node>,
     ) {
         for state in self.states.iter_mut() {
@@ -252,7 +260,7 @@ where
 
     fn insert_before_this(
         &self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool {
         if let Some(first) = self.states.get(0) {
@@ -304,23 +312,25 @@ where
         position: &PositionState,
     ) -> Self::State {
         // TODO does this make sense for hydration from template?
+        let states = self
+            .into_iter()
+            .map(|child| child.hydrate::<FROM_SERVER>(cursor, position))
+            .collect();
+        let (parent, marker) = cursor.list_bounds(position.get());
         VecState {
-            states: self
-                .into_iter()
-                .map(|child| child.hydrate::<FROM_SERVER>(cursor, position))
-                .collect(),
-            parent: None,
-            marker: None,
+            states,
+            parent,
+            marker,
         }
     }
 }
 
-/*
-
 pub trait IterView<R: Renderer> {
     type Iterator: Iterator<Item = Self::View>;
     type View: Render<R>;
 
+    /// Renders each item of the iterator, like a [`Vec`] of views, without
+    /// collecting it first.
     fn iter_view(self) -> RenderIter<Self::Iterator, Self::View, R>;
 }
 
@@ -356,15 +366,21 @@ where
     I: Iterator<Item = V>,
     V: Render<R>,
     R: Renderer,
+    R::Element: Clone,
+    R::Node: Clone,
 {
-    type State = ();
+    type State = VecState<V, R>;
 
     fn build(self) -> Self::State {
-        todo!()
+        VecState {
+            states: self.inner.map(V::build).collect(),
+            parent: None,
+            marker: None,
+        }
     }
 
     fn rebuild(self, state: &mut Self::State) {
-        todo!()
+        self.inner.collect::<Vec<_>>().rebuild(state)
     }
 }
 
@@ -373,11 +389,26 @@ where
     I: Iterator<Item = V>,
     V: RenderHtml<R>,
     R: Renderer,
+    R::Element: Clone,
     R::Node: Clone,
 {
-    fn to_html(self, buf: &mut String, position: &PositionState) {
-        for mut next in self.0.by_ref() {
-            next.to_html(buf, position);
+    const MIN_LENGTH: usize = 0;
+
+    fn to_html_with_buf(self, buf: &mut String, position: &mut Position) {
+        for child in self.inner {
+            child.to_html_with_buf(buf, position);
+        }
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+    ) where
+        Self: Sized,
+    {
+        for child in self.inner {
+            child.to_html_async_with_buf::<OUT_OF_ORDER>(buf, position);
         }
     }
 
@@ -386,25 +417,99 @@ where
         cursor: &Cursor<R>,
         position: &PositionState,
     ) -> Self::State {
-        todo!()
+        self.inner
+            .collect::<Vec<_>>()
+            .hydrate::<FROM_SERVER>(cursor, position)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::IterView;
-    use crate::view::{Render, RenderHtml};
+    use crate::{
+        html::element::{self, li, ul, ElementChild},
+        renderer::{
+            mock_dom::{Element, MockDom},
+            Renderer,
+        },
+        view::{keyed::keyed, Render, RenderHtml},
+    };
+
+    fn item(text: &'static str) -> impl RenderHtml<MockDom> {
+        li().child(text)
+    }
 
     #[test]
     fn iter_view_takes_iterator() {
-        let strings = vec!["a", "b", "c"];
-        let mut iter_view = strings
+        let iter_view = ["a", "b", "c"]
             .into_iter()
             .map(|n| n.to_ascii_uppercase())
             .iter_view();
-        let mut buf = String::new();
-        iter_view.to_html(&mut buf, &Default::default());
-        assert_eq!(buf, "ABC");
+        let html = RenderHtml::<MockDom>::to_html(iter_view);
+        assert_eq!(html, "A<!>B<!>C");
+    }
+
+    // builds <div><ul><li>...</li>...</ul></div>, as if it had been
+    // rendered on the server
+    fn server_list(items: &[&str]) -> (Element, Element) {
+        let root = MockDom::create_element(element::Div);
+        let list = MockDom::create_element(element::Ul);
+        MockDom::insert_node(&root, list.as_ref(), None);
+        for text in items {
+            let li = MockDom::create_element(element::Li);
+            let text = MockDom::create_text_node(text);
+            MockDom::insert_node(&li, text.as_ref(), None);
+            MockDom::insert_node(&list, li.as_ref(), None);
+        }
+        (root, list)
+    }
+
+    #[test]
+    fn hydrated_iter_view_can_be_rebuilt() {
+        let (root, list) = server_list(&["a", "b", "end"]);
+        let first = MockDom::first_child(list.as_ref());
+
+        let view = |items: Vec<&'static str>| {
+            ul::<MockDom>().child((
+                items.into_iter().map(item).iter_view(),
+                li().child("end"),
+            ))
+        };
+        let mut state = view(vec!["a", "b"]).hydrate_from::<true>(&root);
+        view(vec!["a", "b", "c"]).rebuild(&mut state);
+        assert_eq!(
+            root.to_debug_html(),
+            "<div><ul><li>a</li><li>b</li><li>c</li><li>end</li></ul></div>"
+        );
+        // the hydrated nodes are reused
+        assert_eq!(MockDom::first_child(list.as_ref()), first);
+    }
+
+    #[test]
+    fn hydrated_keyed_list_can_be_diffed() {
+        let (root, list) = server_list(&["a", "b", "end"]);
+        let first = MockDom::first_child(list.as_ref());
+
+        let view = |items: Vec<&'static str>| {
+            ul::<MockDom>()
+                .child((keyed(items, |item| *item, item), li().child("end")))
+        };
+        let mut state = view(vec!["a", "b"]).hydrate_from::<true>(&root);
+        view(vec!["c", "a", "b"]).rebuild(&mut state);
+        assert_eq!(
+            root.to_debug_html(),
+            "<div><ul><li>c</li><li>a</li><li>b</li><li>end</li></ul></div>"
+        );
+        // the hydrated nodes are kept, not rebuilt
+        let second = MockDom::first_child(list.as_ref())
+            .and_then(|c| MockDom::next_sibling(&c));
+        assert_eq!(second, first);
+        view(vec!["a", "b", "d"]).rebuild(&mut state);
+        assert_eq!(
+            root.to_debug_html(),
+            "<div><ul><li>a</li><li>b</li><li>d</li><li>end</li></ul></div>"
+        );
+        view(vec![]).rebuild(&mut state);
+        assert_eq!(root.to_debug_html(), "<div><ul><li>end</li></ul></div>");
     }
 }
-*/
@@ -1,9 +1,5 @@
 use super::{Mountable, Position, PositionState, Render, RenderHtml};
-use crate::{
-    hydration::Cursor,
-    renderer::{CastFrom, Renderer},
-    ssr::StreamBuilder,
-};
+use crate::{hydration::Cursor, renderer::Renderer, ssr::StreamBuilder};
 use drain_filter_polyfill::VecExt as VecDrainFilterExt;
 use indexmap::IndexSet;
 use rustc_hash::FxHasher;
@@ -56,7 +52,7 @@ where
     V: Render<Rndr>,
     Rndr: Renderer,
 {
-    parent: Option<Rndr::Element>,
+    parent: Option<Rndr::Node>,
     marker: Option<Rndr::Node>,
     hashed_items: IndexSet<K, BuildHasherDefault<FxHasher>>,
     rendered_items: Vec<Option<V::State>>,
@@ -71,6 +67,7 @@ where
     VF: Fn(T) -> V,
     Rndr: Renderer,
     Rndr::Element: Clone,
+    Rndr::Node: Clone,
 {
     type State = KeyedState<K, V, Rndr>;
 
@@ -167,18 +164,7 @@ where
         cursor: &Cursor<Rndr>,
         position: &PositionState,
     ) -> Self::State {
-        // get parent and position
-        let current = cursor.current();
-        let parent = if position.get() == Position::FirstChild {
-            current
-        } else {
-            Rndr::get_parent(&current)
-                .expect("first child of keyed list has no parent")
-        };
-        let parent = Rndr::Element::cast_from(parent)
-            .expect("parent of keyed list should be an element");
-
-        // build list
+        // hydrate list
         let items = self.items.into_iter();
         let (capacity, _) = items.size_hint();
         let mut hashed_items =
@@ -190,9 +176,13 @@ where
             let item = view.hydrate::<FROM_SERVER>(cursor, position);
             rendered_items.push(Some(item));
         }
+
+        // the hydrated nodes are kept as they are, so the next rebuild only
+        // needs to know where to insert any new items
+        let (parent, marker) = cursor.list_bounds(position.get());
         KeyedState {
-            parent: Some(parent),
-            marker: None, // TODO?
+            parent,
+            marker,
             hashed_items,
             rendered_items,
         }
@@ -205,8 +195,9 @@ where
     V: Render<Rndr>,
     Rndr: Renderer,
     Rndr::Element: Clone,
+    Rndr::Node: Clone,
 {
-    fn mount(&mut self, parent: &Rndr::Element, marker: Option<&Rndr::Node>) {
+    fn mount(&mut self, parent: &Rndr::Node, marker: Option<&Rndr::Node>) {
         self.parent = Some(parent.clone());
         for item in self.rendered_items.iter_mut().flatten() {
             item.mount(parent, marker);
@@ -221,7 +212,7 @@ where
 
     fn insert_before_this(
         &self,
-        parent: &<Rndr as Renderer>::Element,
+        parent: &<Rndr as Renderer>::Node,
         child: &mut dyn Mountable<Rndr>,
     ) -> bool {
         self.rendered_items
@@ -411,7 +402,7 @@ impl Default for DiffOpAddMode {
 }
 
 fn apply_diff<T, V, Rndr>(
-    parent: &Rndr::Element,
+    parent: &Rndr::Node,
     marker: &Option<Rndr::Node>,
     diff: Diff,
     children: &mut Vec<Option<V::State>>,
@@ -430,9 +421,11 @@ fn apply_diff<T, V, Rndr>(
     // 6. Additions
     // 7. Removes holes
     if diff.clear {
-        // TODO fix
-        Rndr::clear_children(parent);
-        children.clear();
+        // the list may share its parent with other nodes, so only its own
+        // items can be removed
+        for mut child in children.drain(..).flatten() {
+            child.unmount();
+        }
 
         if diff.added.is_empty() {
             return;
@@ -503,7 +496,7 @@ fn apply_diff<T, V, Rndr>(
                 }
             }
             DiffOpAddMode::Append => {
-                item.mount(parent, None);
+                item.mount(parent, marker.as_ref());
             }
         }
 
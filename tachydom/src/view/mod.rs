@@ -3,10 +3,12 @@ use parking_lot::RwLock;
 use std::sync::Arc;
 
 pub mod any_view;
+pub mod component;
 pub mod either;
 pub mod error_boundary;
 pub mod iterators;
 pub mod keyed;
+pub mod portal;
 mod primitives;
 #[cfg(feature = "nightly")]
 pub mod static_types;
@@ -55,7 +57,7 @@ where
 pub struct NeverError;
 
 impl core::fmt::Display for NeverError {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn fmt(&self, _f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         Ok(())
     }
 }
@@ -135,7 +137,19 @@ where
     where
         Self: Sized,
     {
-        let mut builder = StreamBuilder::new(Some(vec![0]));
+        self.to_html_stream_out_of_order_with_nonce(None)
+    }
+
+    /// Renders a view to an out-of-order stream of HTML, adding the given
+    /// CSP nonce to the inline scripts that swap each fragment into place.
+    fn to_html_stream_out_of_order_with_nonce(
+        self,
+        nonce: Option<Arc<str>>,
+    ) -> StreamBuilder
+    where
+        Self: Sized,
+    {
+        let mut builder = StreamBuilder::new(Some(vec![0])).with_nonce(nonce);
         self.to_html_async_with_buf::<true>(
             &mut builder,
             &mut Position::FirstChild,
@@ -212,7 +226,7 @@ where
     where
         Self: Sized,
     {
-        let cursor = Cursor::new(el.clone());
+        let cursor = Cursor::new(el.as_ref().clone());
         let position = PositionState::new(position);
         self.hydrate::<FROM_SERVER>(&cursor, &position)
     }
@@ -224,13 +238,13 @@ pub trait Mountable<R: Renderer> {
     fn unmount(&mut self);
 
     /// Mounts a node to the interface.
-    fn mount(&mut self, parent: &R::Element, marker: Option<&R::Node>);
+    fn mount(&mut self, parent: &R::Node, marker: Option<&R::Node>);
 
     /// Inserts another `Mountable` type before this one. Returns `false` if
     /// this does not actually exist in the UI (for example, `()`).
     fn insert_before_this(
         &self,
-        parent: &R::Element,
+        parent: &R::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool;
 
@@ -238,7 +252,7 @@ pub trait Mountable<R: Renderer> {
     /// if this one doesn't exist in the UI (for example, `()`).
     fn insert_before_this_or_marker(
         &self,
-        parent: &R::Element,
+        parent: &R::Node,
         child: &mut dyn Mountable<R>,
         marker: Option<&R::Node>,
     ) {
@@ -270,7 +284,7 @@ where
         }
     }
 
-    fn mount(&mut self, parent: &R::Element, marker: Option<&R::Node>) {
+    fn mount(&mut self, parent: &R::Node, marker: Option<&R::Node>) {
         if let Some(ref mut inner) = self {
             inner.mount(parent, marker);
         }
@@ -278,7 +292,7 @@ where
 
     fn insert_before_this(
         &self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool {
         self.as_ref()
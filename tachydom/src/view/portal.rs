@@ -0,0 +1,245 @@
+use super::{Mountable, Position, PositionState, Render, RenderHtml};
+use crate::{
+    hydration::Cursor,
+    renderer::{dom::Dom, Renderer},
+};
+use std::marker::PhantomData;
+
+/// Renders a view into a different element than its parent, such as the
+/// `<body>`, which is useful for modals, toasts and tooltips.
+///
+/// The view is still built where the portal is, so it is owned by the same
+/// reactive `Owner` as the rest of the component, and it is removed from the
+/// target when the portal is unmounted.
+///
+/// A placeholder is rendered where the portal is. On the server, nothing
+/// else is rendered, and the view is rendered on the client when the
+/// placeholder is hydrated.
+pub struct Portal<R, V>
+where
+    R: Renderer,
+{
+    target: Box<dyn FnOnce() -> R::Element>,
+    view: V,
+    rndr: PhantomData<R>,
+}
+
+impl<R, V> Portal<R, V>
+where
+    R: Renderer,
+{
+    /// Creates a portal that mounts `view` into the element returned by
+    /// `target`, which is only called when the view is rendered on the
+    /// client.
+    pub fn new(target: impl FnOnce() -> R::Element + 'static, view: V) -> Self {
+        Self {
+            target: Box::new(target),
+            view,
+            rndr: PhantomData,
+        }
+    }
+}
+
+impl<V> Portal<Dom, V> {
+    /// Creates a portal that mounts `view` into the document's `<body>`.
+    pub fn body(view: V) -> Self {
+        Self::new(|| crate::dom::body().into(), view)
+    }
+
+    /// Creates a portal that mounts `view` into the element with the given
+    /// `id`.
+    ///
+    /// ## Panics
+    /// Panics when the view is rendered if there is no element with the
+    /// `id`.
+    pub fn into_id(id: impl Into<String>, view: V) -> Self {
+        let id = id.into();
+        Self::new(
+            move || {
+                crate::dom::document()
+                    .get_element_by_id(&id)
+                    .unwrap_or_else(|| {
+                        panic!("no element with the id {id:?} for the portal")
+                    })
+            },
+            view,
+        )
+    }
+}
+
+pub struct PortalState<R, S>
+where
+    R: Renderer,
+{
+    target: R::Element,
+    placeholder: R::Placeholder,
+    state: S,
+}
+
+impl<R, V> Render<R> for Portal<R, V>
+where
+    R: Renderer,
+    V: Render<R>,
+{
+    type State = PortalState<R, V::State>;
+
+    fn build(self) -> Self::State {
+        let placeholder = R::create_placeholder();
+        let target = (self.target)();
+        let state = self.view.build();
+        PortalState {
+            target,
+            placeholder,
+            state,
+        }
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.view.rebuild(&mut state.state);
+    }
+}
+
+impl<R, V> RenderHtml<R> for Portal<R, V>
+where
+    R: Renderer,
+    R::Node: Clone,
+    R::Element: Clone,
+    V: RenderHtml<R>,
+{
+    const MIN_LENGTH: usize = 3; // <!>
+
+    fn to_html_with_buf(self, buf: &mut String, position: &mut Position) {
+        buf.push_str("<!>");
+        *position = Position::NextChild;
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor<R>,
+        position: &PositionState,
+    ) -> Self::State {
+        let curr_position = position.get();
+        if curr_position == Position::FirstChild {
+            cursor.child();
+        } else if curr_position != Position::Current {
+            cursor.sibling();
+        }
+        let placeholder = match cursor.placeholder(curr_position) {
+            Ok(placeholder) => placeholder,
+            Err(mismatch) => {
+                let placeholder = R::create_placeholder();
+                cursor.replace_mismatched(mismatch, placeholder.as_ref());
+                placeholder
+            }
+        };
+        position.set(Position::NextChild);
+
+        // the view was not rendered on the server, so it is rendered now
+        let target = (self.target)();
+        let mut state = self.view.build();
+        state.mount(target.as_ref(), None);
+        PortalState {
+            target,
+            placeholder,
+            state,
+        }
+    }
+}
+
+impl<R, S> Mountable<R> for PortalState<R, S>
+where
+    R: Renderer,
+    S: Mountable<R>,
+{
+    fn unmount(&mut self) {
+        self.placeholder.unmount();
+        self.state.unmount();
+    }
+
+    fn mount(&mut self, parent: &R::Node, marker: Option<&R::Node>) {
+        self.placeholder.mount(parent, marker);
+        self.state.mount(self.target.as_ref(), None);
+    }
+
+    fn insert_before_this(
+        &self,
+        parent: &R::Node,
+        child: &mut dyn Mountable<R>,
+    ) -> bool {
+        self.placeholder.insert_before_this(parent, child)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Portal;
+    use crate::{
+        html::element::{self, main, p, ElementChild},
+        renderer::{mock_dom::MockDom, Renderer},
+        view::{Mountable, Render, RenderHtml},
+    };
+
+    #[test]
+    fn portal_renders_into_target() {
+        let target = MockDom::create_element(element::Aside);
+        let view = main::<MockDom>().child((
+            p().child("Page"),
+            Portal::new(
+                {
+                    let target = target.clone();
+                    move || target
+                },
+                p().child("Modal"),
+            ),
+        ));
+        let mut state = view.build();
+        assert_eq!(state.el.to_debug_html(), "<main><p>Page</p><!></main>");
+        assert_eq!(target.to_debug_html(), "<aside><p>Modal</p></aside>");
+
+        state.children.1.unmount();
+        assert_eq!(state.el.to_debug_html(), "<main><p>Page</p></main>");
+        assert_eq!(target.to_debug_html(), "<aside></aside>");
+    }
+
+    #[test]
+    fn portal_renders_placeholder_on_server() {
+        let view = main::<MockDom>().child((
+            Portal::new(
+                || MockDom::create_element(element::Aside),
+                p().child("Modal"),
+            ),
+            p().child("Page"),
+        ));
+        assert_eq!(view.to_html(), "<main><!><p>Page</p></main>");
+    }
+
+    #[test]
+    fn portal_is_rendered_when_hydrated() {
+        // builds <div><main><!><p>Page</p></main></div>, as if it had been
+        // rendered on the server
+        let root = MockDom::create_element(element::Div);
+        let main_el = MockDom::create_element(element::Main);
+        let placeholder = MockDom::create_placeholder();
+        let page = MockDom::create_element(element::P);
+        let text = MockDom::create_text_node("Page");
+        MockDom::insert_node(&root, main_el.as_ref(), None);
+        MockDom::insert_node(&main_el, placeholder.as_ref(), None);
+        MockDom::insert_node(&main_el, page.as_ref(), None);
+        MockDom::insert_node(&page, text.as_ref(), None);
+
+        let target = MockDom::create_element(element::Aside);
+        let view = main::<MockDom>().child((
+            Portal::new(
+                {
+                    let target = target.clone();
+                    move || target
+                },
+                p().child("Modal"),
+            ),
+            p().child("Page"),
+        ));
+        let state = view.hydrate_from::<true>(&root);
+        assert_eq!(state.children.1.el, page);
+        assert_eq!(target.to_debug_html(), "<aside><p>Modal</p></aside>");
+    }
+}
@@ -1,11 +1,7 @@
 use super::{
     InfallibleRender, Mountable, Position, PositionState, Render, RenderHtml,
 };
-use crate::{
-    hydration::Cursor,
-    renderer::{CastFrom, Renderer},
-    view::ToTemplate,
-};
+use crate::{hydration::Cursor, renderer::Renderer, view::ToTemplate};
 use std::{
     fmt::Write,
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
@@ -29,7 +25,7 @@ macro_rules! render_primitive {
 
 					fn mount(
 						&mut self,
-						parent: &<R as Renderer>::Element,
+						parent: &<R as Renderer>::Node,
 						marker: Option<&<R as Renderer>::Node>,
 					) {
 						R::insert_node(parent, self.0.as_ref(), marker);
@@ -37,7 +33,7 @@ macro_rules! render_primitive {
 
 					fn insert_before_this(
 						&self,
-						parent: &<R as Renderer>::Element,
+						parent: &<R as Renderer>::Node,
 						child: &mut dyn Mountable<R>,
 					) -> bool {
 						child.mount(parent, Some(self.0.as_ref()));
@@ -77,7 +73,7 @@ macro_rules! render_primitive {
 					if matches!(position, Position::NextChildAfterText) {
 						buf.push_str("<!>")
 					}
-					write!(buf, "{}", self);
+					_ = write!(buf, "{}", self);
 					*position = Position::NextChildAfterText;
 				}
 
@@ -97,9 +93,14 @@ macro_rules! render_primitive {
 						cursor.sibling();
 					}
 
-					let node = cursor.current();
-					let node = R::Text::cast_from(node)
-						.expect("couldn't cast text node from node");
+					let node = match cursor.text(position.get()) {
+						Ok(node) => node,
+						Err(mismatch) => {
+							let node = R::create_text_node(&self.to_string());
+							cursor.replace_mismatched(mismatch, node.as_ref());
+							node
+						}
+					};
 
 					if !FROM_SERVER {
 						R::set_text(&node, &self.to_string());
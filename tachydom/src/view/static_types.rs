@@ -91,6 +91,10 @@ where
     }
 
     fn rebuild(self, _state: &mut Self::State) {}
+
+    fn remove(_state: Self::State, el: &R::Element) {
+        R::remove_attribute(el, K::KEY);
+    }
 }
 
 #[derive(Debug)]
@@ -2,10 +2,7 @@ use super::{
     InfallibleRender, Mountable, Position, PositionState, Render, RenderHtml,
     ToTemplate,
 };
-use crate::{
-    hydration::Cursor,
-    renderer::{CastFrom, Renderer},
-};
+use crate::{hydration::Cursor, renderer::Renderer};
 use std::{rc::Rc, sync::Arc};
 
 pub struct StrState<'a, R: Renderer> {
@@ -65,9 +62,14 @@ where
             cursor.sibling();
         }
 
-        let node = cursor.current();
-        let node = R::Text::cast_from(node)
-            .expect("couldn't cast text node from node");
+        let node = match cursor.text(position.get()) {
+            Ok(node) => node,
+            Err(mismatch) => {
+                let node = R::create_text_node(self);
+                cursor.replace_mismatched(mismatch, node.as_ref());
+                node
+            }
+        };
 
         if !FROM_SERVER {
             R::set_text(&node, self);
@@ -106,7 +108,7 @@ where
 
     fn mount(
         &mut self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         marker: Option<&<R as Renderer>::Node>,
     ) {
         R::insert_node(parent, self.node.as_ref(), marker);
@@ -114,7 +116,7 @@ where
 
     fn insert_before_this(
         &self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool {
         child.mount(parent, Some(self.node.as_ref()));
@@ -191,7 +193,7 @@ impl<R: Renderer> Mountable<R> for StringState<R> {
 
     fn mount(
         &mut self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         marker: Option<&<R as Renderer>::Node>,
     ) {
         R::insert_node(parent, self.node.as_ref(), marker);
@@ -199,7 +201,7 @@ impl<R: Renderer> Mountable<R> for StringState<R> {
 
     fn insert_before_this(
         &self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool {
         child.mount(parent, Some(self.node.as_ref()));
@@ -278,7 +280,7 @@ impl<R: Renderer> Mountable<R> for RcStrState<R> {
 
     fn mount(
         &mut self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         marker: Option<&<R as Renderer>::Node>,
     ) {
         R::insert_node(parent, self.node.as_ref(), marker);
@@ -286,7 +288,7 @@ impl<R: Renderer> Mountable<R> for RcStrState<R> {
 
     fn insert_before_this(
         &self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool {
         child.mount(parent, Some(self.node.as_ref()));
@@ -365,7 +367,7 @@ impl<R: Renderer> Mountable<R> for ArcStrState<R> {
 
     fn mount(
         &mut self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         marker: Option<&<R as Renderer>::Node>,
     ) {
         R::insert_node(parent, self.node.as_ref(), marker);
@@ -373,7 +375,7 @@ impl<R: Renderer> Mountable<R> for ArcStrState<R> {
 
     fn insert_before_this(
         &self,
-        parent: &<R as Renderer>::Element,
+        parent: &<R as Renderer>::Node,
         child: &mut dyn Mountable<R>,
     ) -> bool {
         child.mount(parent, Some(self.node.as_ref()));
@@ -65,10 +65,8 @@ where
     fn build(self) -> Self::State {
         let tpl = Self::to_template();
         let contents = tpl.content().clone_node_with_deep(true).unwrap();
-        self.view.hydrate::<false>(
-            &Cursor::new(contents.unchecked_into()),
-            &Default::default(),
-        )
+        self.view
+            .hydrate::<false>(&Cursor::new(contents), &Default::default())
     }
 
     fn rebuild(self, state: &mut Self::State) {
@@ -42,11 +42,11 @@ where
 impl<R: Renderer> Mountable<R> for () {
     fn unmount(&mut self) {}
 
-    fn mount(&mut self, _parent: &R::Element, _marker: Option<&R::Node>) {}
+    fn mount(&mut self, _parent: &R::Node, _marker: Option<&R::Node>) {}
 
     fn insert_before_this(
         &self,
-        _parent: &<R as Renderer>::Element,
+        _parent: &<R as Renderer>::Node,
         _child: &mut dyn Mountable<R>,
     ) -> bool {
         false
@@ -285,7 +285,7 @@ macro_rules! impl_view_for_tuples {
 
 			fn mount(
 				&mut self,
-				parent: &Rndr::Element,
+				parent: &Rndr::Node,
 				marker: Option<&Rndr::Node>,
 			) {
 				paste::paste! {
@@ -297,7 +297,7 @@ macro_rules! impl_view_for_tuples {
 
 			fn insert_before_this(
 				&self,
-				parent: &Rndr::Element,
+				parent: &Rndr::Node,
 				child: &mut dyn Mountable<Rndr>,
 			) -> bool {
 				paste::paste! {
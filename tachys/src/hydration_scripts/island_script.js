@@ -6,20 +6,88 @@
 			c();
 		}
 	}
+	// calls `hydrate` once the island's trigger (its `data-hydrate`) fires;
+	// until then, the island is left as it was rendered on the server
+	function schedule(e, hydrate) {
+		switch (e.dataset.hydrate) {
+			case "idle":
+				idle(hydrate);
+				break;
+			case "visible": {
+				const observer = new IntersectionObserver(entries => {
+					if (entries.some(entry => entry.isIntersecting)) {
+						observer.disconnect();
+						hydrate();
+					}
+				});
+				observer.observe(e);
+				break;
+			}
+			case "interaction": {
+				const events = ["pointerenter", "pointerdown", "focusin"];
+				const listener = () => {
+					for (let name of events) {
+						e.removeEventListener(name, listener);
+					}
+					hydrate();
+				};
+				for (let name of events) {
+					e.addEventListener(name, listener);
+				}
+				break;
+			}
+			case "media": {
+				const query = window.matchMedia(e.dataset.hydrateMedia);
+				if (query.matches) {
+					hydrate();
+				} else {
+					const listener = () => {
+						if (query.matches) {
+							query.removeEventListener("change", listener);
+							hydrate();
+						}
+					};
+					query.addEventListener("change", listener);
+				}
+				break;
+			}
+			default:
+				hydrate();
+		}
+	}
+	// islands that have already been hydrated, or are waiting for their
+	// trigger, so that they aren't hydrated again when a page is swapped in
+	const seen = new WeakSet();
+	function hydrateIslands(mod, root) {
+		for (let e of root.querySelectorAll("leptos-island")) {
+			if (seen.has(e)) {
+				continue;
+			}
+			// an island inside another island's view is hydrated along
+			// with it; one inside server-rendered `<leptos-children>`
+			// is hydrated on its own
+			const parent = e.parentElement?.closest("leptos-island, leptos-children");
+			if (parent?.tagName === "LEPTOS-ISLAND") {
+				continue;
+			}
+			seen.add(e);
+			const l = e.dataset.component;
+			const islandFn = mod["_island_" + l];
+			if (islandFn) {
+				schedule(e, () => islandFn(e));
+			} else {
+				console.warn(`Could not find WASM function for the island ${l}.`);
+			}
+		}
+	}
 	idle(() => {
 		import(`/${pkg_path}/${output_name}.js`)
 			.then(mod => {
 				mod.default(`/${pkg_path}/${wasm_output_name}.wasm`).then(() => {
 					mod.hydrate();
-					for (let e of document.querySelectorAll("leptos-island")) {
-						const l = e.dataset.component;
-						const islandFn = mod["_island_" + l];
-						if (islandFn) {
-							islandFn(e);
-						} else {
-							console.warn(`Could not find WASM function for the island ${l}.`);
-						}
-					}
+					hydrateIslands(mod, document);
+					// used to hydrate new islands after client-side navigation
+					window.__hydrateIslands = root => hydrateIslands(mod, root);
 				});
 			})
 	});
@@ -1,6 +1,6 @@
 #![allow(clippy::needless_lifetimes)]
 
-use crate::prelude::*;
+use crate::{nonce::use_nonce, prelude::*};
 use leptos_config::LeptosOptions;
 use tachydom::view::RenderHtml;
 
@@ -20,9 +20,12 @@ pub fn AutoReload<'a>(
             leptos_config::ReloadWSProtocol::WSS => "'wss://'",
         };
 
+        let nonce = nonce
+            .map(String::from)
+            .or_else(|| use_nonce().map(|nonce| nonce.to_string()));
         let script = include_str!("reload_script.js");
         view! {
-            <script crossorigin=nonce>
+            <script nonce=nonce>
                 {format!("{script}({reload_port:?}, {protocol})")}
             </script>
         }
@@ -40,7 +43,10 @@ pub fn HydrationScripts(
     if std::option_env!("LEPTOS_OUTPUT_NAME").is_none() {
         wasm_output_name.push_str("_bg");
     }
-    let nonce = None::<String>; // use_nonce(); // TODO
+    let nonce = use_nonce().map(|nonce| nonce.to_string());
+    // defines the registry that streamed resources are sent to; this is not
+    // a module, so it runs before any of those scripts
+    let resource_script = include_str!("./resource_script.js");
     let script = if islands {
         include_str!("./island_script.js")
     } else {
@@ -50,6 +56,9 @@ pub fn HydrationScripts(
     view! {
         <link rel="modulepreload" href=format!("/{pkg_path}/{output_name}.js") nonce=nonce.clone()/>
         <link rel="preload" href=format!("/{pkg_path}/{wasm_output_name}.wasm") r#as="fetch" r#type="application/wasm" crossorigin=nonce.clone().unwrap_or_default()/>
+        <script nonce=nonce.clone()>
+            {format!("{resource_script}()")}
+        </script>
         <script type="module" nonce=nonce>
             {format!("{script}({pkg_path:?}, {output_name:?}, {wasm_output_name:?})")}
         </script>
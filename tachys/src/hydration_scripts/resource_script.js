@@ -0,0 +1,29 @@
+(function () {
+	// values of resources that have been sent from the server, by ID
+	window.__RESOLVED_RESOURCES = window.__RESOLVED_RESOURCES || [];
+	// IDs of resources that the server will send in a later chunk
+	window.__PENDING_RESOURCES = window.__PENDING_RESOURCES || [];
+	// callbacks waiting for a pending resource, by ID
+	window.__RESOURCE_RESOLVERS = window.__RESOURCE_RESOLVERS || [];
+	// serialized state that is shared between islands, by name
+	window.__SHARED_STATE = window.__SHARED_STATE || {};
+
+	window.__resolveResource = function (id, data) {
+		__RESOLVED_RESOURCES[id] = data;
+		const resolvers = __RESOURCE_RESOLVERS[id];
+		if (resolvers) {
+			delete __RESOURCE_RESOLVERS[id];
+			for (const resolve of resolvers) {
+				resolve(data);
+			}
+		}
+	};
+
+	window.__awaitResource = function (id, resolve) {
+		if (id in __RESOLVED_RESOURCES) {
+			resolve(__RESOLVED_RESOURCES[id]);
+		} else {
+			(__RESOURCE_RESOLVERS[id] = __RESOURCE_RESOLVERS[id] || []).push(resolve);
+		}
+	};
+})
@@ -0,0 +1,146 @@
+//! Serializes the props of an island when it is rendered on the server, and
+//! deserializes them again when the island is hydrated.
+//!
+//! Each prop is serialized separately with the island's [`Serializer`], so
+//! every prop type must implement [`SerializableData`] for it.
+//!
+//! Islands can also share state with each other, with [`shared_signal`].
+
+use std::{any::Any, fmt::Debug, sync::Arc};
+use tachy_reaccy::{
+    serialization::{SerializableData, Serializer},
+    shared_context::{SharedContext, SharedState},
+    signal::ArcRwSignal,
+    signal_traits::SignalWithUntracked,
+    Owner,
+};
+
+/// Returns the signal that the islands on a page share under `name`,
+/// creating it with `init` if no island has used it yet.
+///
+/// Every island that calls this with the same `name` gets the same signal,
+/// so a change made by one of them is seen by all of them. The value is
+/// serialized with `Ser` on the server, and the signal is created with that
+/// value in the browser, rather than calling `init` again.
+///
+/// ## Panics
+/// Panics if the signal was created with a different type.
+pub fn shared_signal<T, Ser>(
+    name: &'static str,
+    init: impl FnOnce() -> T,
+) -> ArcRwSignal<T>
+where
+    T: SerializableData<Ser> + Send + Sync + 'static,
+    T::DeErr: Debug,
+    Ser: Serializer,
+{
+    let mut init = Some(init);
+    let mut create = |data: Option<String>| {
+        let value = data
+            .and_then(|data| match T::de(&data) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    tachy_reaccy::log(&format!(
+                        "couldn't deserialize the shared state `{name}`: \
+                         {e:?}"
+                    ));
+                    None
+                }
+            })
+            .unwrap_or_else(|| (init.take().unwrap())());
+        let signal = ArcRwSignal::new(value);
+        SharedState {
+            value: Arc::new(signal.clone()),
+            serialize: Box::new(move || {
+                signal
+                    .try_with_untracked(|value| value.ser().ok())
+                    .flatten()
+            }),
+        }
+    };
+
+    let value = match Owner::shared_context() {
+        Some(sc) => sc.shared_state(name, &mut create),
+        None => local_shared_state(name, &mut create),
+    };
+    match value.downcast_ref::<ArcRwSignal<T>>() {
+        Some(signal) => signal.clone(),
+        None => panic!(
+            "the shared state `{name}` was created with a different type"
+        ),
+    }
+}
+
+// islands that are hydrated outside of a root still share the page's state
+#[cfg(feature = "web")]
+fn local_shared_state(
+    name: &'static str,
+    init: &mut dyn FnMut(Option<String>) -> SharedState,
+) -> Arc<dyn Any + Send + Sync> {
+    tachy_reaccy::shared_context::HydrateSharedContext::new_islands()
+        .shared_state(name, init)
+}
+
+#[cfg(not(feature = "web"))]
+fn local_shared_state(
+    name: &'static str,
+    init: &mut dyn FnMut(Option<String>) -> SharedState,
+) -> Arc<dyn Any + Send + Sync> {
+    use std::{cell::RefCell, collections::HashMap};
+
+    thread_local! {
+        static SHARED_STATE: RefCell<HashMap<&'static str, Arc<dyn Any + Send + Sync>>> =
+            Default::default();
+    }
+
+    let existing = SHARED_STATE.with(|state| state.borrow().get(name).cloned());
+    existing.unwrap_or_else(|| {
+        let value = init(None).value;
+        SHARED_STATE
+            .with(|state| state.borrow_mut().insert(name, Arc::clone(&value)));
+        value
+    })
+}
+
+/// Serializes the prop `name` of an island.
+///
+/// ## Panics
+/// Panics if the prop can't be serialized.
+#[doc(hidden)]
+pub fn serialize_prop<Ser, T>(
+    name: &'static str,
+    prop: &T,
+) -> (&'static str, String)
+where
+    Ser: Serializer,
+    T: SerializableData<Ser>,
+    T::SerErr: Debug,
+{
+    match prop.ser() {
+        Ok(data) => (name, data),
+        Err(e) => panic!("couldn't serialize the island prop `{name}`: {e:?}"),
+    }
+}
+
+/// Deserializes the prop `name` of an island from the value of its
+/// attribute.
+///
+/// ## Panics
+/// Panics if the attribute is missing or can't be deserialized.
+#[doc(hidden)]
+pub fn deserialize_prop<Ser, T>(name: &str, data: Option<String>) -> T
+where
+    Ser: Serializer,
+    T: SerializableData<Ser>,
+    T::DeErr: Debug,
+{
+    let Some(data) = data else {
+        panic!("the island prop `{name}` was not serialized");
+    };
+    match T::de(&data) {
+        Ok(prop) => prop,
+        Err(e) => {
+            panic!("couldn't deserialize the island prop `{name}`: {e:?}")
+        }
+    }
+}
@@ -1,5 +1,5 @@
 pub mod prelude {
-    pub use tachy_maccy::{component, view};
+    pub use tachy_maccy::{component, slot, view};
     pub use tachy_reaccy::prelude::*;
     pub use tachydom::prelude::*;
 }
@@ -10,6 +10,8 @@ pub mod children; // TODO fix children
 pub mod component;
 mod for_loop;
 mod hydration_scripts;
+pub mod islands;
+pub mod nonce;
 mod show;
 pub use for_loop::*;
 pub use hydration_scripts::*;
@@ -0,0 +1,133 @@
+//! [Content Security Policy](https://developer.mozilla.org/en-US/docs/Web/HTTP/CSP)
+//! nonces for the inline scripts and styles rendered on the server.
+//!
+//! The server integration creates a [`Nonce`] for each request, provides it
+//! through context, and adds it to the `Content-Security-Policy` header:
+//!
+//! ```rust,ignore
+//! let nonce = Nonce::new();
+//! provide_context(nonce.clone());
+//! let header = format!("script-src 'nonce-{nonce}'");
+//!
+//! // the nonce is added to the scripts for out-of-order streaming...
+//! let stream = app.to_html_stream_out_of_order_with_nonce(Some(nonce.to_arc()));
+//! // ...and to the scripts that send resources to the client
+//! let resources = shared_context
+//!     .pending_data()
+//!     .unwrap()
+//!     .map(move |chunk| inline_script(Some(&nonce), &chunk));
+//! ```
+//!
+//! [`HydrationScripts`](crate::HydrationScripts) and
+//! [`AutoReload`](crate::AutoReload) use the nonce from context, if any. It
+//! can also be added to your own inline scripts and styles:
+//!
+//! ```rust,ignore
+//! view! { <style nonce=use_nonce()>"p { color: red; }"</style> }
+//! ```
+
+use std::{fmt::Display, ops::Deref, sync::Arc};
+use tachy_reaccy::context::use_context;
+use tachydom::{html::attribute::AttributeValue, renderer::Renderer};
+
+/// A nonce, which allows an inline script or style to run under a Content
+/// Security Policy.
+///
+/// A new nonce should be created for each request.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Nonce(Arc<str>);
+
+impl Nonce {
+    /// Creates a new random nonce.
+    #[cfg(feature = "nonce")]
+    pub fn new() -> Self {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        Self(STANDARD.encode(rand::random::<[u8; 16]>()).into())
+    }
+
+    /// Returns the shared string, e.g., to pass to
+    /// [`to_html_stream_out_of_order_with_nonce`](tachydom::view::RenderHtml::to_html_stream_out_of_order_with_nonce).
+    pub fn to_arc(&self) -> Arc<str> {
+        Arc::clone(&self.0)
+    }
+}
+
+#[cfg(feature = "nonce")]
+impl Default for Nonce {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Uses a nonce created elsewhere, such as by a proxy that sets the
+/// `Content-Security-Policy` header.
+///
+/// The nonce is written into HTML attributes as it is, so it should only
+/// contain base64 characters.
+impl From<String> for Nonce {
+    fn from(value: String) -> Self {
+        Self(value.into())
+    }
+}
+
+impl Deref for Nonce {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for Nonce {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<R> AttributeValue<R> for Nonce
+where
+    R: Renderer,
+    R::Element: Clone,
+{
+    type State = <String as AttributeValue<R>>::State;
+
+    fn to_html(self, key: &str, buf: &mut String) {
+        <&str as AttributeValue<R>>::to_html(&self, key, buf);
+    }
+
+    fn to_template(_key: &str, _buf: &mut String) {}
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        key: &str,
+        el: &R::Element,
+    ) -> Self::State {
+        <String as AttributeValue<R>>::hydrate::<FROM_SERVER>(
+            self.to_string(),
+            key,
+            el,
+        )
+    }
+
+    fn build(self, el: &R::Element, key: &str) -> Self::State {
+        <String as AttributeValue<R>>::build(self.to_string(), el, key)
+    }
+
+    fn rebuild(self, key: &str, state: &mut Self::State) {
+        <String as AttributeValue<R>>::rebuild(self.to_string(), key, state);
+    }
+}
+
+/// Returns the nonce for the current request, if one has been provided.
+pub fn use_nonce() -> Option<Nonce> {
+    use_context::<Nonce>()
+}
+
+/// Wraps some JavaScript in an inline `<script>` tag, with the nonce, if any.
+pub fn inline_script(nonce: Option<&Nonce>, js: &str) -> String {
+    match nonce {
+        Some(nonce) => format!("<script nonce=\"{nonce}\">{js}</script>"),
+        None => format!("<script>{js}</script>"),
+    }
+}
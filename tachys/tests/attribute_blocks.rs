@@ -0,0 +1,60 @@
+use tachys::{
+    prelude::*,
+    tachydom::{
+        html::attribute::{id, title},
+        renderer::mock_dom::MockDom,
+        view::{Render, RenderHtml},
+    },
+};
+
+#[test]
+fn spread_block_adds_each_attribute() {
+    let attrs = vec![
+        ("aria-label", "Close".to_string()),
+        ("class", "wide".to_string()),
+    ];
+    let view = view! { <div class="card" {..attrs}>"Hello"</div> };
+    assert_eq!(
+        RenderHtml::<MockDom>::to_html(view),
+        "<div aria-label=\"Close\" class=\"card wide\">Hello</div>"
+    );
+}
+
+#[test]
+fn spread_block_is_diffed_on_rebuild() {
+    let view = |attrs: Vec<(&'static str, String)>| {
+        view! { <div data-static="1" {..attrs}/> }
+    };
+
+    let mut state = Render::<MockDom>::build(view(vec![
+        ("data-a", "1".to_string()),
+        ("data-b", "2".to_string()),
+    ]));
+    view(vec![("data-b", "3".to_string())]).rebuild(&mut state);
+    assert_eq!(state.el.attribute("data-a"), None);
+    assert_eq!(state.el.attribute("data-b").as_deref(), Some("3"));
+    assert_eq!(state.el.attribute("data-static").as_deref(), Some("1"));
+}
+
+#[test]
+fn attribute_block_adds_a_tuple_of_attributes() {
+    let attrs = (id("main"), title("Hello"));
+    let view = view! { <div class="card" {attrs}/> };
+    assert_eq!(
+        RenderHtml::<MockDom>::to_html(view),
+        "<div id=\"main\" title=\"Hello\" class=\"card\"></div>"
+    );
+}
+
+#[test]
+fn attribute_block_adds_an_optional_attribute() {
+    let view = |label: Option<&'static str>| {
+        view! { <div {label.map(title)}/> }
+    };
+
+    let mut state = Render::<MockDom>::build(view(Some("Hello")));
+    assert_eq!(state.el.attribute("title").as_deref(), Some("Hello"));
+
+    view(None).rebuild(&mut state);
+    assert_eq!(state.el.attribute("title"), None);
+}
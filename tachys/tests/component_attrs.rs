@@ -0,0 +1,41 @@
+use std::{cell::Cell, rc::Rc};
+use tachys::{
+    prelude::*,
+    tachydom::{
+        html::{attribute::any_attr::AddAnyAttr, element::Main},
+        renderer::{
+            mock_dom::{document, Element, MockDom},
+            CastFrom, Renderer,
+        },
+        view::{Mountable, Render, RenderHtml},
+    },
+};
+
+#[component]
+fn Card() -> impl RenderHtml<MockDom> + AddAnyAttr<MockDom> {
+    view! { <div class="card">"Card"</div> }
+}
+
+#[test]
+fn call_site_attributes_reach_root_element() {
+    let clicks = Rc::new(Cell::new(0));
+    let view = view! {
+        <Card
+            attr:data-id="card-1"
+            on:click={
+                let clicks = Rc::clone(&clicks);
+                move |_| clicks.set(clicks.get() + 1)
+            }
+        />
+    };
+    let parent = MockDom::create_element(Main);
+    let mut state = view.build();
+    state.mount(&parent, None);
+    let card = Element::cast_from(parent.children()[0].clone())
+        .expect("root of the component should be an element");
+    assert_eq!(card.attribute("data-id").as_deref(), Some("card-1"));
+    assert_eq!(card.attribute("class").as_deref(), Some("card"));
+
+    document().dispatch_event(&card, "click", true);
+    assert_eq!(clicks.get(), 1);
+}
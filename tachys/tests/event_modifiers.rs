@@ -0,0 +1,25 @@
+use std::{cell::Cell, rc::Rc};
+use tachys::{
+    prelude::*,
+    tachydom::{
+        renderer::mock_dom::{document, MockDom},
+        view::Render,
+    },
+};
+
+#[test]
+fn modifiers_apply_to_handlers() {
+    let presses = Rc::new(Cell::new(0));
+    let view = view! {
+        <input on:keydown:enter:prevent={
+            let presses = Rc::clone(&presses);
+            move |_| presses.set(presses.get() + 1)
+        }/>
+    };
+    let input = Render::<MockDom>::build(view).el;
+
+    assert!(document().dispatch_key_event(&input, "keydown", "Escape"));
+    assert_eq!(presses.get(), 0);
+    assert!(!document().dispatch_key_event(&input, "keydown", "Enter"));
+    assert_eq!(presses.get(), 1);
+}
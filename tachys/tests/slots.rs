@@ -0,0 +1,117 @@
+use tachys::{
+    prelude::*,
+    tachydom::{
+        html::element::Main,
+        renderer::{
+            mock_dom::{Element, MockDom},
+            CastFrom, Renderer,
+        },
+        view::{Mountable, Render, RenderHtml},
+    },
+};
+
+#[slot]
+struct Title {
+    text: &'static str,
+}
+
+#[slot]
+struct Subtitle {
+    text: &'static str,
+}
+
+#[slot]
+struct Tab {
+    label: &'static str,
+}
+
+#[component]
+fn Tabs(
+    title: Title,
+    #[prop(optional)] subtitle: Option<Subtitle>,
+    #[prop(default = vec![], into)] tab: Vec<Tab>,
+    #[prop(optional)] footer: Option<Subtitle>,
+) -> impl RenderHtml<MockDom> {
+    let tabs = tab
+        .into_iter()
+        .map(|tab| tab.label)
+        .collect::<Vec<_>>()
+        .join(",");
+    view! {
+        <div>
+            <h1>{title.text}</h1>
+            <h2>{subtitle.map(|subtitle| subtitle.text)}</h2>
+            <nav>{tabs}</nav>
+            <footer>{footer.map(|footer| footer.text)}</footer>
+        </div>
+    }
+}
+
+// mounts the view, and returns the text of each child of its root element
+fn texts(view: impl Render<MockDom>) -> Vec<String> {
+    let parent = MockDom::create_element(Main);
+    let mut state = view.build();
+    state.mount(&parent, None);
+    let root = Element::cast_from(parent.children()[0].clone())
+        .expect("root of the component should be an element");
+    root.children()
+        .into_iter()
+        .map(|child| child.text_content())
+        .collect()
+}
+
+#[test]
+fn single_slot_is_passed_to_its_prop() {
+    let view = view! {
+        <Tabs>
+            <Title slot text="Settings"/>
+        </Tabs>
+    };
+    assert_eq!(texts(view), ["Settings", "", "", ""]);
+}
+
+#[test]
+fn optional_slot_is_passed_when_present() {
+    let view = view! {
+        <Tabs>
+            <Title slot text="Settings"/>
+            <Subtitle slot text="Account"/>
+        </Tabs>
+    };
+    assert_eq!(texts(view), ["Settings", "Account", "", ""]);
+}
+
+#[test]
+fn repeated_slots_are_collected_into_a_vec() {
+    let one = view! {
+        <Tabs>
+            <Title slot text="Settings"/>
+            <Tab slot label="General"/>
+        </Tabs>
+    };
+    assert_eq!(texts(one), ["Settings", "", "General", ""]);
+
+    let several = view! {
+        <Tabs>
+            <Tab slot label="General"/>
+            <Title slot text="Settings"/>
+            <Tab slot label="Privacy"/>
+            <Tab slot label="Billing"/>
+        </Tabs>
+    };
+    assert_eq!(
+        texts(several),
+        ["Settings", "", "General,Privacy,Billing", ""]
+    );
+}
+
+#[test]
+fn renamed_slot_is_passed_to_the_named_prop() {
+    let view = view! {
+        <Tabs>
+            <Title slot text="Settings"/>
+            <Subtitle slot:footer text="Saved"/>
+        </Tabs>
+    };
+    assert_eq!(texts(view), ["Settings", "", "", "Saved"]);
+}